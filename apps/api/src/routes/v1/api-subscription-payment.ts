@@ -9,7 +9,7 @@ import { DEFAULT_NETWORK } from "@neptu/shared";
 import {
   createSolanaClient,
   createNeptuPrograms,
-  deriveAssociatedTokenAddress,
+  derivePayerAccounts,
   buildPayWithSolInstruction,
   buildPayWithNeptuInstruction,
   getLatestBlockhash,
@@ -132,18 +132,12 @@ apiSubscriptionPaymentRoutes.post(
         );
       }
 
-      const userNeptuAccount = await deriveAssociatedTokenAddress(
-        userAddress,
-        programs.mintPda
-      );
-
-      instruction = buildPayWithSolInstruction(
+      instruction = buildPayWithSolInstruction({
         programs,
-        userAddress,
-        treasuryAddress,
-        userNeptuAccount,
-        "POTENSI"
-      );
+        user: userAddress,
+        readingType: "POTENSI",
+        ...(await derivePayerAccounts(programs, userAddress)),
+      });
     } else if (paymentMethod === "neptu") {
       amount = plan.priceNeptu ?? 0;
       if (amount <= 0) {
@@ -156,18 +150,13 @@ apiSubscriptionPaymentRoutes.post(
         );
       }
 
-      const userNeptuAccount = await deriveAssociatedTokenAddress(
-        userAddress,
-        programs.mintPda
-      );
-
-      instruction = buildPayWithNeptuInstruction(
+      instruction = buildPayWithNeptuInstruction({
         programs,
-        userAddress,
-        treasuryAddress,
-        userNeptuAccount,
-        "POTENSI"
-      );
+        user: userAddress,
+        readingType: "POTENSI",
+        ...(await derivePayerAccounts(programs, userAddress)),
+        ecosystemPool: treasuryAddress,
+      });
     } else {
       return c.json(
         { success: false, error: "Payment method not supported yet" },
@@ -319,18 +308,12 @@ apiSubscriptionPaymentRoutes.post(
         );
       }
 
-      const userNeptuAccount = await deriveAssociatedTokenAddress(
-        userAddress,
-        programs.mintPda
-      );
-
-      instruction = buildPayWithSolInstruction(
+      instruction = buildPayWithSolInstruction({
         programs,
-        userAddress,
-        treasuryAddress,
-        userNeptuAccount,
-        "POTENSI"
-      );
+        user: userAddress,
+        readingType: "POTENSI",
+        ...(await derivePayerAccounts(programs, userAddress)),
+      });
     } else if (paymentMethod === "neptu") {
       amount = pack.priceNeptu ?? 0;
       if (amount <= 0) {
@@ -343,18 +326,13 @@ apiSubscriptionPaymentRoutes.post(
         );
       }
 
-      const userNeptuAccount = await deriveAssociatedTokenAddress(
-        userAddress,
-        programs.mintPda
-      );
-
-      instruction = buildPayWithNeptuInstruction(
+      instruction = buildPayWithNeptuInstruction({
         programs,
-        userAddress,
-        treasuryAddress,
-        userNeptuAccount,
-        "POTENSI"
-      );
+        user: userAddress,
+        readingType: "POTENSI",
+        ...(await derivePayerAccounts(programs, userAddress)),
+        ecosystemPool: treasuryAddress,
+      });
     } else {
       return c.json(
         { success: false, error: "Payment method not supported yet" },
@@ -6,6 +6,7 @@ import {
   createNeptuPrograms,
   deriveAssociatedTokenAddress,
   deriveClaimRecordPda,
+  deriveUserProfilePda,
   buildClaimRewardsInstruction,
   getLatestBlockhash,
   address,
@@ -31,9 +32,13 @@ type Env = {
   Bindings: {
     SOLANA_NETWORK?: string;
     SOLANA_RPC_URL?: string;
+    CLAIM_SIGNER_ADDRESS?: string;
   };
 };
 
+// Claim authorizations expire after this many seconds
+const CLAIM_TTL_SECONDS = 300;
+
 const programsCache: Map<string, NeptuPrograms> = new Map();
 
 async function getPrograms(
@@ -71,6 +76,14 @@ paymentClaimRoutes.post(
     const solanaClient = createSolanaClient(network, c.env?.SOLANA_RPC_URL);
 
     try {
+      const claimSignerAddr = c.env?.CLAIM_SIGNER_ADDRESS;
+      if (!claimSignerAddr) {
+        return c.json(
+          { success: false, error: "Claim signer address not configured" },
+          500
+        );
+      }
+
       const programs = await getPrograms(network);
       const userAddress = address(walletAddress);
 
@@ -84,6 +97,15 @@ paymentClaimRoutes.post(
         programs.economyProgramId
       );
 
+      const userProfilePda = await deriveUserProfilePda(
+        userAddress,
+        programs.economyProgramId
+      );
+
+      const expiresAt = BigInt(
+        Math.floor(Date.now() / 1000) + CLAIM_TTL_SECONDS
+      );
+
       // TODO: Generate backend signature for claim authorization
       const authSignature = new Uint8Array(64);
 
@@ -92,8 +114,11 @@ paymentClaimRoutes.post(
         user: userAddress,
         userNeptuAccount,
         claimRecordPda,
+        userProfilePda,
+        claimSigner: address(claimSignerAddr),
         amount: BigInt(Math.round(amount * 1_000_000)),
         nonce: BigInt(nonce),
+        expiresAt,
         signature: authSignature,
       });
 
@@ -135,6 +160,7 @@ paymentClaimRoutes.post(
         claim: {
           amount,
           nonce,
+          expiresAt: Number(expiresAt),
         },
       });
     } catch (error) {
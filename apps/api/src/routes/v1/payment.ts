@@ -9,7 +9,7 @@ import {
 import {
   createSolanaClient,
   createNeptuPrograms,
-  deriveAssociatedTokenAddress,
+  derivePayerAccounts,
   buildPayWithSolInstruction,
   buildPayWithNeptuInstruction,
   getLatestBlockhash,
@@ -51,7 +51,6 @@ const getSolanaClient = (env?: Env["Bindings"]) => {
 };
 
 // Get addresses from env bindings
-const getTreasuryAddress = (env?: Env["Bindings"]) => env?.NEPTU_TREASURY || "";
 const getEcosystemPoolAddress = (env?: Env["Bindings"]) =>
   env?.NEPTU_ECOSYSTEM_POOL || "";
 const getSudigitalTreasuryAddress = (env?: Env["Bindings"]) =>
@@ -100,30 +99,16 @@ paymentRoutes.post(
     const solanaClient = getSolanaClient(c.env);
 
     try {
-      const treasuryAddr = getTreasuryAddress(c.env);
-      if (!treasuryAddr) {
-        return c.json(
-          { success: false, error: "Treasury address not configured" },
-          500
-        );
-      }
-
       const programs = await getPrograms(network);
       const userAddress = address(walletAddress);
-      const treasuryAddress = address(treasuryAddr);
-
-      const userNeptuAccount = await deriveAssociatedTokenAddress(
-        userAddress,
-        programs.mintPda
-      );
+      const payerAccounts = await derivePayerAccounts(programs, userAddress);
 
-      const instruction = buildPayWithSolInstruction(
+      const instruction = buildPayWithSolInstruction({
         programs,
-        userAddress,
-        treasuryAddress,
-        userNeptuAccount,
-        readingType as ReadingType
-      );
+        user: userAddress,
+        readingType: readingType as ReadingType,
+        ...payerAccounts,
+      });
 
       // Use client-provided blockhash if available, otherwise fetch from RPC
       const { blockhash, lastValidBlockHeight } =
@@ -198,18 +183,15 @@ paymentRoutes.post(
       const userAddress = address(walletAddress);
       const ecosystemPoolAddress = address(ecosystemPoolAddr);
 
-      const userNeptuAccount = await deriveAssociatedTokenAddress(
-        userAddress,
-        programs.mintPda
-      );
+      const payerAccounts = await derivePayerAccounts(programs, userAddress);
 
-      const instruction = buildPayWithNeptuInstruction(
+      const instruction = buildPayWithNeptuInstruction({
         programs,
-        userAddress,
-        userNeptuAccount,
-        ecosystemPoolAddress,
-        readingType as ReadingType
-      );
+        user: userAddress,
+        readingType: readingType as ReadingType,
+        ...payerAccounts,
+        ecosystemPool: ecosystemPoolAddress,
+      });
 
       // Use client-provided blockhash if available, otherwise fetch from RPC
       const { blockhash, lastValidBlockHeight } =
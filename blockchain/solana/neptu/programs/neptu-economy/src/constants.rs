@@ -0,0 +1,78 @@
+pub const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+pub const NEPTU_DECIMALS: u64 = 1_000_000; // 6 decimals
+
+/// Burn rate: 50% = 5000 basis points
+pub const BURN_RATE_BPS: u64 = 5000;
+pub const BPS_DENOMINATOR: u64 = 10000;
+
+/// Claim nonce replay window: 256 nonces tracked as 4 x u64 bitmap words
+pub const NONCE_WINDOW: u64 = 256;
+pub const NONCE_WINDOW_WORDS: usize = (NONCE_WINDOW / 64) as usize;
+
+/// Max approvers on the treasury vault (approvals tracked as a u16 bitmask)
+pub const MAX_TREASURY_APPROVERS: usize = 10;
+
+/// Max members of the built-in multisig (approvals tracked as a u16 bitmask)
+pub const MAX_MULTISIG_MEMBERS: usize = 10;
+
+/// Max wallets holding roles
+pub const MAX_ROLE_MEMBERS: usize = 32;
+
+/// Max staking tiers
+pub const MAX_STAKE_TIERS: usize = 4;
+
+/// Max loyalty tiers
+pub const MAX_LOYALTY_TIERS: usize = 4;
+
+/// Max line items in a batch checkout
+pub const MAX_BATCH_ITEMS: usize = 8;
+
+/// Max bulk discount tiers for reading credits
+pub const MAX_CREDIT_TIERS: usize = 4;
+
+/// Session key permissions (bitmask over payment instructions)
+pub const SESSION_PAY_WITH_SOL: u8 = 1 << 0;
+pub const SESSION_PAY_WITH_NEPTU: u8 = 1 << 1;
+
+/// Accounts whose onboarding rent the sponsorship vault reimburses (once per user)
+pub const SPONSOR_NEPTU_ACCOUNT: u8 = 1 << 0;
+pub const SPONSOR_CLAIM_RECORD: u8 = 1 << 1;
+pub const SPONSOR_PROFILE: u8 = 1 << 2;
+pub const SPONSOR_SPONSORED_RENT: u8 = 1 << 3;
+
+/// Max lifetime of a session key
+pub const MAX_SESSION_DURATION: i64 = 7 * 86_400;
+
+/// Fixed-point scale of the staking yield-per-share accumulator
+pub const YIELD_PRECISION: u128 = 1_000_000_000_000;
+
+/// Default pricing (used for initialization)
+pub mod defaults {
+    pub const POTENSI_SOL: u64 = 10_000_000; // 0.01 SOL
+    pub const PELUANG_SOL: u64 = 1_000_000; // 0.001 SOL
+    pub const AI_CHAT_SOL: u64 = 2_000_000; // 0.002 SOL
+    pub const COMPATIBILITY_SOL: u64 = 5_000_000; // 0.005 SOL
+
+    pub const POTENSI_NEPTU: u64 = 10_000_000; // 10 NEPTU
+    pub const PELUANG_NEPTU: u64 = 1_000_000; // 1 NEPTU
+    pub const AI_CHAT_NEPTU: u64 = 2_000_000; // 2 NEPTU
+    pub const COMPATIBILITY_NEPTU: u64 = 5_000_000; // 5 NEPTU
+
+    pub const EPOCH_DURATION: i64 = 86_400; // 1 day
+    pub const EPOCH_GLOBAL_CAP: u64 = 1_000_000_000_000; // 1M NEPTU per epoch
+    pub const EPOCH_USER_CAP: u64 = 1_000_000_000; // 1K NEPTU per user per epoch
+
+    pub const REWARD_CURVE_REFERENCE: u64 = 300_000_000_000_000; // 300M NEPTU (30% of supply)
+    pub const REWARD_CURVE_HALVING_STEP: u64 = 75_000_000_000_000; // halve every 75M NEPTU emitted
+    pub const REWARD_CURVE_FLOOR_BPS: u16 = 625; // never below 1/16 of the base reward
+
+    pub const ESCROW_TIMEOUT: i64 = 86_400; // 1 day to deliver an escrowed reading
+    pub const METERING_NOTICE_PERIOD: i64 = 3 * 86_400; // 3 days before unused deposit can leave
+
+    pub const MAX_PRICE_AGE: i64 = 60; // seconds
+    pub const MAX_PRICE_CONF_BPS: u16 = 200; // confidence within 2% of price
+
+    pub const MAX_SOL_USAGE_RATE: u64 = 2_000_000; // 0.002 SOL per metered unit
+    pub const MAX_NEPTU_USAGE_RATE: u64 = 2_000_000; // 2 NEPTU per metered unit
+}
@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum NeptuError {
+    #[msg("Invalid amount")]
+    InvalidAmount,
+    #[msg("Nonce already used")]
+    NonceAlreadyUsed,
+    #[msg("Arithmetic overflow")]
+    Overflow,
+    #[msg("Rewards pool balance is insufficient for this reward")]
+    InsufficientBalance,
+    #[msg("Unauthorized")]
+    Unauthorized,
+    #[msg("Nonce is older than the replay window")]
+    NonceOutOfWindow,
+    #[msg("Claim authorization has expired")]
+    AuthorizationExpired,
+    #[msg("Claim exceeds the emission budget for this epoch")]
+    EmissionBudgetExceeded,
+    #[msg("Pending reward account required to record an IOU")]
+    PendingRewardAccountMissing,
+    #[msg("Vault balance is insufficient")]
+    InsufficientVaultBalance,
+    #[msg("Ecosystem vault is not above the rebalance threshold")]
+    RebalanceNotNeeded,
+    #[msg("Approvers must be unique and between 1 and 10")]
+    InvalidApprovers,
+    #[msg("Threshold must be between 1 and the number of approvers")]
+    InvalidThreshold,
+    #[msg("Already approved")]
+    AlreadyApproved,
+    #[msg("Not enough approvals")]
+    NotEnoughApprovals,
+    #[msg("Proposal already executed")]
+    ProposalAlreadyExecuted,
+    #[msg("Proposal was created under a previous approver set")]
+    ProposalStale,
+    #[msg("Withdrawal exceeds the spending limit for this period")]
+    SpendingLimitExceeded,
+    #[msg("Payments, claims and outgoing transfers are paused")]
+    ProgramPaused,
+    #[msg("Role registry is full")]
+    TooManyRoleMembers,
+    #[msg("Wallet holds no roles")]
+    RoleNotFound,
+    #[msg("NEPTU balance below the proposal threshold")]
+    BelowProposalThreshold,
+    #[msg("Voting period has ended")]
+    VotingClosed,
+    #[msg("Voting period has not ended")]
+    VotingStillOpen,
+    #[msg("Execution delay has not elapsed")]
+    ExecutionDelayNotElapsed,
+    #[msg("Quorum not reached")]
+    QuorumNotReached,
+    #[msg("Proposal did not pass")]
+    ProposalRejected,
+    #[msg("Stake tiers must be ascending, at most 4, with valid basis points")]
+    InvalidStakeTiers,
+    #[msg("Stake vault does not belong to the staking pool")]
+    InvalidStakeVault,
+    #[msg("Loyalty tiers must be ascending, at most 4, with valid basis points")]
+    InvalidLoyaltyTiers,
+    #[msg("Gift beneficiary or reward recipient is invalid")]
+    InvalidBeneficiary,
+    #[msg("Escrowed order has not timed out yet")]
+    OrderNotExpired,
+    #[msg("Token accounts required for a NEPTU payment are missing")]
+    TokenAccountMissing,
+    #[msg("Credit tiers must be strictly ascending, at most 4, with valid basis points")]
+    InvalidCreditTiers,
+    #[msg("Not enough credits")]
+    InsufficientCredits,
+    #[msg("Usage period is empty or overlaps an already settled period")]
+    InvalidUsagePeriod,
+    #[msg("Withdrawal was not requested or the notice period has not elapsed")]
+    NoticePeriodActive,
+    #[msg("Invalid session expiry or permissions")]
+    InvalidSession,
+    #[msg("Session key has expired")]
+    SessionExpired,
+    #[msg("Session key is not allowed to make this payment")]
+    SessionNotAllowed,
+    #[msg("Session spending limit exceeded")]
+    SessionLimitExceeded,
+    #[msg("Batch must have 1 to 8 line items with non-zero quantities")]
+    InvalidBatch,
+    #[msg("Price feed does not match the configured feed or is out of order")]
+    InvalidPriceFeed,
+    #[msg("Token is not accepted for this reading")]
+    PaymentMintNotAccepted,
+    #[msg("Payment was already refunded")]
+    AlreadyRefunded,
+    #[msg("Cannot refer yourself")]
+    SelfReferral,
+    #[msg("Referrer does not match the payer's referral")]
+    InvalidReferrer,
+    #[msg("Account already uses the current layout")]
+    AlreadyMigrated,
+    #[msg("Wallet does not hold this role")]
+    RoleNotHeld,
+    #[msg("Payer has a bound referrer; the referrer accounts are required")]
+    ReferralAccountsMissing,
+    #[msg("Accounts needed to reverse this payment are missing")]
+    RefundAccountsMissing,
+    #[msg("This reading is priced in USD; the configured price feed is required")]
+    PriceFeedMissing,
+    #[msg("A metered withdrawal is pending; no new usage periods can be settled")]
+    WithdrawalPending,
+    #[msg("Usage rate exceeds the configured cap")]
+    UsageRateTooHigh,
+    #[msg("Hybrid payments need both receipt accounts or neither")]
+    ReceiptAccountsMissing,
+}
@@ -0,0 +1,331 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+
+/// Emitted when a reward hits the epoch emission budget; backend should queue claims
+/// for the next epoch
+#[event]
+pub struct EmissionBudgetExhausted {
+    pub scope: BudgetScope,
+    pub epoch: u64,
+    pub user: Pubkey,
+    pub requested: u64,
+    pub granted: u64,
+    pub next_epoch_at: i64,
+}
+
+/// Emitted when part of a reward is recorded as an IOU
+#[event]
+pub struct RewardDeferred {
+    pub user: Pubkey,
+    pub amount: u64,
+    pub pending_total: u64,
+}
+
+/// Emitted on every ecosystem <-> rewards vault transfer
+#[event]
+pub struct PoolsRebalanced {
+    pub direction: RebalanceDirection,
+    pub trigger: RebalanceTrigger,
+    pub amount: u64,
+    pub ecosystem_balance: u64,
+    pub rewards_balance: u64,
+}
+
+#[event]
+pub struct AuthorityChanged {
+    pub previous: Pubkey,
+    pub new_authority: Pubkey,
+}
+
+#[event]
+pub struct RoleChanged {
+    pub member: Pubkey,
+    pub role: Role,
+    pub granted: bool,
+}
+
+#[event]
+pub struct PauseChanged {
+    pub paused: bool,
+    pub by: Pubkey,
+}
+
+#[event]
+pub struct MultisigProposalCreated {
+    pub id: u64,
+    pub proposer: Pubkey,
+    pub program_id: Pubkey,
+}
+
+#[event]
+pub struct MultisigProposalApproved {
+    pub id: u64,
+    pub member: Pubkey,
+    pub approvals: u8,
+}
+
+#[event]
+pub struct MultisigProposalExecuted {
+    pub id: u64,
+    pub program_id: Pubkey,
+}
+
+#[event]
+pub struct GovernanceProposalCreated {
+    pub id: u64,
+    pub proposer: Pubkey,
+    pub change: ParameterChange,
+    pub voting_ends_at: i64,
+}
+
+#[event]
+pub struct VoteCast {
+    pub proposal_id: u64,
+    pub voter: Pubkey,
+    pub support: bool,
+    pub amount: u64,
+}
+
+#[event]
+pub struct GovernanceProposalExecuted {
+    pub id: u64,
+    pub change: ParameterChange,
+    pub votes_for: u64,
+    pub votes_against: u64,
+}
+
+#[event]
+pub struct ReceiptIssued {
+    pub receipt: Pubkey,
+    pub payer: Pubkey,
+    pub beneficiary: Pubkey,
+    pub reading_type: ReadingType,
+    pub currency: PaymentCurrency,
+    pub amount: u64,
+}
+
+#[event]
+pub struct RentSponsored {
+    pub user: Pubkey,
+    pub sponsor: Pubkey,
+    pub amount: u64,
+    /// Lamports reimbursed for this user so far
+    pub user_total: u64,
+}
+
+#[event]
+pub struct SessionCreated {
+    pub owner: Pubkey,
+    pub session_key: Pubkey,
+    pub expires_at: i64,
+    pub allowed_instructions: u8,
+    pub allowed_readings: u8,
+    pub sol_limit: u64,
+    pub neptu_limit: u64,
+}
+
+#[event]
+pub struct SessionRevoked {
+    pub owner: Pubkey,
+    pub session_key: Pubkey,
+    pub sol_spent: u64,
+    pub neptu_spent: u64,
+}
+
+#[event]
+pub struct MeteredDeposit {
+    pub user: Pubkey,
+    pub currency: PaymentCurrency,
+    pub amount: u64,
+    pub balance: u64,
+}
+
+#[event]
+pub struct UsageSettled {
+    pub user: Pubkey,
+    pub currency: PaymentCurrency,
+    pub units: u64,
+    pub rate: u64,
+    pub period_start: i64,
+    pub period_end: i64,
+    pub charged: u64,
+    /// Usage not covered by the deposit
+    pub unpaid: u64,
+    pub balance: u64,
+}
+
+#[event]
+pub struct MeteredWithdrawal {
+    pub user: Pubkey,
+    pub currency: PaymentCurrency,
+    pub amount: u64,
+}
+
+#[event]
+pub struct CreditsPurchased {
+    pub user: Pubkey,
+    pub reading_type: ReadingType,
+    pub currency: PaymentCurrency,
+    pub quantity: u32,
+    pub amount: u64,
+    pub balance: u32,
+}
+
+#[event]
+pub struct CreditsConsumed {
+    pub user: Pubkey,
+    pub reading_type: ReadingType,
+    pub currency: PaymentCurrency,
+    pub quantity: u32,
+    pub amount: u64,
+    pub balance: u32,
+}
+
+/// Receipt of one line item of a batch checkout
+#[event]
+pub struct LineItemReceipt {
+    pub user: Pubkey,
+    pub index: u8,
+    pub reading_type: ReadingType,
+    pub quantity: u16,
+    pub currency: PaymentCurrency,
+    pub amount: u64,
+}
+
+#[event]
+pub struct TokenPaymentReceived {
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub reading_type: ReadingType,
+    pub amount: u64,
+    pub neptu_reward: u64,
+}
+
+#[event]
+pub struct PaymentRefunded {
+    pub receipt: Pubkey,
+    pub payer: Pubkey,
+    pub currency: PaymentCurrency,
+    pub amount: u64,
+    /// Burned and staker NEPTU paid back from the rewards pool
+    pub reissued: u64,
+    /// NEPTU reward returned to the rewards pool
+    pub reward_returned: u64,
+    /// Referral bonus withheld from the referrer's next bonuses
+    pub referral_clawback: u64,
+    pub by: Pubkey,
+}
+
+#[event]
+pub struct OrderCreated {
+    pub order: Pubkey,
+    pub user: Pubkey,
+    pub reading_type: ReadingType,
+    pub currency: PaymentCurrency,
+    pub amount: u64,
+    pub expires_at: i64,
+}
+
+/// Emitted when an escrowed order is released (fulfilled) or reclaimed
+#[event]
+pub struct OrderSettled {
+    pub order: Pubkey,
+    pub user: Pubkey,
+    pub fulfilled: bool,
+}
+
+#[event]
+pub struct CompatibilityRequestOpened {
+    pub request: Pubkey,
+    pub initiator: Pubkey,
+    pub partner: Pubkey,
+    pub request_id: u64,
+    pub price: u64,
+    pub initiator_share: u64,
+}
+
+#[event]
+pub struct CompatibilityRequestClosed {
+    pub request: Pubkey,
+    pub initiator: Pubkey,
+    pub partner: Pubkey,
+    pub accepted: bool,
+}
+
+#[event]
+pub struct LoyaltyTierChanged {
+    pub user: Pubkey,
+    pub tier: Option<u8>,
+}
+
+#[event]
+pub struct ReferralBound {
+    pub referee: Pubkey,
+    pub referrer: Pubkey,
+}
+
+#[event]
+pub struct ReferralBonusPaid {
+    pub referrer: Pubkey,
+    pub referee: Pubkey,
+    pub amount: u64,
+    pub total_earned: u64,
+}
+
+#[event]
+pub struct Staked {
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub total: u64,
+    pub lock_until: i64,
+    pub tier: Option<u8>,
+}
+
+#[event]
+pub struct StakeYieldClaimed {
+    pub owner: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct Unstaked {
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub penalty_burned: u64,
+    pub remaining: u64,
+}
+
+/// Emitted when SOL revenue lands in the treasury vault
+#[event]
+pub struct TreasuryDeposited {
+    pub from: Pubkey,
+    pub amount: u64,
+    pub total_received: u64,
+}
+
+#[event]
+pub struct WithdrawalProposed {
+    pub id: u64,
+    pub proposer: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub memo: [u8; 32],
+    pub mint: Option<Pubkey>,
+}
+
+#[event]
+pub struct WithdrawalApproved {
+    pub id: u64,
+    pub approver: Pubkey,
+    pub approvals: u8,
+}
+
+#[event]
+pub struct WithdrawalExecuted {
+    pub id: u64,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub period_spent: u64,
+    pub total_withdrawn: u64,
+}
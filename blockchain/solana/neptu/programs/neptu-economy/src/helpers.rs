@@ -0,0 +1,541 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::associated_token::{self, AssociatedToken};
+use anchor_spl::token::{burn, transfer, Burn, Mint, Token, TokenAccount, Transfer};
+
+use crate::{constants::*, errors::NeptuError, events::*, state::*};
+
+/// Benefits of the caller's stake tier (zero tier when not staking)
+pub(crate) fn stake_benefits(
+    pool: Option<&StakingPool>,
+    position: &Option<Account<StakePosition>>,
+    now: i64,
+) -> StakeTier {
+    match (pool, position) {
+        (Some(pool), Some(position)) => pool.tier_for(position, now),
+        _ => StakeTier::default(),
+    }
+}
+
+/// SOL amount and reward for a hybrid payment of `neptu_amount` out of `neptu_price`
+/// The SOL share rounds up so split payments never undercharge; the reward rounds down
+pub(crate) fn hybrid_shares(
+    neptu_price: u64,
+    sol_price: u64,
+    sol_reward: u64,
+    neptu_amount: u64,
+) -> Result<(u64, u64)> {
+    require!(
+        neptu_price > 0 && neptu_amount <= neptu_price,
+        NeptuError::InvalidAmount
+    );
+    let remaining = (neptu_price - neptu_amount) as u128;
+    let sol_amount = u64::try_from((sol_price as u128 * remaining).div_ceil(neptu_price as u128))
+        .map_err(|_| error!(NeptuError::Overflow))?;
+    let reward = u64::try_from(sol_reward as u128 * remaining / neptu_price as u128)
+        .map_err(|_| error!(NeptuError::Overflow))?;
+    Ok((sol_amount, reward))
+}
+
+/// Where a NEPTU payment went; `recycled` includes the stakers' `staker_yield` share
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NeptuSplit {
+    pub burned: u64,
+    pub recycled: u64,
+    pub staker_yield: u64,
+}
+
+/// Token accounts a NEPTU payment moves through
+pub(crate) struct NeptuPaymentAccounts<'a, 'info> {
+    pub(crate) token_program: &'a Program<'info, Token>,
+    pub(crate) neptu_mint: &'a Account<'info, Mint>,
+    /// Payer's account, or a program vault holding the payment
+    pub(crate) from: &'a Account<'info, TokenAccount>,
+    /// Owner or delegate of `from`
+    pub(crate) authority: AccountInfo<'info>,
+    pub(crate) ecosystem_pool: &'a Account<'info, TokenAccount>,
+    /// Absent until staking is initialized; no yield is split off then
+    pub(crate) stake_vault: Option<&'a Account<'info, TokenAccount>>,
+}
+
+/// Burn and recycle a NEPTU payment; the stakers' yield share goes to the stake vault
+pub(crate) fn settle_neptu_payment(
+    config: &PricingConfig,
+    state: &mut EconomyState,
+    staking_pool: Option<&mut StakingPool>,
+    accounts: NeptuPaymentAccounts,
+    amount: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<NeptuSplit> {
+    let (burned, recycled) = config.split_neptu_payment(amount)?;
+    let staker_yield = match (staking_pool, accounts.stake_vault) {
+        (Some(staking_pool), Some(_)) => staking_pool.distribute_yield(recycled)?,
+        _ => 0,
+    };
+    state.total_neptu_burned = state
+        .total_neptu_burned
+        .checked_add(burned)
+        .ok_or(NeptuError::Overflow)?;
+    state.total_neptu_recycled = state
+        .total_neptu_recycled
+        .checked_add(recycled)
+        .ok_or(NeptuError::Overflow)?;
+
+    let token_program = accounts.token_program.to_account_info();
+    if burned > 0 {
+        burn(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                Burn {
+                    mint: accounts.neptu_mint.to_account_info(),
+                    from: accounts.from.to_account_info(),
+                    authority: accounts.authority.clone(),
+                },
+                signer_seeds,
+            ),
+            burned,
+        )?;
+        msg!("Burned {} NEPTU", burned);
+    }
+    if recycled > staker_yield {
+        transfer(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                Transfer {
+                    from: accounts.from.to_account_info(),
+                    to: accounts.ecosystem_pool.to_account_info(),
+                    authority: accounts.authority.clone(),
+                },
+                signer_seeds,
+            ),
+            recycled - staker_yield,
+        )?;
+        msg!(
+            "Recycled {} NEPTU to ecosystem pool",
+            recycled - staker_yield
+        );
+    }
+    if let Some(stake_vault) = accounts.stake_vault.filter(|_| staker_yield > 0) {
+        transfer(
+            CpiContext::new_with_signer(
+                token_program,
+                Transfer {
+                    from: accounts.from.to_account_info(),
+                    to: stake_vault.to_account_info(),
+                    authority: accounts.authority,
+                },
+                signer_seeds,
+            ),
+            staker_yield,
+        )?;
+        msg!("Distributed {} NEPTU yield to stakers", staker_yield);
+    }
+
+    Ok(NeptuSplit {
+        burned,
+        recycled,
+        staker_yield,
+    })
+}
+
+/// Add `amount` to the current period's spending, starting a new period when due
+pub(crate) fn charge_spending_limit(
+    period_start: &mut i64,
+    period_spent: &mut u64,
+    limit: u64,
+    period_duration: i64,
+    amount: u64,
+    now: i64,
+) -> Result<()> {
+    if now >= period_start.saturating_add(period_duration) {
+        *period_start = now;
+        *period_spent = 0;
+    }
+    let spent = period_spent
+        .checked_add(amount)
+        .ok_or(NeptuError::Overflow)?;
+    require!(spent <= limit, NeptuError::SpendingLimitExceeded);
+    *period_spent = spent;
+    Ok(())
+}
+
+/// Check an M-of-N member set: 1..=max distinct keys and 1..=len threshold
+pub(crate) fn validate_member_set(members: &[Pubkey], threshold: u8, max: usize) -> Result<()> {
+    require!(
+        !members.is_empty() && members.len() <= max,
+        NeptuError::InvalidApprovers
+    );
+    for (i, m) in members.iter().enumerate() {
+        require!(!members[..i].contains(m), NeptuError::InvalidApprovers);
+    }
+    require!(
+        threshold > 0 && usize::from(threshold) <= members.len(),
+        NeptuError::InvalidThreshold
+    );
+    Ok(())
+}
+
+/// Run a SOL payment reward through the curve, shortfall policy and emission budget
+/// Returns the NEPTU to transfer now; any deferred part is recorded as an IOU
+pub(crate) fn grant_payment_reward(
+    state: &mut EconomyState,
+    claim_record: &mut ClaimRecord,
+    pending_reward: &mut Option<Account<PendingReward>>,
+    reward: u64,
+    pool_balance: u64,
+    now: i64,
+) -> Result<u64> {
+    let user = claim_record.user;
+    let curve_reward = state.reward_curve.apply(reward, pool_balance)?;
+    let base_reward = state.shortfall_policy.cover(curve_reward, pool_balance)?;
+    let grant = state.reserve_emission(claim_record, base_reward, now)?;
+    if let Some(scope) = grant.exhausted {
+        emit!(EmissionBudgetExhausted {
+            scope,
+            epoch: state.current_epoch,
+            user,
+            requested: base_reward,
+            granted: grant.granted,
+            next_epoch_at: state.next_epoch_at(),
+        });
+    }
+    let neptu_reward = grant.granted.min(pool_balance);
+    let owed = grant.granted - neptu_reward;
+    state.total_neptu_rewarded = state
+        .total_neptu_rewarded
+        .checked_add(neptu_reward)
+        .ok_or(NeptuError::Overflow)?;
+
+    if owed > 0 {
+        let pending = pending_reward
+            .as_mut()
+            .ok_or(NeptuError::PendingRewardAccountMissing)?;
+        pending.user = user;
+        pending.record(owed)?;
+        emit!(RewardDeferred {
+            user,
+            amount: owed,
+            pending_total: pending.amount,
+        });
+        msg!("Recorded {} NEPTU reward IOU", owed);
+    }
+    Ok(neptu_reward)
+}
+
+/// Reimburse the approved sponsor for onboarding rent of accounts created by this instruction
+/// `accounts` lists (flag, space, created); no-op without both sponsorship accounts
+pub(crate) fn sponsor_rent(
+    sponsorship: &mut Option<Account<RentSponsorship>>,
+    sponsored: &mut Option<Account<SponsoredRent>>,
+    fee_payer: &AccountInfo,
+    user: Pubkey,
+    accounts: &[(u8, usize, bool)],
+) -> Result<u64> {
+    let Some(sponsored) = sponsored else {
+        return Ok(0);
+    };
+    let sponsored_created = sponsored.user == Pubkey::default();
+    sponsored.user = user;
+    let Some(sponsorship) = sponsorship else {
+        return Ok(0);
+    };
+    require_keys_eq!(
+        fee_payer.key(),
+        sponsorship.sponsor,
+        NeptuError::Unauthorized
+    );
+
+    let rent = Rent::get()?;
+    let mut owed: u64 = 0;
+    for &(flag, space, created) in accounts.iter().chain(&[(
+        SPONSOR_SPONSORED_RENT,
+        SponsoredRent::SIZE,
+        sponsored_created,
+    )]) {
+        if created && sponsored.accounts & flag == 0 {
+            sponsored.accounts |= flag;
+            owed = owed
+                .checked_add(rent.minimum_balance(space))
+                .ok_or(NeptuError::Overflow)?;
+        }
+    }
+
+    let info = sponsorship.to_account_info();
+    let available = info
+        .lamports()
+        .saturating_sub(rent.minimum_balance(info.data_len()));
+    let amount = owed
+        .min(
+            sponsorship
+                .per_user_limit
+                .saturating_sub(sponsored.lamports),
+        )
+        .min(available);
+    if amount == 0 {
+        return Ok(0);
+    }
+    sponsored.lamports = sponsored
+        .lamports
+        .checked_add(amount)
+        .ok_or(NeptuError::Overflow)?;
+    sponsorship.total_sponsored = sponsorship
+        .total_sponsored
+        .checked_add(amount)
+        .ok_or(NeptuError::Overflow)?;
+    info.sub_lamports(amount)?;
+    fee_payer.add_lamports(amount)?;
+
+    emit!(RentSponsored {
+        user,
+        sponsor: fee_payer.key(),
+        amount,
+        user_total: sponsored.lamports,
+    });
+    msg!("Sponsored {} lamports of rent for {}", amount, user);
+    Ok(amount)
+}
+
+/// Open the user's NEPTU ATA unless it already exists; true when this call created it
+pub(crate) fn open_neptu_account<'info>(
+    account: &UncheckedAccount<'info>,
+    fee_payer: &Signer<'info>,
+    user: &Signer<'info>,
+    neptu_mint: &Account<'info, Mint>,
+    token_program: &Program<'info, Token>,
+    associated_token_program: &Program<'info, AssociatedToken>,
+    system_program: &Program<'info, System>,
+) -> Result<bool> {
+    if !account.data_is_empty() {
+        return Ok(false);
+    }
+    associated_token::create(CpiContext::new(
+        associated_token_program.to_account_info(),
+        associated_token::Create {
+            payer: fee_payer.to_account_info(),
+            associated_token: account.to_account_info(),
+            authority: user.to_account_info(),
+            mint: neptu_mint.to_account_info(),
+            system_program: system_program.to_account_info(),
+            token_program: token_program.to_account_info(),
+        },
+    ))?;
+    Ok(true)
+}
+
+/// Store a receipt and announce it to the backend
+pub(crate) fn issue_receipt(receipt: &mut Account<Receipt>, inner: Receipt) {
+    emit_receipt_issued(receipt.key(), &inner);
+    receipt.set_inner(inner);
+}
+
+pub(crate) fn emit_receipt_issued(receipt: Pubkey, inner: &Receipt) {
+    emit!(ReceiptIssued {
+        receipt,
+        payer: inner.payer,
+        beneficiary: inner.beneficiary,
+        reading_type: inner.reading_type,
+        currency: inner.currency,
+        amount: inner.amount,
+    });
+}
+
+/// Create the payer's next receipt PDA from an account passed in `remaining_accounts`
+/// (one per batch line item) and advance the payer's receipt index
+pub(crate) fn issue_receipt_at<'info>(
+    info: &AccountInfo<'info>,
+    rent_payer: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    profile: &mut UserProfile,
+    inner: Receipt,
+) -> Result<()> {
+    let index = profile.receipts_paid.to_le_bytes();
+    let (address, bump) =
+        Pubkey::find_program_address(&[b"receipt", inner.payer.as_ref(), &index], &crate::ID);
+    require_keys_eq!(info.key(), address, ErrorCode::ConstraintSeeds);
+    let seeds: &[&[u8]] = &[b"receipt", inner.payer.as_ref(), &index, &[bump]];
+    let signer_seeds = &[seeds];
+    let lamports = Rent::get()?.minimum_balance(Receipt::SIZE);
+    let program = system_program.to_account_info();
+
+    // Same as `init`: an address already holding lamports is topped up and claimed
+    if info.lamports() == 0 {
+        system_program::create_account(
+            CpiContext::new_with_signer(
+                program,
+                system_program::CreateAccount {
+                    from: rent_payer.clone(),
+                    to: info.clone(),
+                },
+                signer_seeds,
+            ),
+            lamports,
+            Receipt::SIZE as u64,
+            &crate::ID,
+        )?;
+    } else {
+        let top_up = lamports.saturating_sub(info.lamports());
+        if top_up > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    program.clone(),
+                    system_program::Transfer {
+                        from: rent_payer.clone(),
+                        to: info.clone(),
+                    },
+                ),
+                top_up,
+            )?;
+        }
+        system_program::allocate(
+            CpiContext::new_with_signer(
+                program.clone(),
+                system_program::Allocate {
+                    account_to_allocate: info.clone(),
+                },
+                signer_seeds,
+            ),
+            Receipt::SIZE as u64,
+        )?;
+        system_program::assign(
+            CpiContext::new_with_signer(
+                program,
+                system_program::Assign {
+                    account_to_assign: info.clone(),
+                },
+                signer_seeds,
+            ),
+            &crate::ID,
+        )?;
+    }
+
+    profile.receipts_paid = profile
+        .receipts_paid
+        .checked_add(1)
+        .ok_or(NeptuError::Overflow)?;
+    emit_receipt_issued(info.key(), &inner);
+    inner.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])
+}
+
+/// Split `total` across items in proportion to `weights`; the last item takes the
+/// rounding remainder so the shares always add up to `total`
+pub(crate) fn pro_rata_shares(total: u64, weights: &[u64]) -> Result<Vec<u64>> {
+    let weight_sum = weights
+        .iter()
+        .try_fold(0u64, |sum, weight| sum.checked_add(*weight))
+        .ok_or(NeptuError::Overflow)?;
+    let mut shares = Vec::with_capacity(weights.len());
+    let mut allocated = 0;
+    for (index, weight) in weights.iter().enumerate() {
+        let share = if index + 1 == weights.len() {
+            total - allocated
+        } else {
+            ((total as u128 * *weight as u128) / weight_sum.max(1) as u128) as u64
+        };
+        allocated += share;
+        shares.push(share);
+    }
+    Ok(shares)
+}
+
+/// Benefits of the payer's loyalty tier (zero tier without a loyalty program)
+pub(crate) fn loyalty_benefits(
+    program: &Option<Account<LoyaltyProgram>>,
+    profile: &UserProfile,
+) -> LoyaltyTier {
+    program
+        .as_ref()
+        .and_then(|program| program.tier_for(profile))
+        .unwrap_or_default()
+}
+
+/// Bind the payer to a referrer on their first payment and book the referral bonus
+/// `basis` is the pre-curve amount (list reward for SOL, price for NEPTU)
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn settle_referral(
+    state: &mut EconomyState,
+    user: Pubkey,
+    referral: &mut Account<Referral>,
+    referrer_stats: &mut Option<Account<ReferrerStats>>,
+    referrer_claim_record: &mut Option<Account<ClaimRecord>>,
+    referrer_account: &Option<Account<TokenAccount>>,
+    basis: u64,
+    pool_balance: Option<u64>,
+    now: i64,
+) -> Result<u64> {
+    if referral.referee == Pubkey::default() {
+        referral.referee = user;
+        referral.bound_at = now;
+        if let Some(stats) = referrer_stats.as_mut() {
+            require_keys_neq!(stats.referrer, user, NeptuError::SelfReferral);
+            referral.referrer = stats.referrer;
+            stats.referee_count = stats
+                .referee_count
+                .checked_add(1)
+                .ok_or(NeptuError::Overflow)?;
+            emit!(ReferralBound {
+                referee: user,
+                referrer: stats.referrer,
+            });
+            msg!("Referral bound: {} referred by {}", user, stats.referrer);
+        }
+    }
+    if referral.referrer == Pubkey::default() {
+        return Ok(0);
+    }
+
+    let (Some(stats), Some(claim_record), Some(referrer_account), Some(pool_balance)) = (
+        referrer_stats,
+        referrer_claim_record,
+        referrer_account,
+        pool_balance,
+    ) else {
+        return err!(NeptuError::ReferralAccountsMissing);
+    };
+    require_keys_eq!(
+        stats.referrer,
+        referral.referrer,
+        NeptuError::InvalidReferrer
+    );
+    require_keys_eq!(
+        claim_record.user,
+        referral.referrer,
+        NeptuError::InvalidReferrer
+    );
+    require_keys_eq!(
+        referrer_account.owner,
+        referral.referrer,
+        NeptuError::InvalidReferrer
+    );
+    stats.referred_payments = stats
+        .referred_payments
+        .checked_add(1)
+        .ok_or(NeptuError::Overflow)?;
+
+    let bonus =
+        ((basis as u128 * state.referral_bonus_bps as u128) / BPS_DENOMINATOR as u128) as u64;
+    let bonus = stats.clip_bonus(bonus, state.referral_cap, pool_balance);
+    if bonus == 0 {
+        return Ok(0);
+    }
+    // Clipped to the pool above, so the shortfall policy never defers a bonus
+    let bonus = grant_payment_reward(state, claim_record, &mut None, bonus, pool_balance, now)?;
+    if bonus == 0 {
+        return Ok(0);
+    }
+
+    stats.total_earned = stats
+        .total_earned
+        .checked_add(bonus)
+        .ok_or(NeptuError::Overflow)?;
+    state.total_referral_bonuses = state
+        .total_referral_bonuses
+        .checked_add(bonus)
+        .ok_or(NeptuError::Overflow)?;
+    emit!(ReferralBonusPaid {
+        referrer: referral.referrer,
+        referee: user,
+        amount: bonus,
+        total_earned: stats.total_earned,
+    });
+    Ok(bonus)
+}
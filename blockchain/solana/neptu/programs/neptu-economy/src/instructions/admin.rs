@@ -0,0 +1,374 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, TokenAccount};
+
+use crate::{constants::*, errors::NeptuError, events::*, state::*};
+
+pub(crate) fn initialize_economy(ctx: Context<InitializeEconomy>) -> Result<()> {
+    msg!("Initializing NEPTU Economy");
+    let state = &mut ctx.accounts.economy_state;
+    state.authority = ctx.accounts.authority.key();
+    state.neptu_mint = ctx.accounts.neptu_mint.key();
+    state.treasury = ctx.accounts.treasury.key();
+    state.ecosystem_pool = ctx.accounts.ecosystem_pool.key();
+    state.total_sol_collected = 0;
+    state.total_neptu_burned = 0;
+    state.total_neptu_rewarded = 0;
+    state.epoch_duration = defaults::EPOCH_DURATION;
+    state.epoch_global_cap = defaults::EPOCH_GLOBAL_CAP;
+    state.epoch_user_cap = defaults::EPOCH_USER_CAP;
+    state.current_epoch = 0;
+    state.epoch_emitted = 0;
+    state.reward_curve = RewardCurve {
+        kind: RewardCurveKind::Halving,
+        reference_balance: defaults::REWARD_CURVE_REFERENCE,
+        halving_step: defaults::REWARD_CURVE_HALVING_STEP,
+        floor_bps: defaults::REWARD_CURVE_FLOOR_BPS,
+    };
+    state.shortfall_policy = RewardShortfallPolicy::Fail;
+    state.total_neptu_recycled = 0;
+    state.rebalance_threshold = u64::MAX;
+    state.rebalance_bps = 0;
+    state.paused = false;
+    state.claim_signer = ctx.accounts.authority.key();
+    state.referral_bonus_bps = 0;
+    state.referral_cap = 0;
+    state.total_referral_bonuses = 0;
+    state.fulfillment_signer = ctx.accounts.authority.key();
+    state.escrow_timeout = defaults::ESCROW_TIMEOUT;
+    state.refund_burn_policy = RefundBurnPolicy::Reissue;
+    state.metering_notice_period = defaults::METERING_NOTICE_PERIOD;
+    state.epoch_start = 0;
+    state.epoch_base = 0;
+    msg!("Economy initialized");
+    Ok(())
+}
+
+pub(crate) fn update_emission_budget(
+    ctx: Context<UpdateEconomyConfig>,
+    epoch_duration: Option<i64>,
+    global_cap: Option<u64>,
+    user_cap: Option<u64>,
+) -> Result<()> {
+    let state = &mut ctx.accounts.economy_state;
+
+    if let Some(v) = epoch_duration {
+        require!(v > 0, NeptuError::InvalidAmount);
+        state.set_epoch_duration(v, Clock::get()?.unix_timestamp);
+    }
+    if let Some(v) = global_cap {
+        state.epoch_global_cap = v;
+    }
+    if let Some(v) = user_cap {
+        state.epoch_user_cap = v;
+    }
+
+    msg!(
+        "Emission budget updated: {}s epochs, global cap {}, user cap {}",
+        state.epoch_duration,
+        state.epoch_global_cap,
+        state.epoch_user_cap
+    );
+    Ok(())
+}
+
+pub(crate) fn set_authority(ctx: Context<SetAuthority>, new_authority: Pubkey) -> Result<()> {
+    ctx.accounts.economy_state.authority = new_authority;
+    ctx.accounts.pricing_config.authority = new_authority;
+    emit!(AuthorityChanged {
+        previous: ctx.accounts.authority.key(),
+        new_authority,
+    });
+    msg!("Authority changed to {}", new_authority);
+    Ok(())
+}
+
+pub(crate) fn initialize_roles(_ctx: Context<InitializeRoles>) -> Result<()> {
+    msg!("Roles initialized");
+    Ok(())
+}
+
+pub(crate) fn grant_role(ctx: Context<UpdateRoles>, member: Pubkey, role: Role) -> Result<()> {
+    ctx.accounts.roles.grant(member, role)?;
+    emit!(RoleChanged {
+        member,
+        role,
+        granted: true,
+    });
+    msg!("Granted {:?} to {}", role, member);
+    Ok(())
+}
+
+pub(crate) fn revoke_role(ctx: Context<UpdateRoles>, member: Pubkey, role: Role) -> Result<()> {
+    ctx.accounts.roles.revoke(member, role)?;
+    emit!(RoleChanged {
+        member,
+        role,
+        granted: false,
+    });
+    msg!("Revoked {:?} from {}", role, member);
+    Ok(())
+}
+
+pub(crate) fn set_claim_signer(ctx: Context<SetClaimSigner>, claim_signer: Pubkey) -> Result<()> {
+    ctx.accounts.economy_state.claim_signer = claim_signer;
+    msg!("Claim signer set to {}", claim_signer);
+    Ok(())
+}
+
+pub(crate) fn set_fulfillment_signer(
+    ctx: Context<SetClaimSigner>,
+    fulfillment_signer: Pubkey,
+) -> Result<()> {
+    ctx.accounts.economy_state.fulfillment_signer = fulfillment_signer;
+    msg!("Fulfillment signer set to {}", fulfillment_signer);
+    Ok(())
+}
+
+pub(crate) fn set_paused(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+    ctx.accounts.economy_state.paused = paused;
+    emit!(PauseChanged {
+        paused,
+        by: ctx.accounts.authority.key(),
+    });
+    msg!("Economy paused: {}", paused);
+    Ok(())
+}
+
+pub(crate) fn update_shortfall_policy(
+    ctx: Context<UpdateEconomyConfig>,
+    policy: RewardShortfallPolicy,
+) -> Result<()> {
+    ctx.accounts.economy_state.shortfall_policy = policy;
+    msg!("Reward shortfall policy updated: {:?}", policy);
+    Ok(())
+}
+
+pub(crate) fn update_reward_curve(
+    ctx: Context<UpdateEconomyConfig>,
+    curve: RewardCurve,
+) -> Result<()> {
+    require!(curve.reference_balance > 0, NeptuError::InvalidAmount);
+    require!(
+        curve.kind != RewardCurveKind::Halving || curve.halving_step > 0,
+        NeptuError::InvalidAmount
+    );
+    require!(
+        u64::from(curve.floor_bps) <= BPS_DENOMINATOR,
+        NeptuError::InvalidAmount
+    );
+
+    ctx.accounts.economy_state.reward_curve = curve;
+    msg!("Reward curve updated: {:?}", curve.kind);
+    Ok(())
+}
+
+pub(crate) fn update_referral_policy(
+    ctx: Context<UpdateEconomyConfig>,
+    bonus_bps: Option<u16>,
+    referrer_cap: Option<u64>,
+) -> Result<()> {
+    let state = &mut ctx.accounts.economy_state;
+
+    if let Some(v) = bonus_bps {
+        require!(u64::from(v) <= BPS_DENOMINATOR, NeptuError::InvalidAmount);
+        state.referral_bonus_bps = v;
+    }
+    if let Some(v) = referrer_cap {
+        state.referral_cap = v;
+    }
+
+    msg!(
+        "Referral policy updated: {} bps, cap {} NEPTU per referrer",
+        state.referral_bonus_bps,
+        state.referral_cap
+    );
+    Ok(())
+}
+
+pub(crate) fn update_escrow_timeout(ctx: Context<UpdateEconomyConfig>, timeout: i64) -> Result<()> {
+    require!(timeout > 0, NeptuError::InvalidAmount);
+    ctx.accounts.economy_state.escrow_timeout = timeout;
+    msg!("Escrow timeout set to {} seconds", timeout);
+    Ok(())
+}
+
+pub(crate) fn update_refund_policy(
+    ctx: Context<UpdateEconomyConfig>,
+    policy: RefundBurnPolicy,
+) -> Result<()> {
+    ctx.accounts.economy_state.refund_burn_policy = policy;
+    msg!("Refund burn policy updated: {:?}", policy);
+    Ok(())
+}
+
+pub(crate) fn update_metering_notice(ctx: Context<UpdateEconomyConfig>, period: i64) -> Result<()> {
+    require!(period >= 0, NeptuError::InvalidAmount);
+    ctx.accounts.economy_state.metering_notice_period = period;
+    msg!("Metering notice period set to {} seconds", period);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeEconomy<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init,
+        seeds = [b"economy_state"],
+        bump,
+        payer = authority,
+        space = EconomyState::SIZE,
+    )]
+    pub economy_state: Account<'info, EconomyState>,
+
+    /// CHECK: Economy authority PDA
+    #[account(
+        seeds = [b"economy"],
+        bump,
+    )]
+    pub economy_authority: UncheckedAccount<'info>,
+
+    pub neptu_mint: Account<'info, Mint>,
+
+    /// CHECK: Treasury wallet
+    pub treasury: UncheckedAccount<'info>,
+
+    pub ecosystem_pool: Account<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeRoles<'info> {
+    #[account(
+        mut,
+        constraint = authority.key() == economy_state.authority @ NeptuError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"economy_state"],
+        bump,
+    )]
+    pub economy_state: Account<'info, EconomyState>,
+
+    #[account(
+        init,
+        seeds = [b"roles"],
+        bump,
+        payer = authority,
+        space = Roles::SIZE,
+    )]
+    pub roles: Account<'info, Roles>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateRoles<'info> {
+    #[account(
+        constraint = authority.key() == economy_state.authority @ NeptuError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"economy_state"],
+        bump,
+    )]
+    pub economy_state: Account<'info, EconomyState>,
+
+    #[account(
+        mut,
+        seeds = [b"roles"],
+        bump,
+    )]
+    pub roles: Account<'info, Roles>,
+}
+
+#[derive(Accounts)]
+pub struct SetClaimSigner<'info> {
+    #[account(
+        constraint = roles.authorize(
+            &economy_state.authority,
+            &authority.key(),
+            Role::ClaimSignerAdmin,
+        ) @ NeptuError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"economy_state"],
+        bump,
+    )]
+    pub economy_state: Account<'info, EconomyState>,
+
+    #[account(
+        seeds = [b"roles"],
+        bump,
+    )]
+    pub roles: Account<'info, Roles>,
+}
+
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    #[account(
+        constraint = roles.authorize(
+            &economy_state.authority,
+            &authority.key(),
+            Role::Pauser,
+        ) @ NeptuError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"economy_state"],
+        bump,
+    )]
+    pub economy_state: Account<'info, EconomyState>,
+
+    #[account(
+        seeds = [b"roles"],
+        bump,
+    )]
+    pub roles: Account<'info, Roles>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateEconomyConfig<'info> {
+    #[account(
+        constraint = authority.key() == economy_state.authority @ NeptuError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"economy_state"],
+        bump,
+    )]
+    pub economy_state: Account<'info, EconomyState>,
+}
+
+#[derive(Accounts)]
+pub struct SetAuthority<'info> {
+    #[account(
+        constraint = authority.key() == economy_state.authority @ NeptuError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"economy_state"],
+        bump,
+    )]
+    pub economy_state: Account<'info, EconomyState>,
+
+    #[account(
+        mut,
+        seeds = [b"pricing_config"],
+        bump,
+    )]
+    pub pricing_config: Account<'info, PricingConfig>,
+}
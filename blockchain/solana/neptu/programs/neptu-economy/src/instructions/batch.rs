@@ -0,0 +1,611 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{transfer, Mint, Token, TokenAccount, Transfer};
+
+use crate::{constants::*, errors::NeptuError, events::*, helpers::*, state::*};
+
+pub(crate) fn pay_batch_with_sol<'info>(
+    ctx: Context<'_, '_, 'info, 'info, PayBatchWithSol<'info>>,
+    items: Vec<LineItem>,
+) -> Result<()> {
+    validate_batch(&items)?;
+    let receipts = ctx.remaining_accounts;
+    require!(
+        receipts.is_empty() || receipts.len() == items.len(),
+        NeptuError::InvalidBatch
+    );
+    let now = Clock::get()?.unix_timestamp;
+    let config = &ctx.accounts.pricing_config;
+    let tier = stake_benefits(
+        ctx.accounts.staking_pool.as_deref(),
+        &ctx.accounts.stake_position,
+        now,
+    );
+    let loyalty = loyalty_benefits(&ctx.accounts.loyalty_program, &ctx.accounts.user_profile);
+    let user = ctx.accounts.user.key();
+    let state = &mut ctx.accounts.economy_state;
+    let claim_record = &mut ctx.accounts.claim_record;
+    claim_record.user = user;
+    let profile = &mut ctx.accounts.user_profile;
+
+    let mut pool_balance = ctx.accounts.rewards_pool.amount;
+    let mut total_price: u64 = 0;
+    let mut neptu_reward: u64 = 0;
+    let mut referral_bonus: u64 = 0;
+    for (index, item) in items.iter().enumerate() {
+        let unit_price =
+            loyalty.discounted(tier.discounted(
+                config.resolve_sol_price(&item.reading_type, &ctx.accounts.price_feed)?,
+            ));
+        let amount = item.total(unit_price)?;
+        let base_reward = item.total(tier.boosted(config.get_sol_reward(&item.reading_type)?)?)?;
+        let reward = grant_payment_reward(
+            state,
+            claim_record,
+            &mut ctx.accounts.pending_reward,
+            base_reward,
+            pool_balance,
+            now,
+        )?;
+        pool_balance -= reward;
+        let bonus = settle_referral(
+            state,
+            user,
+            &mut ctx.accounts.referral,
+            &mut ctx.accounts.referrer_stats,
+            &mut ctx.accounts.referrer_claim_record,
+            &ctx.accounts.referrer_neptu_account,
+            base_reward,
+            Some(pool_balance),
+            now,
+        )?;
+        pool_balance -= bonus;
+
+        profile.record_readings(user, item.reading_type, u32::from(item.quantity), now)?;
+        if let Some(receipt) = receipts.get(index) {
+            issue_receipt_at(
+                receipt,
+                &ctx.accounts.fee_payer,
+                &ctx.accounts.system_program,
+                profile,
+                Receipt {
+                    payer: user,
+                    beneficiary: user,
+                    reading_type: item.reading_type,
+                    currency: PaymentCurrency::Sol,
+                    amount,
+                    issued_at: now,
+                    message_hash: None,
+                    burned: 0,
+                    refunded: false,
+                    reward,
+                    reward_recipient: user,
+                    staker_yield: 0,
+                    referral_bonus: bonus,
+                    quantity: item.quantity,
+                },
+            )?;
+        }
+        emit!(LineItemReceipt {
+            user,
+            index: index as u8,
+            reading_type: item.reading_type,
+            quantity: item.quantity,
+            currency: PaymentCurrency::Sol,
+            amount,
+        });
+        total_price = total_price
+            .checked_add(amount)
+            .ok_or(NeptuError::Overflow)?;
+        neptu_reward += reward;
+        referral_bonus += bonus;
+    }
+
+    state.total_sol_collected = state
+        .total_sol_collected
+        .checked_add(total_price)
+        .ok_or(NeptuError::Overflow)?;
+    profile.total_sol_spent = profile
+        .total_sol_spent
+        .checked_add(total_price)
+        .ok_or(NeptuError::Overflow)?;
+    profile.record_earned(neptu_reward)?;
+    profile.refresh_loyalty_tier(&ctx.accounts.loyalty_program);
+
+    msg!(
+        "Batch pay with SOL: {} items, {} lamports, reward: {} NEPTU",
+        items.len(),
+        total_price,
+        neptu_reward
+    );
+
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.user.to_account_info(),
+                to: ctx.accounts.treasury_vault.to_account_info(),
+            },
+        ),
+        total_price,
+    )?;
+    ctx.accounts
+        .treasury_vault
+        .record_deposit(user, total_price)?;
+
+    if neptu_reward == 0 {
+        msg!("No NEPTU reward paid for this payment");
+        return Ok(());
+    }
+
+    let seeds = &[b"economy".as_ref(), &[ctx.bumps.economy_authority]];
+    let signer_seeds = &[&seeds[..]];
+
+    transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.rewards_pool.to_account_info(),
+                to: ctx.accounts.user_neptu_account.to_account_info(),
+                authority: ctx.accounts.economy_authority.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        neptu_reward,
+    )?;
+    msg!("Transferred {} NEPTU reward to user", neptu_reward);
+
+    if let Some(referrer_account) = &ctx.accounts.referrer_neptu_account {
+        if referral_bonus > 0 {
+            transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.rewards_pool.to_account_info(),
+                        to: referrer_account.to_account_info(),
+                        authority: ctx.accounts.economy_authority.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                referral_bonus,
+            )?;
+            msg!("Transferred {} NEPTU referral bonus", referral_bonus);
+        }
+    }
+
+    Ok(())
+}
+
+pub(crate) fn pay_batch_with_neptu<'info>(
+    ctx: Context<'_, '_, 'info, 'info, PayBatchWithNeptu<'info>>,
+    items: Vec<LineItem>,
+) -> Result<()> {
+    validate_batch(&items)?;
+    let receipts = ctx.remaining_accounts;
+    require!(
+        receipts.is_empty() || receipts.len() == items.len(),
+        NeptuError::InvalidBatch
+    );
+    let now = Clock::get()?.unix_timestamp;
+    let config = &ctx.accounts.pricing_config;
+    let tier = stake_benefits(
+        ctx.accounts.staking_pool.as_deref(),
+        &ctx.accounts.stake_position,
+        now,
+    );
+    let loyalty = loyalty_benefits(&ctx.accounts.loyalty_program, &ctx.accounts.user_profile);
+    let user = ctx.accounts.user.key();
+
+    let amounts = items
+        .iter()
+        .map(|item| {
+            item.total(
+                loyalty.discounted(tier.discounted(config.get_neptu_price(&item.reading_type))),
+            )
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let total_price = amounts
+        .iter()
+        .try_fold(0u64, |sum, amount| sum.checked_add(*amount))
+        .ok_or(NeptuError::Overflow)?;
+    let split = settle_neptu_payment(
+        config,
+        &mut ctx.accounts.economy_state,
+        ctx.accounts.staking_pool.as_deref_mut(),
+        NeptuPaymentAccounts {
+            token_program: &ctx.accounts.token_program,
+            neptu_mint: &ctx.accounts.neptu_mint,
+            from: &ctx.accounts.user_neptu_account,
+            authority: ctx.accounts.user.to_account_info(),
+            ecosystem_pool: &ctx.accounts.ecosystem_pool,
+            stake_vault: ctx.accounts.stake_vault.as_ref(),
+        },
+        total_price,
+        &[],
+    )?;
+    let burned = pro_rata_shares(split.burned, &amounts)?;
+    let staker_yield = pro_rata_shares(split.staker_yield, &amounts)?;
+
+    let state = &mut ctx.accounts.economy_state;
+    let profile = &mut ctx.accounts.user_profile;
+    let mut pool_balance = ctx.accounts.rewards_pool.as_ref().map(|pool| pool.amount);
+    let mut referral_bonus: u64 = 0;
+    for (index, item) in items.iter().enumerate() {
+        let bonus = settle_referral(
+            state,
+            user,
+            &mut ctx.accounts.referral,
+            &mut ctx.accounts.referrer_stats,
+            &mut ctx.accounts.referrer_claim_record,
+            &ctx.accounts.referrer_neptu_account,
+            amounts[index],
+            pool_balance,
+            now,
+        )?;
+        pool_balance = pool_balance.map(|balance| balance - bonus);
+
+        profile.record_readings(user, item.reading_type, u32::from(item.quantity), now)?;
+        if let Some(receipt) = receipts.get(index) {
+            issue_receipt_at(
+                receipt,
+                &ctx.accounts.fee_payer,
+                &ctx.accounts.system_program,
+                profile,
+                Receipt {
+                    payer: user,
+                    beneficiary: user,
+                    reading_type: item.reading_type,
+                    currency: PaymentCurrency::Neptu,
+                    amount: amounts[index],
+                    issued_at: now,
+                    message_hash: None,
+                    burned: burned[index],
+                    refunded: false,
+                    reward: 0,
+                    reward_recipient: user,
+                    staker_yield: staker_yield[index],
+                    referral_bonus: bonus,
+                    quantity: item.quantity,
+                },
+            )?;
+        }
+        emit!(LineItemReceipt {
+            user,
+            index: index as u8,
+            reading_type: item.reading_type,
+            quantity: item.quantity,
+            currency: PaymentCurrency::Neptu,
+            amount: amounts[index],
+        });
+        referral_bonus += bonus;
+    }
+    profile.total_neptu_spent = profile
+        .total_neptu_spent
+        .checked_add(total_price)
+        .ok_or(NeptuError::Overflow)?;
+    profile.total_neptu_burned = profile
+        .total_neptu_burned
+        .checked_add(split.burned)
+        .ok_or(NeptuError::Overflow)?;
+    profile.refresh_loyalty_tier(&ctx.accounts.loyalty_program);
+
+    msg!(
+        "Batch pay with NEPTU: {} items, {} total, {} burned, {} recycled",
+        items.len(),
+        total_price,
+        split.burned,
+        split.recycled
+    );
+
+    if let (Some(rewards_pool), Some(referrer_account)) = (
+        &ctx.accounts.rewards_pool,
+        &ctx.accounts.referrer_neptu_account,
+    ) {
+        if referral_bonus > 0 {
+            let seeds = &[b"economy".as_ref(), &[ctx.bumps.economy_authority]];
+            let signer_seeds = &[&seeds[..]];
+            transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: rewards_pool.to_account_info(),
+                        to: referrer_account.to_account_info(),
+                        authority: ctx.accounts.economy_authority.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                referral_bonus,
+            )?;
+            msg!("Transferred {} NEPTU referral bonus", referral_bonus);
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct PayBatchWithSol<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// Pays rent and fees; may be a sponsor other than the user
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+
+    #[account(
+        seeds = [b"pricing_config"],
+        bump,
+    )]
+    pub pricing_config: Account<'info, PricingConfig>,
+
+    /// CHECK: Pyth SOL/USD price update, must be `pricing_config.price_feed`;
+    /// required for USD-priced readings
+    pub price_feed: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        mut,
+        seeds = [b"economy_state"],
+        bump,
+        constraint = !economy_state.paused @ NeptuError::ProgramPaused,
+    )]
+    pub economy_state: Account<'info, EconomyState>,
+
+    /// Per-user emission budget tracking
+    #[account(
+        init_if_needed,
+        seeds = [b"claim", user.key().as_ref()],
+        bump,
+        payer = fee_payer,
+        space = ClaimRecord::SIZE,
+    )]
+    pub claim_record: Account<'info, ClaimRecord>,
+
+    #[account(
+        init_if_needed,
+        seeds = [b"pending_reward", user.key().as_ref()],
+        bump,
+        payer = fee_payer,
+        space = PendingReward::SIZE,
+    )]
+    pub pending_reward: Option<Account<'info, PendingReward>>,
+
+    #[account(
+        init_if_needed,
+        seeds = [b"profile", user.key().as_ref()],
+        bump,
+        payer = fee_payer,
+        space = UserProfile::SIZE,
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    /// Applies the profile's loyalty tier discount when given
+    #[account(
+        seeds = [b"loyalty"],
+        bump,
+    )]
+    pub loyalty_program: Option<Account<'info, LoyaltyProgram>>,
+
+    /// Treasury vault receives SOL
+    #[account(
+        mut,
+        seeds = [b"treasury_vault"],
+        bump = treasury_vault.bump,
+    )]
+    pub treasury_vault: Account<'info, TreasuryVault>,
+
+    #[account(address = economy_state.neptu_mint)]
+    pub neptu_mint: Account<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = fee_payer,
+        associated_token::mint = neptu_mint,
+        associated_token::authority = user,
+    )]
+    pub user_neptu_account: Account<'info, TokenAccount>,
+
+    /// Rewards pool: ATA owned by economy_authority PDA
+    #[account(
+        mut,
+        associated_token::mint = neptu_mint,
+        associated_token::authority = economy_authority,
+    )]
+    pub rewards_pool: Account<'info, TokenAccount>,
+
+    /// CHECK: Economy authority PDA (rewards pool owner)
+    #[account(
+        seeds = [b"economy"],
+        bump,
+    )]
+    pub economy_authority: UncheckedAccount<'info>,
+
+    /// Caller's stake tier applies a price discount / reward boost when both are given
+    #[account(
+        seeds = [b"staking_pool"],
+        bump,
+    )]
+    pub staking_pool: Option<Account<'info, StakingPool>>,
+
+    #[account(
+        seeds = [b"stake", user.key().as_ref()],
+        bump,
+    )]
+    pub stake_position: Option<Account<'info, StakePosition>>,
+
+    /// Referral binding of the payer, set once on first payment
+    #[account(
+        init_if_needed,
+        seeds = [b"referral", user.key().as_ref()],
+        bump,
+        payer = fee_payer,
+        space = Referral::SIZE,
+    )]
+    pub referral: Account<'info, Referral>,
+
+    /// Stats of the payer's referrer; binds the referrer on first payment and is
+    /// required once bound
+    #[account(
+        mut,
+        seeds = [b"referrer", referrer_stats.referrer.as_ref()],
+        bump,
+    )]
+    pub referrer_stats: Option<Account<'info, ReferrerStats>>,
+
+    /// Referrer's claim record, charged against the referrer's emission budget
+    #[account(
+        mut,
+        seeds = [b"claim", referrer_claim_record.user.as_ref()],
+        bump,
+    )]
+    pub referrer_claim_record: Option<Account<'info, ClaimRecord>>,
+
+    /// Referrer's NEPTU account receiving the bonus
+    #[account(
+        mut,
+        token::mint = neptu_mint,
+    )]
+    pub referrer_neptu_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PayBatchWithNeptu<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// Pays rent and fees; may be a sponsor other than the user
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+
+    #[account(
+        seeds = [b"pricing_config"],
+        bump,
+    )]
+    pub pricing_config: Account<'info, PricingConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"economy_state"],
+        bump,
+        constraint = !economy_state.paused @ NeptuError::ProgramPaused,
+    )]
+    pub economy_state: Account<'info, EconomyState>,
+
+    #[account(
+        init_if_needed,
+        seeds = [b"profile", user.key().as_ref()],
+        bump,
+        payer = fee_payer,
+        space = UserProfile::SIZE,
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    /// Applies the profile's loyalty tier discount when given
+    #[account(
+        seeds = [b"loyalty"],
+        bump,
+    )]
+    pub loyalty_program: Option<Account<'info, LoyaltyProgram>>,
+
+    #[account(mut, address = economy_state.neptu_mint)]
+    pub neptu_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = neptu_mint,
+        token::authority = user,
+    )]
+    pub user_neptu_account: Account<'info, TokenAccount>,
+
+    #[account(mut, address = economy_state.ecosystem_pool)]
+    pub ecosystem_pool: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"staking_pool"],
+        bump,
+    )]
+    pub staking_pool: Option<Account<'info, StakingPool>>,
+
+    #[account(
+        seeds = [b"stake", user.key().as_ref()],
+        bump,
+    )]
+    pub stake_position: Option<Account<'info, StakePosition>>,
+
+    /// Receives the stakers' yield share of the recycled amount once staking is set up
+    #[account(
+        mut,
+        seeds = [b"stake_vault"],
+        bump,
+    )]
+    pub stake_vault: Option<Account<'info, TokenAccount>>,
+
+    /// Rewards pool funding the referral bonus
+    #[account(
+        mut,
+        associated_token::mint = neptu_mint,
+        associated_token::authority = economy_authority,
+    )]
+    pub rewards_pool: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: Economy authority PDA (rewards pool owner)
+    #[account(
+        seeds = [b"economy"],
+        bump,
+    )]
+    pub economy_authority: UncheckedAccount<'info>,
+
+    /// Referral binding of the payer, set once on first payment
+    #[account(
+        init_if_needed,
+        seeds = [b"referral", user.key().as_ref()],
+        bump,
+        payer = fee_payer,
+        space = Referral::SIZE,
+    )]
+    pub referral: Account<'info, Referral>,
+
+    /// Stats of the payer's referrer; binds the referrer on first payment and is
+    /// required once bound
+    #[account(
+        mut,
+        seeds = [b"referrer", referrer_stats.referrer.as_ref()],
+        bump,
+    )]
+    pub referrer_stats: Option<Account<'info, ReferrerStats>>,
+
+    /// Referrer's claim record, charged against the referrer's emission budget
+    #[account(
+        mut,
+        seeds = [b"claim", referrer_claim_record.user.as_ref()],
+        bump,
+    )]
+    pub referrer_claim_record: Option<Account<'info, ClaimRecord>>,
+
+    /// Referrer's NEPTU account receiving the bonus
+    #[account(
+        mut,
+        token::mint = neptu_mint,
+    )]
+    pub referrer_neptu_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+fn validate_batch(items: &[LineItem]) -> Result<()> {
+    require!(
+        !items.is_empty() && items.len() <= MAX_BATCH_ITEMS,
+        NeptuError::InvalidBatch
+    );
+    require!(
+        items.iter().all(|item| item.quantity > 0),
+        NeptuError::InvalidBatch
+    );
+    Ok(())
+}
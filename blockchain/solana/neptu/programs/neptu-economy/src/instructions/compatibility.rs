@@ -0,0 +1,327 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::{constants::*, errors::NeptuError, events::*, helpers::*, state::*};
+
+pub(crate) fn open_compatibility_request(
+    ctx: Context<OpenCompatibilityRequest>,
+    request_id: u64,
+    initiator_share_bps: u16,
+) -> Result<()> {
+    require!(
+        u64::from(initiator_share_bps) <= BPS_DENOMINATOR,
+        NeptuError::InvalidAmount
+    );
+    let price = ctx
+        .accounts
+        .pricing_config
+        .resolve_sol_price(&ReadingType::Compatibility, &ctx.accounts.price_feed)?;
+    let initiator_share =
+        ((price as u128 * initiator_share_bps as u128) / BPS_DENOMINATOR as u128) as u64;
+
+    let request = &mut ctx.accounts.compatibility_request;
+    request.initiator = ctx.accounts.initiator.key();
+    request.partner = ctx.accounts.partner.key();
+    request.request_id = request_id;
+    request.price = price;
+    request.initiator_share = initiator_share;
+    request.created_at = Clock::get()?.unix_timestamp;
+    request.rent_payer = ctx.accounts.fee_payer.key();
+
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.initiator.to_account_info(),
+                to: ctx.accounts.compatibility_request.to_account_info(),
+            },
+        ),
+        initiator_share,
+    )?;
+
+    emit!(CompatibilityRequestOpened {
+        request: ctx.accounts.compatibility_request.key(),
+        initiator: ctx.accounts.initiator.key(),
+        partner: ctx.accounts.partner.key(),
+        request_id,
+        price,
+        initiator_share,
+    });
+    msg!(
+        "Compatibility request opened: {} of {} lamports deposited",
+        initiator_share,
+        price
+    );
+    Ok(())
+}
+
+pub(crate) fn accept_compatibility_request(ctx: Context<AcceptCompatibilityRequest>) -> Result<()> {
+    let request = &ctx.accounts.compatibility_request;
+    let (initiator, partner) = (request.initiator, request.partner);
+    let (price, initiator_share) = (request.price, request.initiator_share);
+    let partner_share = price - initiator_share;
+    let now = Clock::get()?.unix_timestamp;
+
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.partner.to_account_info(),
+                to: ctx.accounts.treasury_vault.to_account_info(),
+            },
+        ),
+        partner_share,
+    )?;
+    ctx.accounts
+        .compatibility_request
+        .sub_lamports(initiator_share)?;
+    ctx.accounts.treasury_vault.add_lamports(initiator_share)?;
+
+    let vault = &mut ctx.accounts.treasury_vault;
+    vault.record_deposit(initiator, initiator_share)?;
+    vault.record_deposit(partner, partner_share)?;
+
+    let state = &mut ctx.accounts.economy_state;
+    state.total_sol_collected = state
+        .total_sol_collected
+        .checked_add(price)
+        .ok_or(NeptuError::Overflow)?;
+
+    for (profile, user, share) in [
+        (
+            &mut ctx.accounts.initiator_profile,
+            initiator,
+            initiator_share,
+        ),
+        (&mut ctx.accounts.partner_profile, partner, partner_share),
+    ] {
+        profile.record_reading(user, ReadingType::Compatibility, now)?;
+        profile.total_sol_spent = profile
+            .total_sol_spent
+            .checked_add(share)
+            .ok_or(NeptuError::Overflow)?;
+    }
+
+    for (receipt, user, share) in [
+        (
+            &mut ctx.accounts.initiator_receipt,
+            initiator,
+            initiator_share,
+        ),
+        (&mut ctx.accounts.partner_receipt, partner, partner_share),
+    ] {
+        issue_receipt(
+            receipt,
+            Receipt {
+                payer: user,
+                beneficiary: user,
+                reading_type: ReadingType::Compatibility,
+                currency: PaymentCurrency::Sol,
+                amount: share,
+                issued_at: now,
+                message_hash: None,
+                burned: 0,
+                refunded: false,
+                reward: 0,
+                reward_recipient: user,
+                staker_yield: 0,
+                referral_bonus: 0,
+                quantity: 1,
+            },
+        );
+    }
+
+    emit!(CompatibilityRequestClosed {
+        request: ctx.accounts.compatibility_request.key(),
+        initiator,
+        partner,
+        accepted: true,
+    });
+    msg!(
+        "Compatibility request accepted: {} + {} lamports to treasury vault",
+        initiator_share,
+        partner_share
+    );
+    Ok(())
+}
+
+pub(crate) fn close_compatibility_request(ctx: Context<CloseCompatibilityRequest>) -> Result<()> {
+    let request = &ctx.accounts.compatibility_request;
+    request.sub_lamports(request.initiator_share)?;
+    ctx.accounts
+        .initiator
+        .add_lamports(request.initiator_share)?;
+    emit!(CompatibilityRequestClosed {
+        request: request.key(),
+        initiator: request.initiator,
+        partner: request.partner,
+        accepted: false,
+    });
+    msg!(
+        "Compatibility request closed, {} lamports refunded",
+        request.initiator_share
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(request_id: u64)]
+pub struct OpenCompatibilityRequest<'info> {
+    #[account(mut)]
+    pub initiator: Signer<'info>,
+
+    /// Pays rent and fees; may be a sponsor other than the initiator
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+
+    /// CHECK: Second party of the reading, any wallet other than the initiator
+    #[account(constraint = partner.key() != initiator.key() @ NeptuError::InvalidBeneficiary)]
+    pub partner: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"pricing_config"],
+        bump,
+    )]
+    pub pricing_config: Account<'info, PricingConfig>,
+
+    /// CHECK: Pyth SOL/USD price update, must be `pricing_config.price_feed`;
+    /// required for USD-priced readings
+    pub price_feed: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        seeds = [b"economy_state"],
+        bump,
+        constraint = !economy_state.paused @ NeptuError::ProgramPaused,
+    )]
+    pub economy_state: Account<'info, EconomyState>,
+
+    #[account(
+        init,
+        seeds = [
+            b"compat",
+            initiator.key().as_ref(),
+            partner.key().as_ref(),
+            &request_id.to_le_bytes(),
+        ],
+        bump,
+        payer = fee_payer,
+        space = CompatibilityRequest::SIZE,
+    )]
+    pub compatibility_request: Account<'info, CompatibilityRequest>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptCompatibilityRequest<'info> {
+    #[account(mut)]
+    pub partner: Signer<'info>,
+
+    /// Pays rent and fees; may be a sponsor other than the partner
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+
+    /// CHECK: Request initiator, checked by the request seeds
+    pub initiator: UncheckedAccount<'info>,
+
+    /// CHECK: Paid the request rent, receives it back
+    #[account(mut, address = compatibility_request.rent_payer)]
+    pub rent_payer: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"compat",
+            initiator.key().as_ref(),
+            partner.key().as_ref(),
+            &compatibility_request.request_id.to_le_bytes(),
+        ],
+        bump,
+        close = rent_payer,
+    )]
+    pub compatibility_request: Account<'info, CompatibilityRequest>,
+
+    #[account(
+        mut,
+        seeds = [b"economy_state"],
+        bump,
+        constraint = !economy_state.paused @ NeptuError::ProgramPaused,
+    )]
+    pub economy_state: Account<'info, EconomyState>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury_vault"],
+        bump = treasury_vault.bump,
+    )]
+    pub treasury_vault: Account<'info, TreasuryVault>,
+
+    #[account(
+        init_if_needed,
+        seeds = [b"profile", initiator.key().as_ref()],
+        bump,
+        payer = fee_payer,
+        space = UserProfile::SIZE,
+    )]
+    pub initiator_profile: Account<'info, UserProfile>,
+
+    #[account(
+        init_if_needed,
+        seeds = [b"profile", partner.key().as_ref()],
+        bump,
+        payer = fee_payer,
+        space = UserProfile::SIZE,
+    )]
+    pub partner_profile: Account<'info, UserProfile>,
+
+    #[account(
+        init,
+        seeds = [b"receipt", compatibility_request.key().as_ref(), initiator.key().as_ref()],
+        bump,
+        payer = fee_payer,
+        space = Receipt::SIZE,
+    )]
+    pub initiator_receipt: Account<'info, Receipt>,
+
+    #[account(
+        init,
+        seeds = [b"receipt", compatibility_request.key().as_ref(), partner.key().as_ref()],
+        bump,
+        payer = fee_payer,
+        space = Receipt::SIZE,
+    )]
+    pub partner_receipt: Account<'info, Receipt>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseCompatibilityRequest<'info> {
+    /// Partner (decline) or initiator (withdraw)
+    #[account(
+        constraint = closer.key() == compatibility_request.partner
+            || closer.key() == compatibility_request.initiator @ NeptuError::Unauthorized
+    )]
+    pub closer: Signer<'info>,
+
+    /// CHECK: Request initiator, refunded on close
+    #[account(mut, address = compatibility_request.initiator)]
+    pub initiator: UncheckedAccount<'info>,
+
+    /// CHECK: Paid the request rent, receives it back
+    #[account(mut, address = compatibility_request.rent_payer)]
+    pub rent_payer: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"compat",
+            compatibility_request.initiator.as_ref(),
+            compatibility_request.partner.as_ref(),
+            &compatibility_request.request_id.to_le_bytes(),
+        ],
+        bump,
+        close = rent_payer,
+    )]
+    pub compatibility_request: Account<'info, CompatibilityRequest>,
+}
@@ -0,0 +1,451 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token::{transfer, Mint, Token, TokenAccount, Transfer};
+
+use crate::{constants::*, errors::NeptuError, events::*, helpers::*, state::*};
+
+pub(crate) fn set_credit_discounts(
+    ctx: Context<SetCreditDiscounts>,
+    tiers: Vec<CreditDiscountTier>,
+) -> Result<()> {
+    let policy = &mut ctx.accounts.credit_policy;
+    policy.set_tiers(tiers)?;
+    msg!("Credit discounts updated: {} tiers", policy.tiers.len());
+    Ok(())
+}
+
+pub(crate) fn buy_credits(
+    ctx: Context<BuyCredits>,
+    reading_type: ReadingType,
+    currency: PaymentCurrency,
+    quantity: u32,
+) -> Result<()> {
+    require!(quantity > 0, NeptuError::InvalidAmount);
+    let config = &ctx.accounts.pricing_config;
+    let unit_price = match currency {
+        PaymentCurrency::Sol => {
+            config.resolve_sol_price(&reading_type, &ctx.accounts.price_feed)?
+        }
+        PaymentCurrency::Neptu => config.get_neptu_price(&reading_type),
+        PaymentCurrency::Token(_) => return err!(NeptuError::PaymentMintNotAccepted),
+    };
+    let discount_bps = ctx
+        .accounts
+        .credit_policy
+        .as_ref()
+        .map_or(0, |policy| policy.discount_bps(quantity));
+    let gross = unit_price
+        .checked_mul(u64::from(quantity))
+        .ok_or(NeptuError::Overflow)?;
+    let amount = gross - ((gross as u128 * discount_bps as u128) / BPS_DENOMINATOR as u128) as u64;
+
+    let user = ctx.accounts.user.key();
+    let credits = &mut ctx.accounts.credits;
+    credits.user = user;
+    credits.reading_type = reading_type;
+    credits.currency = currency;
+    credits.add(quantity, amount)?;
+
+    match currency {
+        PaymentCurrency::Sol => {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.user.to_account_info(),
+                        to: ctx.accounts.credits.to_account_info(),
+                    },
+                ),
+                amount,
+            )?;
+        }
+        PaymentCurrency::Neptu => {
+            let (Some(user_neptu_account), Some(credits_vault)) = (
+                &ctx.accounts.user_neptu_account,
+                &ctx.accounts.credits_vault,
+            ) else {
+                return err!(NeptuError::TokenAccountMissing);
+            };
+            transfer(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: user_neptu_account.to_account_info(),
+                        to: credits_vault.to_account_info(),
+                        authority: ctx.accounts.user.to_account_info(),
+                    },
+                ),
+                amount,
+            )?;
+        }
+        PaymentCurrency::Token(_) => return err!(NeptuError::PaymentMintNotAccepted),
+    }
+
+    let credits = &ctx.accounts.credits;
+    emit!(CreditsPurchased {
+        user,
+        reading_type,
+        currency,
+        quantity,
+        amount,
+        balance: credits.balance,
+    });
+    msg!(
+        "Bought {} {:?} credits for {} {:?} ({} bps discount)",
+        quantity,
+        reading_type,
+        amount,
+        currency,
+        discount_bps
+    );
+    Ok(())
+}
+
+pub(crate) fn consume_credit(ctx: Context<ConsumeCredit>, quantity: u32) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let credits = &mut ctx.accounts.credits;
+    let (user, reading_type, currency) = (credits.user, credits.reading_type, credits.currency);
+    let amount = credits.consume(quantity)?;
+
+    let profile = &mut ctx.accounts.user_profile;
+    profile.record_readings(user, reading_type, quantity, now)?;
+
+    let seeds = &[b"economy".as_ref(), &[ctx.bumps.economy_authority]];
+    let signer_seeds = &[&seeds[..]];
+
+    match currency {
+        PaymentCurrency::Sol => {
+            ctx.accounts.credits.sub_lamports(amount)?;
+            let vault = &mut ctx.accounts.treasury_vault;
+            vault.add_lamports(amount)?;
+            vault.record_deposit(user, amount)?;
+
+            let reward = ctx
+                .accounts
+                .pricing_config
+                .get_sol_reward(&reading_type)?
+                .checked_mul(u64::from(quantity))
+                .ok_or(NeptuError::Overflow)?;
+            let state = &mut ctx.accounts.economy_state;
+            let claim_record = &mut ctx.accounts.claim_record;
+            claim_record.user = user;
+            let neptu_reward = grant_payment_reward(
+                state,
+                claim_record,
+                &mut ctx.accounts.pending_reward,
+                reward,
+                ctx.accounts.rewards_pool.amount,
+                now,
+            )?;
+            state.total_sol_collected = state
+                .total_sol_collected
+                .checked_add(amount)
+                .ok_or(NeptuError::Overflow)?;
+            profile.total_sol_spent = profile
+                .total_sol_spent
+                .checked_add(amount)
+                .ok_or(NeptuError::Overflow)?;
+            profile.record_earned(neptu_reward)?;
+
+            if neptu_reward > 0 {
+                transfer(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        Transfer {
+                            from: ctx.accounts.rewards_pool.to_account_info(),
+                            to: ctx.accounts.user_neptu_account.to_account_info(),
+                            authority: ctx.accounts.economy_authority.to_account_info(),
+                        },
+                        signer_seeds,
+                    ),
+                    neptu_reward,
+                )?;
+                msg!("Transferred {} NEPTU reward to user", neptu_reward);
+            }
+        }
+        PaymentCurrency::Neptu => {
+            let credits_vault = ctx
+                .accounts
+                .credits_vault
+                .as_ref()
+                .ok_or(NeptuError::TokenAccountMissing)?;
+            let split = settle_neptu_payment(
+                &ctx.accounts.pricing_config,
+                &mut ctx.accounts.economy_state,
+                ctx.accounts.staking_pool.as_deref_mut(),
+                NeptuPaymentAccounts {
+                    token_program: &ctx.accounts.token_program,
+                    neptu_mint: &ctx.accounts.neptu_mint,
+                    from: credits_vault,
+                    authority: ctx.accounts.economy_authority.to_account_info(),
+                    ecosystem_pool: &ctx.accounts.ecosystem_pool,
+                    stake_vault: ctx.accounts.stake_vault.as_ref(),
+                },
+                amount,
+                signer_seeds,
+            )?;
+            profile.total_neptu_spent = profile
+                .total_neptu_spent
+                .checked_add(amount)
+                .ok_or(NeptuError::Overflow)?;
+            profile.total_neptu_burned = profile
+                .total_neptu_burned
+                .checked_add(split.burned)
+                .ok_or(NeptuError::Overflow)?;
+        }
+        PaymentCurrency::Token(_) => return err!(NeptuError::PaymentMintNotAccepted),
+    }
+
+    emit!(CreditsConsumed {
+        user,
+        reading_type,
+        currency,
+        quantity,
+        amount,
+        balance: ctx.accounts.credits.balance,
+    });
+    msg!(
+        "Consumed {} {:?} credits ({} {:?})",
+        quantity,
+        reading_type,
+        amount,
+        currency
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetCreditDiscounts<'info> {
+    #[account(
+        mut,
+        constraint = roles.authorize(
+            &pricing_config.authority,
+            &authority.key(),
+            Role::PricingManager,
+        ) @ NeptuError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"pricing_config"],
+        bump,
+    )]
+    pub pricing_config: Account<'info, PricingConfig>,
+
+    #[account(
+        seeds = [b"roles"],
+        bump,
+    )]
+    pub roles: Account<'info, Roles>,
+
+    #[account(
+        init_if_needed,
+        seeds = [b"credit_policy"],
+        bump,
+        payer = authority,
+        space = CreditPolicy::SIZE,
+    )]
+    pub credit_policy: Account<'info, CreditPolicy>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(reading_type: ReadingType, currency: PaymentCurrency)]
+pub struct BuyCredits<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// Pays rent and fees; may be a sponsor other than the user
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+
+    #[account(
+        seeds = [b"pricing_config"],
+        bump,
+    )]
+    pub pricing_config: Account<'info, PricingConfig>,
+
+    /// CHECK: Pyth SOL/USD price update, must be `pricing_config.price_feed`;
+    /// required for USD-priced readings
+    pub price_feed: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        seeds = [b"economy_state"],
+        bump,
+        constraint = !economy_state.paused @ NeptuError::ProgramPaused,
+    )]
+    pub economy_state: Account<'info, EconomyState>,
+
+    #[account(
+        seeds = [b"credit_policy"],
+        bump,
+    )]
+    pub credit_policy: Option<Account<'info, CreditPolicy>>,
+
+    #[account(
+        init_if_needed,
+        seeds = [
+            b"credits",
+            user.key().as_ref(),
+            &[reading_type as u8, currency.seed()],
+        ],
+        bump,
+        payer = fee_payer,
+        space = Credits::SIZE,
+    )]
+    pub credits: Account<'info, Credits>,
+
+    #[account(address = economy_state.neptu_mint)]
+    pub neptu_mint: Option<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        token::authority = user,
+    )]
+    pub user_neptu_account: Option<Account<'info, TokenAccount>>,
+
+    /// Holds NEPTU paid for NEPTU credits
+    #[account(
+        init_if_needed,
+        seeds = [b"credits_vault", credits.key().as_ref()],
+        bump,
+        payer = fee_payer,
+        token::mint = neptu_mint,
+        token::authority = economy_authority,
+    )]
+    pub credits_vault: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: Economy authority PDA (credits vault owner)
+    #[account(
+        seeds = [b"economy"],
+        bump,
+    )]
+    pub economy_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ConsumeCredit<'info> {
+    /// Backend key attesting delivery
+    #[account(
+        mut,
+        address = economy_state.fulfillment_signer @ NeptuError::Unauthorized
+    )]
+    pub fulfillment_signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"credits",
+            credits.user.as_ref(),
+            &[credits.reading_type as u8, credits.currency.seed()],
+        ],
+        bump,
+    )]
+    pub credits: Account<'info, Credits>,
+
+    #[account(
+        seeds = [b"pricing_config"],
+        bump,
+    )]
+    pub pricing_config: Account<'info, PricingConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"economy_state"],
+        bump,
+        constraint = !economy_state.paused @ NeptuError::ProgramPaused,
+    )]
+    pub economy_state: Account<'info, EconomyState>,
+
+    #[account(
+        init_if_needed,
+        seeds = [b"profile", credits.user.as_ref()],
+        bump,
+        payer = fulfillment_signer,
+        space = UserProfile::SIZE,
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    /// Per-user emission budget tracking
+    #[account(
+        init_if_needed,
+        seeds = [b"claim", credits.user.as_ref()],
+        bump,
+        payer = fulfillment_signer,
+        space = ClaimRecord::SIZE,
+    )]
+    pub claim_record: Account<'info, ClaimRecord>,
+
+    #[account(
+        init_if_needed,
+        seeds = [b"pending_reward", credits.user.as_ref()],
+        bump,
+        payer = fulfillment_signer,
+        space = PendingReward::SIZE,
+    )]
+    pub pending_reward: Option<Account<'info, PendingReward>>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury_vault"],
+        bump = treasury_vault.bump,
+    )]
+    pub treasury_vault: Account<'info, TreasuryVault>,
+
+    #[account(mut, address = economy_state.neptu_mint)]
+    pub neptu_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = neptu_mint,
+        associated_token::authority = credits.user,
+    )]
+    pub user_neptu_account: Account<'info, TokenAccount>,
+
+    /// Rewards pool: ATA owned by economy_authority PDA
+    #[account(
+        mut,
+        associated_token::mint = neptu_mint,
+        associated_token::authority = economy_authority,
+    )]
+    pub rewards_pool: Account<'info, TokenAccount>,
+
+    #[account(mut, address = economy_state.ecosystem_pool)]
+    pub ecosystem_pool: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"staking_pool"],
+        bump,
+    )]
+    pub staking_pool: Option<Account<'info, StakingPool>>,
+
+    /// Receives the stakers' yield share of the recycled amount once staking is set up
+    #[account(
+        mut,
+        seeds = [b"stake_vault"],
+        bump,
+    )]
+    pub stake_vault: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"credits_vault", credits.key().as_ref()],
+        bump,
+    )]
+    pub credits_vault: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: Economy authority PDA (pool and vault owner)
+    #[account(
+        seeds = [b"economy"],
+        bump,
+    )]
+    pub economy_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,442 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{transfer, Mint, Token, TokenAccount, Transfer};
+
+use crate::{errors::NeptuError, helpers::*, state::*};
+
+pub(crate) fn gift_with_sol(
+    ctx: Context<GiftWithSol>,
+    reading_type: ReadingType,
+    reward_to_beneficiary: bool,
+    message_hash: Option<[u8; 32]>,
+) -> Result<()> {
+    let config = &ctx.accounts.pricing_config;
+    let sol_price = config.resolve_sol_price(&reading_type, &ctx.accounts.price_feed)?;
+    let now = Clock::get()?.unix_timestamp;
+    let payer = ctx.accounts.payer.key();
+    let beneficiary = ctx.accounts.beneficiary.key();
+
+    let state = &mut ctx.accounts.economy_state;
+    let claim_record = &mut ctx.accounts.claim_record;
+    claim_record.user = ctx.accounts.reward_recipient.key();
+    let neptu_reward = grant_payment_reward(
+        state,
+        claim_record,
+        &mut ctx.accounts.pending_reward,
+        config.get_sol_reward(&reading_type)?,
+        ctx.accounts.rewards_pool.amount,
+        now,
+    )?;
+    state.total_sol_collected = state
+        .total_sol_collected
+        .checked_add(sol_price)
+        .ok_or(NeptuError::Overflow)?;
+
+    let payer_profile = &mut ctx.accounts.payer_profile;
+    let beneficiary_profile = &mut ctx.accounts.beneficiary_profile;
+    record_gift(
+        payer_profile,
+        beneficiary_profile,
+        payer,
+        beneficiary,
+        reading_type,
+        now,
+    )?;
+    payer_profile.total_sol_spent = payer_profile
+        .total_sol_spent
+        .checked_add(sol_price)
+        .ok_or(NeptuError::Overflow)?;
+    if reward_to_beneficiary {
+        beneficiary_profile.record_earned(neptu_reward)?;
+    } else {
+        payer_profile.record_earned(neptu_reward)?;
+    }
+
+    issue_receipt(
+        &mut ctx.accounts.receipt,
+        Receipt {
+            payer,
+            beneficiary,
+            reading_type,
+            currency: PaymentCurrency::Sol,
+            amount: sol_price,
+            issued_at: now,
+            message_hash,
+            burned: 0,
+            refunded: false,
+            reward: neptu_reward,
+            reward_recipient: ctx.accounts.reward_recipient.key(),
+            staker_yield: 0,
+            referral_bonus: 0,
+            quantity: 1,
+        },
+    );
+
+    msg!(
+        "Gift with SOL: {} lamports for {:?} to {}, reward: {} NEPTU",
+        sol_price,
+        reading_type,
+        beneficiary,
+        neptu_reward
+    );
+
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.payer.to_account_info(),
+                to: ctx.accounts.treasury_vault.to_account_info(),
+            },
+        ),
+        sol_price,
+    )?;
+    ctx.accounts
+        .treasury_vault
+        .record_deposit(payer, sol_price)?;
+
+    if neptu_reward == 0 {
+        msg!("No NEPTU reward paid for this gift");
+        return Ok(());
+    }
+
+    let seeds = &[b"economy".as_ref(), &[ctx.bumps.economy_authority]];
+    let signer_seeds = &[&seeds[..]];
+
+    transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.rewards_pool.to_account_info(),
+                to: ctx.accounts.recipient_neptu_account.to_account_info(),
+                authority: ctx.accounts.economy_authority.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        neptu_reward,
+    )?;
+    msg!("Transferred {} NEPTU gift reward", neptu_reward);
+
+    Ok(())
+}
+
+pub(crate) fn gift_with_neptu(
+    ctx: Context<GiftWithNeptu>,
+    reading_type: ReadingType,
+    message_hash: Option<[u8; 32]>,
+) -> Result<()> {
+    let config = &ctx.accounts.pricing_config;
+    let neptu_price = config.get_neptu_price(&reading_type);
+    let now = Clock::get()?.unix_timestamp;
+    let payer = ctx.accounts.payer.key();
+    let beneficiary = ctx.accounts.beneficiary.key();
+
+    let split = settle_neptu_payment(
+        config,
+        &mut ctx.accounts.economy_state,
+        ctx.accounts.staking_pool.as_deref_mut(),
+        NeptuPaymentAccounts {
+            token_program: &ctx.accounts.token_program,
+            neptu_mint: &ctx.accounts.neptu_mint,
+            from: &ctx.accounts.payer_neptu_account,
+            authority: ctx.accounts.payer.to_account_info(),
+            ecosystem_pool: &ctx.accounts.ecosystem_pool,
+            stake_vault: ctx.accounts.stake_vault.as_ref(),
+        },
+        neptu_price,
+        &[],
+    )?;
+
+    let payer_profile = &mut ctx.accounts.payer_profile;
+    record_gift(
+        payer_profile,
+        &mut ctx.accounts.beneficiary_profile,
+        payer,
+        beneficiary,
+        reading_type,
+        now,
+    )?;
+    payer_profile.total_neptu_spent = payer_profile
+        .total_neptu_spent
+        .checked_add(neptu_price)
+        .ok_or(NeptuError::Overflow)?;
+    payer_profile.total_neptu_burned = payer_profile
+        .total_neptu_burned
+        .checked_add(split.burned)
+        .ok_or(NeptuError::Overflow)?;
+
+    issue_receipt(
+        &mut ctx.accounts.receipt,
+        Receipt {
+            payer,
+            beneficiary,
+            reading_type,
+            currency: PaymentCurrency::Neptu,
+            amount: neptu_price,
+            issued_at: now,
+            message_hash,
+            burned: split.burned,
+            refunded: false,
+            reward: 0,
+            reward_recipient: payer,
+            staker_yield: split.staker_yield,
+            referral_bonus: 0,
+            quantity: 1,
+        },
+    );
+
+    msg!(
+        "Gift with NEPTU: {} total for {:?} to {}, {} burned, {} recycled",
+        neptu_price,
+        reading_type,
+        beneficiary,
+        split.burned,
+        split.recycled
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(reading_type: ReadingType, reward_to_beneficiary: bool)]
+pub struct GiftWithSol<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Pays rent and fees; may be a sponsor other than the gifting wallet
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+
+    /// CHECK: Gift recipient, any wallet other than the payer
+    #[account(constraint = beneficiary.key() != payer.key() @ NeptuError::InvalidBeneficiary)]
+    pub beneficiary: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"pricing_config"],
+        bump,
+    )]
+    pub pricing_config: Account<'info, PricingConfig>,
+
+    /// CHECK: Pyth SOL/USD price update, must be `pricing_config.price_feed`;
+    /// required for USD-priced readings
+    pub price_feed: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        mut,
+        seeds = [b"economy_state"],
+        bump,
+        constraint = !economy_state.paused @ NeptuError::ProgramPaused,
+    )]
+    pub economy_state: Account<'info, EconomyState>,
+
+    #[account(
+        init_if_needed,
+        seeds = [b"profile", payer.key().as_ref()],
+        bump,
+        payer = fee_payer,
+        space = UserProfile::SIZE,
+    )]
+    pub payer_profile: Account<'info, UserProfile>,
+
+    #[account(
+        init_if_needed,
+        seeds = [b"profile", beneficiary.key().as_ref()],
+        bump,
+        payer = fee_payer,
+        space = UserProfile::SIZE,
+    )]
+    pub beneficiary_profile: Account<'info, UserProfile>,
+
+    #[account(
+        init,
+        seeds = [
+            b"receipt",
+            payer.key().as_ref(),
+            &payer_profile.receipts_paid.to_le_bytes(),
+        ],
+        bump,
+        payer = fee_payer,
+        space = Receipt::SIZE,
+    )]
+    pub receipt: Account<'info, Receipt>,
+
+    /// CHECK: Receives the NEPTU reward, the beneficiary or the payer
+    #[account(
+        constraint = reward_recipient.key()
+            == if reward_to_beneficiary { beneficiary.key() } else { payer.key() }
+            @ NeptuError::InvalidBeneficiary
+    )]
+    pub reward_recipient: UncheckedAccount<'info>,
+
+    /// Emission budget tracking of the reward recipient
+    #[account(
+        init_if_needed,
+        seeds = [b"claim", reward_recipient.key().as_ref()],
+        bump,
+        payer = fee_payer,
+        space = ClaimRecord::SIZE,
+    )]
+    pub claim_record: Account<'info, ClaimRecord>,
+
+    /// IOU record of the reward recipient, required when the pool is short under Iou
+    #[account(
+        init_if_needed,
+        seeds = [b"pending_reward", reward_recipient.key().as_ref()],
+        bump,
+        payer = fee_payer,
+        space = PendingReward::SIZE,
+    )]
+    pub pending_reward: Option<Account<'info, PendingReward>>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury_vault"],
+        bump = treasury_vault.bump,
+    )]
+    pub treasury_vault: Account<'info, TreasuryVault>,
+
+    #[account(address = economy_state.neptu_mint)]
+    pub neptu_mint: Account<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = fee_payer,
+        associated_token::mint = neptu_mint,
+        associated_token::authority = reward_recipient,
+    )]
+    pub recipient_neptu_account: Account<'info, TokenAccount>,
+
+    /// Rewards pool: ATA owned by economy_authority PDA
+    #[account(
+        mut,
+        associated_token::mint = neptu_mint,
+        associated_token::authority = economy_authority,
+    )]
+    pub rewards_pool: Account<'info, TokenAccount>,
+
+    /// CHECK: Economy authority PDA (rewards pool owner)
+    #[account(
+        seeds = [b"economy"],
+        bump,
+    )]
+    pub economy_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct GiftWithNeptu<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Pays rent and fees; may be a sponsor other than the gifting wallet
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+
+    /// CHECK: Gift recipient, any wallet other than the payer
+    #[account(constraint = beneficiary.key() != payer.key() @ NeptuError::InvalidBeneficiary)]
+    pub beneficiary: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"pricing_config"],
+        bump,
+    )]
+    pub pricing_config: Account<'info, PricingConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"economy_state"],
+        bump,
+        constraint = !economy_state.paused @ NeptuError::ProgramPaused,
+    )]
+    pub economy_state: Account<'info, EconomyState>,
+
+    #[account(
+        init_if_needed,
+        seeds = [b"profile", payer.key().as_ref()],
+        bump,
+        payer = fee_payer,
+        space = UserProfile::SIZE,
+    )]
+    pub payer_profile: Account<'info, UserProfile>,
+
+    #[account(
+        init_if_needed,
+        seeds = [b"profile", beneficiary.key().as_ref()],
+        bump,
+        payer = fee_payer,
+        space = UserProfile::SIZE,
+    )]
+    pub beneficiary_profile: Account<'info, UserProfile>,
+
+    #[account(
+        init,
+        seeds = [
+            b"receipt",
+            payer.key().as_ref(),
+            &payer_profile.receipts_paid.to_le_bytes(),
+        ],
+        bump,
+        payer = fee_payer,
+        space = Receipt::SIZE,
+    )]
+    pub receipt: Account<'info, Receipt>,
+
+    #[account(mut, address = economy_state.neptu_mint)]
+    pub neptu_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = neptu_mint,
+        token::authority = payer,
+    )]
+    pub payer_neptu_account: Account<'info, TokenAccount>,
+
+    #[account(mut, address = economy_state.ecosystem_pool)]
+    pub ecosystem_pool: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"staking_pool"],
+        bump,
+    )]
+    pub staking_pool: Option<Account<'info, StakingPool>>,
+
+    /// Receives the stakers' yield share of the recycled amount once staking is set up
+    #[account(
+        mut,
+        seeds = [b"stake_vault"],
+        bump,
+    )]
+    pub stake_vault: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Book a gift on both profiles and advance the payer's receipt index
+fn record_gift(
+    payer_profile: &mut UserProfile,
+    beneficiary_profile: &mut UserProfile,
+    payer: Pubkey,
+    beneficiary: Pubkey,
+    reading_type: ReadingType,
+    now: i64,
+) -> Result<()> {
+    payer_profile.touch(payer, now);
+    payer_profile.receipts_paid = payer_profile
+        .receipts_paid
+        .checked_add(1)
+        .ok_or(NeptuError::Overflow)?;
+    payer_profile.gifts_sent = payer_profile
+        .gifts_sent
+        .checked_add(1)
+        .ok_or(NeptuError::Overflow)?;
+    beneficiary_profile.record_reading(beneficiary, reading_type, now)?;
+    beneficiary_profile.gifts_received = beneficiary_profile
+        .gifts_received
+        .checked_add(1)
+        .ok_or(NeptuError::Overflow)?;
+    Ok(())
+}
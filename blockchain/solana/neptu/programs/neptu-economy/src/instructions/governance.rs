@@ -0,0 +1,398 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer, Mint, Token, TokenAccount, Transfer};
+
+use crate::{errors::NeptuError, events::*, state::*};
+
+pub(crate) fn initialize_governance(
+    ctx: Context<InitializeGovernance>,
+    quorum: u64,
+    proposal_threshold: u64,
+    voting_period: i64,
+    execution_delay: i64,
+) -> Result<()> {
+    require!(
+        quorum > 0 && voting_period > 0 && execution_delay >= 0,
+        NeptuError::InvalidAmount
+    );
+
+    let governance = &mut ctx.accounts.governance;
+    governance.quorum = quorum;
+    governance.proposal_threshold = proposal_threshold;
+    governance.voting_period = voting_period;
+    governance.execution_delay = execution_delay;
+    governance.proposal_count = 0;
+    governance.vote_vault = ctx.accounts.vote_vault.key();
+    msg!(
+        "Governance initialized: quorum {}, {}s voting, {}s delay",
+        quorum,
+        voting_period,
+        execution_delay
+    );
+    Ok(())
+}
+
+pub(crate) fn create_governance_proposal(
+    ctx: Context<CreateGovernanceProposal>,
+    change: ParameterChange,
+) -> Result<()> {
+    change.validate()?;
+
+    let governance = &mut ctx.accounts.governance;
+    require!(
+        ctx.accounts.proposer_neptu_account.amount >= governance.proposal_threshold,
+        NeptuError::BelowProposalThreshold
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.id = governance.proposal_count;
+    proposal.proposer = ctx.accounts.proposer.key();
+    proposal.change = change;
+    proposal.votes_for = 0;
+    proposal.votes_against = 0;
+    proposal.created_at = now;
+    proposal.voting_ends_at = now
+        .checked_add(governance.voting_period)
+        .ok_or(NeptuError::Overflow)?;
+    proposal.executable_at = proposal
+        .voting_ends_at
+        .checked_add(governance.execution_delay)
+        .ok_or(NeptuError::Overflow)?;
+    proposal.executed = false;
+
+    governance.proposal_count = governance
+        .proposal_count
+        .checked_add(1)
+        .ok_or(NeptuError::Overflow)?;
+
+    emit!(GovernanceProposalCreated {
+        id: proposal.id,
+        proposer: proposal.proposer,
+        change,
+        voting_ends_at: proposal.voting_ends_at,
+    });
+    msg!("Governance proposal {} created: {:?}", proposal.id, change);
+    Ok(())
+}
+
+pub(crate) fn cast_vote(ctx: Context<CastVote>, support: bool, amount: u64) -> Result<()> {
+    require!(amount > 0, NeptuError::InvalidAmount);
+
+    let proposal = &mut ctx.accounts.proposal;
+    require!(
+        Clock::get()?.unix_timestamp < proposal.voting_ends_at,
+        NeptuError::VotingClosed
+    );
+
+    transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.voter_neptu_account.to_account_info(),
+                to: ctx.accounts.vote_vault.to_account_info(),
+                authority: ctx.accounts.voter.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    if support {
+        proposal.votes_for = proposal
+            .votes_for
+            .checked_add(amount)
+            .ok_or(NeptuError::Overflow)?;
+    } else {
+        proposal.votes_against = proposal
+            .votes_against
+            .checked_add(amount)
+            .ok_or(NeptuError::Overflow)?;
+    }
+
+    let vote = &mut ctx.accounts.vote_record;
+    vote.voter = ctx.accounts.voter.key();
+    vote.proposal = proposal.key();
+    vote.amount = amount;
+    vote.support = support;
+
+    emit!(VoteCast {
+        proposal_id: proposal.id,
+        voter: vote.voter,
+        support,
+        amount,
+    });
+    msg!(
+        "Vote on proposal {}: {} NEPTU {}",
+        proposal.id,
+        amount,
+        if support { "for" } else { "against" }
+    );
+    Ok(())
+}
+
+pub(crate) fn withdraw_vote(ctx: Context<WithdrawVote>) -> Result<()> {
+    require!(
+        Clock::get()?.unix_timestamp >= ctx.accounts.proposal.voting_ends_at,
+        NeptuError::VotingStillOpen
+    );
+
+    let amount = ctx.accounts.vote_record.amount;
+    let seeds = &[b"economy".as_ref(), &[ctx.bumps.economy_authority]];
+    let signer_seeds = &[&seeds[..]];
+
+    transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.vote_vault.to_account_info(),
+                to: ctx.accounts.voter_neptu_account.to_account_info(),
+                authority: ctx.accounts.economy_authority.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+    )?;
+    msg!("Returned {} NEPTU vote lock", amount);
+    Ok(())
+}
+
+pub(crate) fn execute_governance_proposal(ctx: Context<ExecuteGovernanceProposal>) -> Result<()> {
+    let governance = &ctx.accounts.governance;
+    let proposal = &mut ctx.accounts.proposal;
+    require!(!proposal.executed, NeptuError::ProposalAlreadyExecuted);
+    require!(
+        Clock::get()?.unix_timestamp >= proposal.executable_at,
+        NeptuError::ExecutionDelayNotElapsed
+    );
+
+    let total_votes = proposal
+        .votes_for
+        .checked_add(proposal.votes_against)
+        .ok_or(NeptuError::Overflow)?;
+    require!(
+        total_votes >= governance.quorum,
+        NeptuError::QuorumNotReached
+    );
+    require!(
+        proposal.votes_for > proposal.votes_against,
+        NeptuError::ProposalRejected
+    );
+
+    proposal.change.apply(
+        &mut ctx.accounts.pricing_config,
+        &mut ctx.accounts.economy_state,
+    )?;
+    proposal.executed = true;
+
+    emit!(GovernanceProposalExecuted {
+        id: proposal.id,
+        change: proposal.change,
+        votes_for: proposal.votes_for,
+        votes_against: proposal.votes_against,
+    });
+    msg!("Governance proposal {} executed", proposal.id);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeGovernance<'info> {
+    #[account(
+        mut,
+        constraint = authority.key() == economy_state.authority @ NeptuError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"economy_state"],
+        bump,
+    )]
+    pub economy_state: Account<'info, EconomyState>,
+
+    #[account(
+        init,
+        seeds = [b"governance"],
+        bump,
+        payer = authority,
+        space = GovernanceConfig::SIZE,
+    )]
+    pub governance: Account<'info, GovernanceConfig>,
+
+    #[account(address = economy_state.neptu_mint)]
+    pub neptu_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        seeds = [b"vote_vault"],
+        bump,
+        payer = authority,
+        token::mint = neptu_mint,
+        token::authority = economy_authority,
+    )]
+    pub vote_vault: Account<'info, TokenAccount>,
+
+    /// CHECK: Economy authority PDA (vault owner)
+    #[account(
+        seeds = [b"economy"],
+        bump,
+    )]
+    pub economy_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateGovernanceProposal<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"governance"],
+        bump,
+    )]
+    pub governance: Account<'info, GovernanceConfig>,
+
+    #[account(
+        init,
+        seeds = [b"gov_proposal", governance.proposal_count.to_le_bytes().as_ref()],
+        bump,
+        payer = proposer,
+        space = GovernanceProposal::SIZE,
+    )]
+    pub proposal: Account<'info, GovernanceProposal>,
+
+    #[account(
+        seeds = [b"economy_state"],
+        bump,
+    )]
+    pub economy_state: Account<'info, EconomyState>,
+
+    #[account(
+        token::mint = economy_state.neptu_mint,
+        token::authority = proposer,
+    )]
+    pub proposer_neptu_account: Account<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CastVote<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    #[account(
+        seeds = [b"governance"],
+        bump,
+    )]
+    pub governance: Account<'info, GovernanceConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"gov_proposal", proposal.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub proposal: Account<'info, GovernanceProposal>,
+
+    /// One vote per voter per proposal
+    #[account(
+        init,
+        seeds = [b"vote", proposal.key().as_ref(), voter.key().as_ref()],
+        bump,
+        payer = voter,
+        space = VoteRecord::SIZE,
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+
+    #[account(
+        mut,
+        token::authority = voter,
+    )]
+    pub voter_neptu_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = governance.vote_vault,
+    )]
+    pub vote_vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawVote<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    #[account(
+        seeds = [b"governance"],
+        bump,
+    )]
+    pub governance: Account<'info, GovernanceConfig>,
+
+    #[account(
+        seeds = [b"gov_proposal", proposal.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub proposal: Account<'info, GovernanceProposal>,
+
+    #[account(
+        mut,
+        close = voter,
+        seeds = [b"vote", proposal.key().as_ref(), voter.key().as_ref()],
+        bump,
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+
+    #[account(
+        mut,
+        token::authority = voter,
+    )]
+    pub voter_neptu_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = governance.vote_vault,
+    )]
+    pub vote_vault: Account<'info, TokenAccount>,
+
+    /// CHECK: Economy authority PDA (vault owner)
+    #[account(
+        seeds = [b"economy"],
+        bump,
+    )]
+    pub economy_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteGovernanceProposal<'info> {
+    #[account(
+        seeds = [b"governance"],
+        bump,
+    )]
+    pub governance: Account<'info, GovernanceConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"gov_proposal", proposal.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub proposal: Account<'info, GovernanceProposal>,
+
+    #[account(
+        mut,
+        seeds = [b"pricing_config"],
+        bump,
+    )]
+    pub pricing_config: Account<'info, PricingConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"economy_state"],
+        bump,
+    )]
+    pub economy_state: Account<'info, EconomyState>,
+}
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::NeptuError, state::*};
+
+pub(crate) fn set_loyalty_tiers(
+    ctx: Context<SetLoyaltyTiers>,
+    tiers: Vec<LoyaltyTier>,
+) -> Result<()> {
+    let program = &mut ctx.accounts.loyalty_program;
+    program.set_tiers(tiers)?;
+    msg!("Loyalty tiers updated: {} tiers", program.tiers.len());
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetLoyaltyTiers<'info> {
+    #[account(
+        mut,
+        constraint = roles.authorize(
+            &pricing_config.authority,
+            &authority.key(),
+            Role::PricingManager,
+        ) @ NeptuError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"pricing_config"],
+        bump,
+    )]
+    pub pricing_config: Account<'info, PricingConfig>,
+
+    #[account(
+        seeds = [b"roles"],
+        bump,
+    )]
+    pub roles: Account<'info, Roles>,
+
+    #[account(
+        init_if_needed,
+        seeds = [b"loyalty"],
+        bump,
+        payer = authority,
+        space = LoyaltyProgram::SIZE,
+    )]
+    pub loyalty_program: Account<'info, LoyaltyProgram>,
+
+    pub system_program: Program<'info, System>,
+}
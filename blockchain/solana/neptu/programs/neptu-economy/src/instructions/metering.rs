@@ -0,0 +1,437 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token::{transfer, Mint, Token, TokenAccount, Transfer};
+
+use crate::{errors::NeptuError, events::*, helpers::*, state::*};
+
+pub(crate) fn deposit_metered(
+    ctx: Context<DepositMetered>,
+    currency: PaymentCurrency,
+    amount: u64,
+) -> Result<()> {
+    require!(amount > 0, NeptuError::InvalidAmount);
+    let user = ctx.accounts.user.key();
+    let metering = &mut ctx.accounts.metering;
+    metering.user = user;
+    metering.currency = currency;
+    metering.balance = metering
+        .balance
+        .checked_add(amount)
+        .ok_or(NeptuError::Overflow)?;
+    metering.total_deposited = metering
+        .total_deposited
+        .checked_add(amount)
+        .ok_or(NeptuError::Overflow)?;
+    metering.withdrawal_requested_at = 0;
+
+    match currency {
+        PaymentCurrency::Sol => {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.user.to_account_info(),
+                        to: ctx.accounts.metering.to_account_info(),
+                    },
+                ),
+                amount,
+            )?;
+        }
+        PaymentCurrency::Neptu => {
+            let (Some(user_neptu_account), Some(metering_vault)) = (
+                &ctx.accounts.user_neptu_account,
+                &ctx.accounts.metering_vault,
+            ) else {
+                return err!(NeptuError::TokenAccountMissing);
+            };
+            transfer(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: user_neptu_account.to_account_info(),
+                        to: metering_vault.to_account_info(),
+                        authority: ctx.accounts.user.to_account_info(),
+                    },
+                ),
+                amount,
+            )?;
+        }
+        PaymentCurrency::Token(_) => return err!(NeptuError::PaymentMintNotAccepted),
+    }
+
+    emit!(MeteredDeposit {
+        user,
+        currency,
+        amount,
+        balance: ctx.accounts.metering.balance,
+    });
+    msg!("Metered deposit: {} {:?}", amount, currency);
+    Ok(())
+}
+
+pub(crate) fn settle_usage(
+    ctx: Context<SettleUsage>,
+    units: u64,
+    rate: u64,
+    period_start: i64,
+    period_end: i64,
+) -> Result<()> {
+    let metering = &mut ctx.accounts.metering;
+    require!(
+        rate <= ctx
+            .accounts
+            .pricing_config
+            .max_usage_rate(metering.currency)?,
+        NeptuError::UsageRateTooHigh
+    );
+    let (charged, unpaid) = metering.settle(units, rate, period_start, period_end)?;
+    let (user, currency) = (metering.user, metering.currency);
+
+    let profile = &mut ctx.accounts.user_profile;
+    profile.touch(user, Clock::get()?.unix_timestamp);
+
+    if charged > 0 {
+        match currency {
+            PaymentCurrency::Sol => {
+                ctx.accounts.metering.sub_lamports(charged)?;
+                let vault = &mut ctx.accounts.treasury_vault;
+                vault.add_lamports(charged)?;
+                vault.record_deposit(user, charged)?;
+
+                let state = &mut ctx.accounts.economy_state;
+                state.total_sol_collected = state
+                    .total_sol_collected
+                    .checked_add(charged)
+                    .ok_or(NeptuError::Overflow)?;
+                profile.total_sol_spent = profile
+                    .total_sol_spent
+                    .checked_add(charged)
+                    .ok_or(NeptuError::Overflow)?;
+            }
+            PaymentCurrency::Neptu => {
+                let (Some(neptu_mint), Some(metering_vault), Some(ecosystem_pool)) = (
+                    &ctx.accounts.neptu_mint,
+                    &ctx.accounts.metering_vault,
+                    &ctx.accounts.ecosystem_pool,
+                ) else {
+                    return err!(NeptuError::TokenAccountMissing);
+                };
+                let seeds = &[b"economy".as_ref(), &[ctx.bumps.economy_authority]];
+                let split = settle_neptu_payment(
+                    &ctx.accounts.pricing_config,
+                    &mut ctx.accounts.economy_state,
+                    ctx.accounts.staking_pool.as_deref_mut(),
+                    NeptuPaymentAccounts {
+                        token_program: &ctx.accounts.token_program,
+                        neptu_mint,
+                        from: metering_vault,
+                        authority: ctx.accounts.economy_authority.to_account_info(),
+                        ecosystem_pool,
+                        stake_vault: ctx.accounts.stake_vault.as_ref(),
+                    },
+                    charged,
+                    &[&seeds[..]],
+                )?;
+                profile.total_neptu_spent = profile
+                    .total_neptu_spent
+                    .checked_add(charged)
+                    .ok_or(NeptuError::Overflow)?;
+                profile.total_neptu_burned = profile
+                    .total_neptu_burned
+                    .checked_add(split.burned)
+                    .ok_or(NeptuError::Overflow)?;
+            }
+            PaymentCurrency::Token(_) => return err!(NeptuError::PaymentMintNotAccepted),
+        }
+    }
+
+    emit!(UsageSettled {
+        user,
+        currency,
+        units,
+        rate,
+        period_start,
+        period_end,
+        charged,
+        unpaid,
+        balance: ctx.accounts.metering.balance,
+    });
+    msg!(
+        "Usage settled: {} units x {} = {} {:?} charged, {} unpaid",
+        units,
+        rate,
+        charged,
+        currency,
+        unpaid
+    );
+    Ok(())
+}
+
+pub(crate) fn request_metered_withdrawal(ctx: Context<RequestMeteredWithdrawal>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let metering = &mut ctx.accounts.metering;
+    metering.withdrawal_requested_at = now;
+    let available_at = now.saturating_add(ctx.accounts.economy_state.metering_notice_period);
+    msg!(
+        "Metered withdrawal requested, available at {}",
+        available_at
+    );
+    Ok(())
+}
+
+pub(crate) fn withdraw_metered(ctx: Context<WithdrawMetered>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let metering = &mut ctx.accounts.metering;
+    require!(
+        metering.withdrawal_requested_at > 0
+            && now
+                >= metering
+                    .withdrawal_requested_at
+                    .saturating_add(ctx.accounts.economy_state.metering_notice_period),
+        NeptuError::NoticePeriodActive
+    );
+    let amount = metering.balance;
+    let (user, currency) = (metering.user, metering.currency);
+    metering.balance = 0;
+    metering.withdrawal_requested_at = 0;
+
+    match currency {
+        PaymentCurrency::Sol => {
+            ctx.accounts.metering.sub_lamports(amount)?;
+            ctx.accounts.user.add_lamports(amount)?;
+        }
+        PaymentCurrency::Neptu => {
+            let (Some(metering_vault), Some(user_neptu_account)) = (
+                &ctx.accounts.metering_vault,
+                &ctx.accounts.user_neptu_account,
+            ) else {
+                return err!(NeptuError::TokenAccountMissing);
+            };
+            let seeds = &[b"economy".as_ref(), &[ctx.bumps.economy_authority]];
+            let signer_seeds = &[&seeds[..]];
+            transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: metering_vault.to_account_info(),
+                        to: user_neptu_account.to_account_info(),
+                        authority: ctx.accounts.economy_authority.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                amount,
+            )?;
+        }
+        PaymentCurrency::Token(_) => return err!(NeptuError::PaymentMintNotAccepted),
+    }
+
+    emit!(MeteredWithdrawal {
+        user,
+        currency,
+        amount,
+    });
+    msg!("Withdrew {} {:?} unused metered deposit", amount, currency);
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(currency: PaymentCurrency)]
+pub struct DepositMetered<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// Pays rent and fees; may be a sponsor other than the user
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+
+    #[account(
+        seeds = [b"economy_state"],
+        bump,
+        constraint = !economy_state.paused @ NeptuError::ProgramPaused,
+    )]
+    pub economy_state: Account<'info, EconomyState>,
+
+    #[account(
+        init_if_needed,
+        seeds = [b"metering", user.key().as_ref(), &[currency.seed()]],
+        bump,
+        payer = fee_payer,
+        space = MeteringAccount::SIZE,
+    )]
+    pub metering: Account<'info, MeteringAccount>,
+
+    #[account(address = economy_state.neptu_mint)]
+    pub neptu_mint: Option<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        token::authority = user,
+    )]
+    pub user_neptu_account: Option<Account<'info, TokenAccount>>,
+
+    /// Holds NEPTU deposits
+    #[account(
+        init_if_needed,
+        seeds = [b"metering_vault", metering.key().as_ref()],
+        bump,
+        payer = fee_payer,
+        token::mint = neptu_mint,
+        token::authority = economy_authority,
+    )]
+    pub metering_vault: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: Economy authority PDA (metering vault owner)
+    #[account(
+        seeds = [b"economy"],
+        bump,
+    )]
+    pub economy_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SettleUsage<'info> {
+    /// Backend key signing usage reports
+    #[account(
+        mut,
+        address = economy_state.fulfillment_signer @ NeptuError::Unauthorized
+    )]
+    pub fulfillment_signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"metering", metering.user.as_ref(), &[metering.currency.seed()]],
+        bump,
+    )]
+    pub metering: Account<'info, MeteringAccount>,
+
+    #[account(
+        seeds = [b"pricing_config"],
+        bump,
+    )]
+    pub pricing_config: Account<'info, PricingConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"economy_state"],
+        bump,
+        constraint = !economy_state.paused @ NeptuError::ProgramPaused,
+    )]
+    pub economy_state: Account<'info, EconomyState>,
+
+    #[account(
+        init_if_needed,
+        seeds = [b"profile", metering.user.as_ref()],
+        bump,
+        payer = fulfillment_signer,
+        space = UserProfile::SIZE,
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury_vault"],
+        bump = treasury_vault.bump,
+    )]
+    pub treasury_vault: Account<'info, TreasuryVault>,
+
+    #[account(mut, address = economy_state.neptu_mint)]
+    pub neptu_mint: Option<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [b"metering_vault", metering.key().as_ref()],
+        bump,
+    )]
+    pub metering_vault: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut, address = economy_state.ecosystem_pool)]
+    pub ecosystem_pool: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"staking_pool"],
+        bump,
+    )]
+    pub staking_pool: Option<Account<'info, StakingPool>>,
+
+    /// Receives the stakers' yield share once staking is set up
+    #[account(
+        mut,
+        seeds = [b"stake_vault"],
+        bump,
+    )]
+    pub stake_vault: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: Economy authority PDA (metering vault owner)
+    #[account(
+        seeds = [b"economy"],
+        bump,
+    )]
+    pub economy_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RequestMeteredWithdrawal<'info> {
+    #[account(address = metering.user @ NeptuError::Unauthorized)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"metering", metering.user.as_ref(), &[metering.currency.seed()]],
+        bump,
+    )]
+    pub metering: Account<'info, MeteringAccount>,
+
+    #[account(
+        seeds = [b"economy_state"],
+        bump,
+    )]
+    pub economy_state: Account<'info, EconomyState>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawMetered<'info> {
+    #[account(mut, address = metering.user @ NeptuError::Unauthorized)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"metering", metering.user.as_ref(), &[metering.currency.seed()]],
+        bump,
+    )]
+    pub metering: Account<'info, MeteringAccount>,
+
+    #[account(
+        seeds = [b"economy_state"],
+        bump,
+    )]
+    pub economy_state: Account<'info, EconomyState>,
+
+    #[account(
+        mut,
+        seeds = [b"metering_vault", metering.key().as_ref()],
+        bump,
+    )]
+    pub metering_vault: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::authority = user,
+    )]
+    pub user_neptu_account: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: Economy authority PDA (metering vault owner)
+    #[account(
+        seeds = [b"economy"],
+        bump,
+    )]
+    pub economy_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
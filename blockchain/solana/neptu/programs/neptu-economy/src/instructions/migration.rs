@@ -0,0 +1,187 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::{constants::*, errors::NeptuError, state::*};
+
+pub(crate) fn migrate_pricing_config(ctx: Context<MigratePricingConfig>) -> Result<()> {
+    let info = ctx.accounts.pricing_config.to_account_info();
+    let v0: PricingConfigV0 =
+        read_v0_account(&info, PricingConfig::DISCRIMINATOR, PricingConfigV0::SIZE)?;
+    require_keys_eq!(
+        v0.authority,
+        ctx.accounts.authority.key(),
+        NeptuError::Unauthorized
+    );
+
+    let config = PricingConfig {
+        authority: v0.authority,
+        potensi_sol_price: v0.potensi_sol_price,
+        peluang_sol_price: v0.peluang_sol_price,
+        ai_chat_sol_price: v0.ai_chat_sol_price,
+        compatibility_sol_price: v0.compatibility_sol_price,
+        potensi_neptu_price: v0.potensi_neptu_price,
+        peluang_neptu_price: v0.peluang_neptu_price,
+        ai_chat_neptu_price: v0.ai_chat_neptu_price,
+        compatibility_neptu_price: v0.compatibility_neptu_price,
+        burn_rate_bps: BURN_RATE_BPS as u16,
+        reward_multiplier_bps: BPS_DENOMINATOR as u16,
+        usd_prices_cents: [0; 4],
+        price_feed: Pubkey::default(),
+        max_price_age: defaults::MAX_PRICE_AGE,
+        max_price_conf_bps: defaults::MAX_PRICE_CONF_BPS,
+        max_sol_usage_rate: defaults::MAX_SOL_USAGE_RATE,
+        max_neptu_usage_rate: defaults::MAX_NEPTU_USAGE_RATE,
+    };
+    write_migrated_account(
+        &info,
+        &config,
+        PricingConfig::SIZE,
+        &ctx.accounts.authority,
+        &ctx.accounts.system_program,
+    )?;
+    msg!("Pricing config migrated");
+    Ok(())
+}
+
+pub(crate) fn migrate_economy_state(ctx: Context<MigrateEconomyState>) -> Result<()> {
+    let info = ctx.accounts.economy_state.to_account_info();
+    let v0: EconomyStateV0 =
+        read_v0_account(&info, EconomyState::DISCRIMINATOR, EconomyStateV0::SIZE)?;
+    require_keys_eq!(
+        v0.authority,
+        ctx.accounts.authority.key(),
+        NeptuError::Unauthorized
+    );
+
+    let state = EconomyState::from(v0);
+    write_migrated_account(
+        &info,
+        &state,
+        EconomyState::SIZE,
+        &ctx.accounts.authority,
+        &ctx.accounts.system_program,
+    )?;
+    msg!("Economy state migrated");
+    Ok(())
+}
+
+pub(crate) fn migrate_claim_record(ctx: Context<MigrateClaimRecord>) -> Result<()> {
+    let info = ctx.accounts.claim_record.to_account_info();
+    let v0: ClaimRecordV0 =
+        read_v0_account(&info, ClaimRecord::DISCRIMINATOR, ClaimRecordV0::SIZE)?;
+
+    let record = ClaimRecord {
+        user: v0.user,
+        nonce_base: v0.last_nonce.checked_add(1).ok_or(NeptuError::Overflow)?,
+        nonce_bitmap: [0; NONCE_WINDOW_WORDS],
+        total_claimed: v0.total_claimed,
+        epoch: 0,
+        epoch_emitted: 0,
+    };
+    write_migrated_account(
+        &info,
+        &record,
+        ClaimRecord::SIZE,
+        &ctx.accounts.user,
+        &ctx.accounts.system_program,
+    )?;
+    msg!("Claim record migrated for {}", record.user);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct MigratePricingConfig<'info> {
+    /// Pricing authority; pays rent for the added fields
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: v0 pricing config, decoded and checked by the handler
+    #[account(
+        mut,
+        seeds = [b"pricing_config"],
+        bump,
+        owner = crate::ID,
+    )]
+    pub pricing_config: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateEconomyState<'info> {
+    /// Economy authority; pays rent for the added fields
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: v0 economy state, decoded and checked by the handler
+    #[account(
+        mut,
+        seeds = [b"economy_state"],
+        bump,
+        owner = crate::ID,
+    )]
+    pub economy_state: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateClaimRecord<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// CHECK: v0 claim record of the user, decoded by the handler
+    #[account(
+        mut,
+        seeds = [b"claim", user.key().as_ref()],
+        bump,
+        owner = crate::ID,
+    )]
+    pub claim_record: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Decode an account still in its v0 layout; fails once it has been migrated
+fn read_v0_account<T: AnchorDeserialize>(
+    info: &AccountInfo,
+    discriminator: &[u8],
+    v0_size: usize,
+) -> Result<T> {
+    let data = info.try_borrow_data()?;
+    require!(
+        data.starts_with(discriminator),
+        ErrorCode::AccountDiscriminatorMismatch
+    );
+    require!(data.len() == v0_size, NeptuError::AlreadyMigrated);
+    T::deserialize(&mut &data[8..]).map_err(|_| error!(ErrorCode::AccountDidNotDeserialize))
+}
+
+/// Grow a migrated account to `size`, topping up its rent from `payer`, and write it
+fn write_migrated_account<'info, T: AccountSerialize>(
+    info: &AccountInfo<'info>,
+    account: &T,
+    size: usize,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    let rent = Rent::get()?
+        .minimum_balance(size)
+        .saturating_sub(info.lamports());
+    if rent > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: info.clone(),
+                },
+            ),
+            rent,
+        )?;
+    }
+    info.resize(size)?;
+    let mut data = info.try_borrow_mut_data()?;
+    let mut writer: &mut [u8] = &mut data;
+    account.try_serialize(&mut writer)
+}
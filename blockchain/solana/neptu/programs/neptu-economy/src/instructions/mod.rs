@@ -0,0 +1,45 @@
+mod admin;
+mod batch;
+mod compatibility;
+mod credits;
+mod gift;
+mod governance;
+mod loyalty;
+mod metering;
+mod migration;
+mod multisig;
+mod order;
+mod payment;
+#[cfg(feature = "mock-price-feed")]
+mod price_feed;
+mod pricing;
+mod referral;
+mod refund;
+mod rent_sponsorship;
+mod rewards;
+mod session;
+mod staking;
+mod treasury;
+
+pub use admin::*;
+pub use batch::*;
+pub use compatibility::*;
+pub use credits::*;
+pub use gift::*;
+pub use governance::*;
+pub use loyalty::*;
+pub use metering::*;
+pub use migration::*;
+pub use multisig::*;
+pub use order::*;
+pub use payment::*;
+#[cfg(feature = "mock-price-feed")]
+pub use price_feed::*;
+pub use pricing::*;
+pub use referral::*;
+pub use refund::*;
+pub use rent_sponsorship::*;
+pub use rewards::*;
+pub use session::*;
+pub use staking::*;
+pub use treasury::*;
@@ -0,0 +1,275 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+
+use crate::{errors::NeptuError, events::*, state::*};
+
+pub(crate) fn create_multisig(
+    ctx: Context<CreateMultisig>,
+    members: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    let multisig = &mut ctx.accounts.multisig;
+    multisig.set_members(members, threshold)?;
+    multisig.nonce = 0;
+    multisig.proposal_count = 0;
+    multisig.signer_bump = ctx.bumps.multisig_signer;
+    msg!(
+        "Multisig created: {}-of-{}, signer {}",
+        multisig.threshold,
+        multisig.members.len(),
+        ctx.accounts.multisig_signer.key()
+    );
+    Ok(())
+}
+
+pub(crate) fn set_multisig_members(
+    ctx: Context<SetMultisigMembers>,
+    members: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    let multisig = &mut ctx.accounts.multisig;
+    multisig.set_members(members, threshold)?;
+    multisig.nonce = multisig.nonce.checked_add(1).ok_or(NeptuError::Overflow)?;
+    msg!(
+        "Multisig members updated: {}-of-{}, nonce {}",
+        multisig.threshold,
+        multisig.members.len(),
+        multisig.nonce
+    );
+    Ok(())
+}
+
+pub(crate) fn propose_multisig_transaction(
+    ctx: Context<ProposeMultisigTransaction>,
+    program_id: Pubkey,
+    accounts: Vec<ProposalAccountMeta>,
+    data: Vec<u8>,
+) -> Result<()> {
+    let multisig = &mut ctx.accounts.multisig;
+    let proposer = ctx.accounts.proposer.key();
+    let index = multisig.member_index(&proposer)?;
+
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.id = multisig.proposal_count;
+    proposal.proposer = proposer;
+    proposal.program_id = program_id;
+    proposal.accounts = accounts;
+    proposal.data = data;
+    proposal.approvals = 1 << index;
+    proposal.multisig_nonce = multisig.nonce;
+    proposal.created_at = Clock::get()?.unix_timestamp;
+    proposal.executed_at = 0;
+
+    multisig.proposal_count = multisig
+        .proposal_count
+        .checked_add(1)
+        .ok_or(NeptuError::Overflow)?;
+
+    emit!(MultisigProposalCreated {
+        id: proposal.id,
+        proposer,
+        program_id,
+    });
+    msg!("Multisig proposal {} created", proposal.id);
+    Ok(())
+}
+
+pub(crate) fn approve_multisig_transaction(ctx: Context<ApproveMultisigTransaction>) -> Result<()> {
+    let multisig = &ctx.accounts.multisig;
+    let member = ctx.accounts.member.key();
+    let index = multisig.member_index(&member)?;
+
+    let proposal = &mut ctx.accounts.proposal;
+    require!(
+        proposal.executed_at == 0,
+        NeptuError::ProposalAlreadyExecuted
+    );
+    require!(
+        proposal.multisig_nonce == multisig.nonce,
+        NeptuError::ProposalStale
+    );
+    require!(
+        proposal.approvals & (1 << index) == 0,
+        NeptuError::AlreadyApproved
+    );
+    proposal.approvals |= 1 << index;
+
+    emit!(MultisigProposalApproved {
+        id: proposal.id,
+        member,
+        approvals: proposal.approvals.count_ones() as u8,
+    });
+    msg!(
+        "Multisig proposal {} approved ({}/{})",
+        proposal.id,
+        proposal.approvals.count_ones(),
+        multisig.threshold
+    );
+    Ok(())
+}
+
+pub(crate) fn execute_multisig_transaction<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ExecuteMultisigTransaction<'info>>,
+) -> Result<()> {
+    let multisig = &ctx.accounts.multisig;
+    multisig.member_index(&ctx.accounts.member.key())?;
+
+    let proposal = &mut ctx.accounts.proposal;
+    require!(
+        proposal.executed_at == 0,
+        NeptuError::ProposalAlreadyExecuted
+    );
+    require!(
+        proposal.multisig_nonce == multisig.nonce,
+        NeptuError::ProposalStale
+    );
+    require!(
+        proposal.approvals.count_ones() >= u32::from(multisig.threshold),
+        NeptuError::NotEnoughApprovals
+    );
+    proposal.executed_at = Clock::get()?.unix_timestamp;
+
+    let signer_key = ctx.accounts.multisig_signer.key();
+    let ix = Instruction {
+        program_id: proposal.program_id,
+        accounts: proposal
+            .accounts
+            .iter()
+            .map(|a| AccountMeta {
+                pubkey: a.pubkey,
+                is_signer: a.is_signer || a.pubkey == signer_key,
+                is_writable: a.is_writable,
+            })
+            .collect(),
+        data: proposal.data.clone(),
+    };
+
+    let seeds = &[b"multisig_signer".as_ref(), &[multisig.signer_bump]];
+    let signer_seeds = &[&seeds[..]];
+    invoke_signed(&ix, ctx.remaining_accounts, signer_seeds)?;
+
+    emit!(MultisigProposalExecuted {
+        id: proposal.id,
+        program_id: proposal.program_id,
+    });
+    msg!("Multisig proposal {} executed", proposal.id);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CreateMultisig<'info> {
+    #[account(
+        mut,
+        constraint = authority.key() == economy_state.authority @ NeptuError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"economy_state"],
+        bump,
+    )]
+    pub economy_state: Account<'info, EconomyState>,
+
+    #[account(
+        init,
+        seeds = [b"multisig"],
+        bump,
+        payer = authority,
+        space = Multisig::SIZE,
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    /// CHECK: Multisig signer PDA
+    #[account(
+        seeds = [b"multisig_signer"],
+        bump,
+    )]
+    pub multisig_signer: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetMultisigMembers<'info> {
+    #[account(
+        seeds = [b"multisig_signer"],
+        bump = multisig.signer_bump,
+    )]
+    pub multisig_signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"multisig"],
+        bump,
+    )]
+    pub multisig: Account<'info, Multisig>,
+}
+
+#[derive(Accounts)]
+#[instruction(program_id: Pubkey, accounts: Vec<ProposalAccountMeta>, data: Vec<u8>)]
+pub struct ProposeMultisigTransaction<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"multisig"],
+        bump,
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        init,
+        seeds = [b"multisig_proposal", multisig.proposal_count.to_le_bytes().as_ref()],
+        bump,
+        payer = proposer,
+        space = MultisigProposal::size(accounts.len(), data.len()),
+    )]
+    pub proposal: Account<'info, MultisigProposal>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveMultisigTransaction<'info> {
+    pub member: Signer<'info>,
+
+    #[account(
+        seeds = [b"multisig"],
+        bump,
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        mut,
+        seeds = [b"multisig_proposal", proposal.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub proposal: Account<'info, MultisigProposal>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteMultisigTransaction<'info> {
+    pub member: Signer<'info>,
+
+    #[account(
+        seeds = [b"multisig"],
+        bump,
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    /// CHECK: Multisig signer PDA, signs the proposed instruction
+    #[account(
+        seeds = [b"multisig_signer"],
+        bump = multisig.signer_bump,
+    )]
+    pub multisig_signer: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"multisig_proposal", proposal.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub proposal: Account<'info, MultisigProposal>,
+}
@@ -0,0 +1,603 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token::{
+    close_account, transfer, CloseAccount, Mint, Token, TokenAccount, Transfer,
+};
+
+use crate::{errors::NeptuError, events::*, helpers::*, state::*};
+
+pub(crate) fn create_sol_order(
+    ctx: Context<CreateSolOrder>,
+    order_id: u64,
+    reading_type: ReadingType,
+) -> Result<()> {
+    let amount = ctx
+        .accounts
+        .pricing_config
+        .resolve_sol_price(&reading_type, &ctx.accounts.price_feed)?;
+    let now = Clock::get()?.unix_timestamp;
+    let expires_at = now
+        .checked_add(ctx.accounts.economy_state.escrow_timeout)
+        .ok_or(NeptuError::Overflow)?;
+    ctx.accounts.order.set_inner(Order {
+        user: ctx.accounts.user.key(),
+        order_id,
+        reading_type,
+        currency: PaymentCurrency::Sol,
+        amount,
+        created_at: now,
+        expires_at,
+        rent_payer: ctx.accounts.fee_payer.key(),
+    });
+
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.user.to_account_info(),
+                to: ctx.accounts.order.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    emit!(OrderCreated {
+        order: ctx.accounts.order.key(),
+        user: ctx.accounts.user.key(),
+        reading_type,
+        currency: PaymentCurrency::Sol,
+        amount,
+        expires_at,
+    });
+    msg!("Escrowed {} lamports for {:?}", amount, reading_type);
+    Ok(())
+}
+
+pub(crate) fn create_neptu_order(
+    ctx: Context<CreateNeptuOrder>,
+    order_id: u64,
+    reading_type: ReadingType,
+) -> Result<()> {
+    let amount = ctx.accounts.pricing_config.get_neptu_price(&reading_type);
+    let now = Clock::get()?.unix_timestamp;
+    let expires_at = now
+        .checked_add(ctx.accounts.economy_state.escrow_timeout)
+        .ok_or(NeptuError::Overflow)?;
+    ctx.accounts.order.set_inner(Order {
+        user: ctx.accounts.user.key(),
+        order_id,
+        reading_type,
+        currency: PaymentCurrency::Neptu,
+        amount,
+        created_at: now,
+        expires_at,
+        rent_payer: ctx.accounts.fee_payer.key(),
+    });
+
+    transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.user_neptu_account.to_account_info(),
+                to: ctx.accounts.order_vault.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    emit!(OrderCreated {
+        order: ctx.accounts.order.key(),
+        user: ctx.accounts.user.key(),
+        reading_type,
+        currency: PaymentCurrency::Neptu,
+        amount,
+        expires_at,
+    });
+    msg!("Escrowed {} NEPTU for {:?}", amount, reading_type);
+    Ok(())
+}
+
+pub(crate) fn fulfill_order(ctx: Context<FulfillOrder>) -> Result<()> {
+    let order = &ctx.accounts.order;
+    let (user, reading_type, currency, escrowed) =
+        (order.user, order.reading_type, order.currency, order.amount);
+    let now = Clock::get()?.unix_timestamp;
+
+    let profile = &mut ctx.accounts.user_profile;
+    profile.record_reading(user, reading_type, now)?;
+
+    let (amount, burned, staker_yield, reward) = match currency {
+        PaymentCurrency::Sol => {
+            let tier = stake_benefits(
+                ctx.accounts.staking_pool.as_deref(),
+                &ctx.accounts.stake_position,
+                now,
+            );
+            let loyalty = loyalty_benefits(&ctx.accounts.loyalty_program, profile);
+            // The escrow holds the list price; the discount goes back to the user
+            let amount = loyalty.discounted(tier.discounted(escrowed));
+            ctx.accounts.order.sub_lamports(escrowed)?;
+            ctx.accounts.user.add_lamports(escrowed - amount)?;
+            let vault = &mut ctx.accounts.treasury_vault;
+            vault.add_lamports(amount)?;
+            vault.record_deposit(user, amount)?;
+
+            let state = &mut ctx.accounts.economy_state;
+            let claim_record = &mut ctx.accounts.claim_record;
+            claim_record.user = user;
+            let neptu_reward = grant_payment_reward(
+                state,
+                claim_record,
+                &mut ctx.accounts.pending_reward,
+                tier.boosted(ctx.accounts.pricing_config.get_sol_reward(&reading_type)?)?,
+                ctx.accounts.rewards_pool.amount,
+                now,
+            )?;
+            state.total_sol_collected = state
+                .total_sol_collected
+                .checked_add(amount)
+                .ok_or(NeptuError::Overflow)?;
+            profile.total_sol_spent = profile
+                .total_sol_spent
+                .checked_add(amount)
+                .ok_or(NeptuError::Overflow)?;
+            profile.record_earned(neptu_reward)?;
+
+            if neptu_reward > 0 {
+                let user_neptu_account = ctx
+                    .accounts
+                    .user_neptu_account
+                    .as_ref()
+                    .ok_or(NeptuError::TokenAccountMissing)?;
+                let seeds = &[b"economy".as_ref(), &[ctx.bumps.economy_authority]];
+                transfer(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        Transfer {
+                            from: ctx.accounts.rewards_pool.to_account_info(),
+                            to: user_neptu_account.to_account_info(),
+                            authority: ctx.accounts.economy_authority.to_account_info(),
+                        },
+                        &[&seeds[..]],
+                    ),
+                    neptu_reward,
+                )?;
+                msg!("Transferred {} NEPTU reward to user", neptu_reward);
+            }
+            (amount, 0, 0, neptu_reward)
+        }
+        PaymentCurrency::Neptu => {
+            let (Some(neptu_mint), Some(order_vault), Some(ecosystem_pool)) = (
+                &ctx.accounts.neptu_mint,
+                &ctx.accounts.order_vault,
+                &ctx.accounts.ecosystem_pool,
+            ) else {
+                return err!(NeptuError::TokenAccountMissing);
+            };
+            let seeds = &[b"economy".as_ref(), &[ctx.bumps.economy_authority]];
+            let signer_seeds = &[&seeds[..]];
+            let authority = ctx.accounts.economy_authority.to_account_info();
+            let split = settle_neptu_payment(
+                &ctx.accounts.pricing_config,
+                &mut ctx.accounts.economy_state,
+                ctx.accounts.staking_pool.as_deref_mut(),
+                NeptuPaymentAccounts {
+                    token_program: &ctx.accounts.token_program,
+                    neptu_mint,
+                    from: order_vault,
+                    authority: authority.clone(),
+                    ecosystem_pool,
+                    stake_vault: ctx.accounts.stake_vault.as_ref(),
+                },
+                escrowed,
+                signer_seeds,
+            )?;
+            profile.total_neptu_spent = profile
+                .total_neptu_spent
+                .checked_add(escrowed)
+                .ok_or(NeptuError::Overflow)?;
+            profile.total_neptu_burned = profile
+                .total_neptu_burned
+                .checked_add(split.burned)
+                .ok_or(NeptuError::Overflow)?;
+
+            close_account(CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                CloseAccount {
+                    account: order_vault.to_account_info(),
+                    destination: ctx.accounts.rent_payer.to_account_info(),
+                    authority,
+                },
+                signer_seeds,
+            ))?;
+            (escrowed, split.burned, split.staker_yield, 0)
+        }
+        PaymentCurrency::Token(_) => return err!(NeptuError::PaymentMintNotAccepted),
+    };
+
+    issue_receipt(
+        &mut ctx.accounts.receipt,
+        Receipt {
+            payer: user,
+            beneficiary: user,
+            reading_type,
+            currency,
+            amount,
+            issued_at: now,
+            message_hash: None,
+            burned,
+            refunded: false,
+            reward,
+            reward_recipient: user,
+            staker_yield,
+            referral_bonus: 0,
+            quantity: 1,
+        },
+    );
+    emit!(OrderSettled {
+        order: ctx.accounts.order.key(),
+        user,
+        fulfilled: true,
+    });
+    msg!(
+        "Order fulfilled: {} {:?} for {:?}",
+        amount,
+        currency,
+        reading_type
+    );
+    Ok(())
+}
+
+pub(crate) fn reclaim_order(ctx: Context<ReclaimOrder>) -> Result<()> {
+    let order = &ctx.accounts.order;
+    require!(
+        Clock::get()?.unix_timestamp >= order.expires_at,
+        NeptuError::OrderNotExpired
+    );
+
+    if order.currency == PaymentCurrency::Sol {
+        ctx.accounts.order.sub_lamports(order.amount)?;
+        ctx.accounts.user.add_lamports(order.amount)?;
+    } else if order.currency == PaymentCurrency::Neptu {
+        let (Some(order_vault), Some(user_neptu_account)) =
+            (&ctx.accounts.order_vault, &ctx.accounts.user_neptu_account)
+        else {
+            return err!(NeptuError::TokenAccountMissing);
+        };
+        let seeds = &[b"economy".as_ref(), &[ctx.bumps.economy_authority]];
+        let signer_seeds = &[&seeds[..]];
+        let token_program = ctx.accounts.token_program.to_account_info();
+        let authority = ctx.accounts.economy_authority.to_account_info();
+
+        transfer(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                Transfer {
+                    from: order_vault.to_account_info(),
+                    to: user_neptu_account.to_account_info(),
+                    authority: authority.clone(),
+                },
+                signer_seeds,
+            ),
+            order.amount,
+        )?;
+        close_account(CpiContext::new_with_signer(
+            token_program,
+            CloseAccount {
+                account: order_vault.to_account_info(),
+                destination: ctx.accounts.rent_payer.to_account_info(),
+                authority,
+            },
+            signer_seeds,
+        ))?;
+    }
+
+    emit!(OrderSettled {
+        order: order.key(),
+        user: order.user,
+        fulfilled: false,
+    });
+    msg!(
+        "Order reclaimed: {} {:?} refunded",
+        order.amount,
+        order.currency
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(order_id: u64)]
+pub struct CreateSolOrder<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// Pays rent and fees; may be a sponsor other than the user
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+
+    #[account(
+        seeds = [b"pricing_config"],
+        bump,
+    )]
+    pub pricing_config: Account<'info, PricingConfig>,
+
+    /// CHECK: Pyth SOL/USD price update, must be `pricing_config.price_feed`;
+    /// required for USD-priced readings
+    pub price_feed: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        seeds = [b"economy_state"],
+        bump,
+        constraint = !economy_state.paused @ NeptuError::ProgramPaused,
+    )]
+    pub economy_state: Account<'info, EconomyState>,
+
+    #[account(
+        init,
+        seeds = [b"order", user.key().as_ref(), &order_id.to_le_bytes()],
+        bump,
+        payer = fee_payer,
+        space = Order::SIZE,
+    )]
+    pub order: Account<'info, Order>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(order_id: u64)]
+pub struct CreateNeptuOrder<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// Pays rent and fees; may be a sponsor other than the user
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+
+    #[account(
+        seeds = [b"pricing_config"],
+        bump,
+    )]
+    pub pricing_config: Account<'info, PricingConfig>,
+
+    #[account(
+        seeds = [b"economy_state"],
+        bump,
+        constraint = !economy_state.paused @ NeptuError::ProgramPaused,
+    )]
+    pub economy_state: Account<'info, EconomyState>,
+
+    #[account(
+        init,
+        seeds = [b"order", user.key().as_ref(), &order_id.to_le_bytes()],
+        bump,
+        payer = fee_payer,
+        space = Order::SIZE,
+    )]
+    pub order: Account<'info, Order>,
+
+    #[account(address = economy_state.neptu_mint)]
+    pub neptu_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = neptu_mint,
+        token::authority = user,
+    )]
+    pub user_neptu_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        seeds = [b"order_vault", order.key().as_ref()],
+        bump,
+        payer = fee_payer,
+        token::mint = neptu_mint,
+        token::authority = economy_authority,
+    )]
+    pub order_vault: Account<'info, TokenAccount>,
+
+    /// CHECK: Economy authority PDA (order vault owner)
+    #[account(
+        seeds = [b"economy"],
+        bump,
+    )]
+    pub economy_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FulfillOrder<'info> {
+    /// Backend key attesting delivery
+    #[account(
+        mut,
+        address = economy_state.fulfillment_signer @ NeptuError::Unauthorized
+    )]
+    pub fulfillment_signer: Signer<'info>,
+
+    /// CHECK: Order owner, receives the discount off the escrowed SOL
+    #[account(mut, address = order.user)]
+    pub user: UncheckedAccount<'info>,
+
+    /// CHECK: Paid the order rent, receives it back
+    #[account(mut, address = order.rent_payer)]
+    pub rent_payer: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"order", order.user.as_ref(), &order.order_id.to_le_bytes()],
+        bump,
+        close = rent_payer,
+    )]
+    pub order: Account<'info, Order>,
+
+    #[account(
+        seeds = [b"pricing_config"],
+        bump,
+    )]
+    pub pricing_config: Account<'info, PricingConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"economy_state"],
+        bump,
+        constraint = !economy_state.paused @ NeptuError::ProgramPaused,
+    )]
+    pub economy_state: Account<'info, EconomyState>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury_vault"],
+        bump = treasury_vault.bump,
+    )]
+    pub treasury_vault: Account<'info, TreasuryVault>,
+
+    #[account(
+        init_if_needed,
+        seeds = [b"profile", order.user.as_ref()],
+        bump,
+        payer = fulfillment_signer,
+        space = UserProfile::SIZE,
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    #[account(
+        init,
+        seeds = [b"receipt", order.key().as_ref()],
+        bump,
+        payer = fulfillment_signer,
+        space = Receipt::SIZE,
+    )]
+    pub receipt: Account<'info, Receipt>,
+
+    /// Per-user emission budget tracking
+    #[account(
+        init_if_needed,
+        seeds = [b"claim", order.user.as_ref()],
+        bump,
+        payer = fulfillment_signer,
+        space = ClaimRecord::SIZE,
+    )]
+    pub claim_record: Account<'info, ClaimRecord>,
+
+    /// IOU record, required when the shortfall policy is Iou and the pool is short
+    #[account(
+        init_if_needed,
+        seeds = [b"pending_reward", order.user.as_ref()],
+        bump,
+        payer = fulfillment_signer,
+        space = PendingReward::SIZE,
+    )]
+    pub pending_reward: Option<Account<'info, PendingReward>>,
+
+    /// Rewards pool: ATA owned by economy_authority PDA
+    #[account(
+        mut,
+        associated_token::mint = economy_state.neptu_mint,
+        associated_token::authority = economy_authority,
+    )]
+    pub rewards_pool: Account<'info, TokenAccount>,
+
+    /// User's NEPTU account receiving the reward, required for rewarded SOL orders
+    #[account(
+        mut,
+        associated_token::mint = economy_state.neptu_mint,
+        associated_token::authority = user,
+    )]
+    pub user_neptu_account: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [b"stake", order.user.as_ref()],
+        bump,
+    )]
+    pub stake_position: Option<Account<'info, StakePosition>>,
+
+    /// Applies the profile's loyalty tier discount when given
+    #[account(
+        seeds = [b"loyalty"],
+        bump,
+    )]
+    pub loyalty_program: Option<Account<'info, LoyaltyProgram>>,
+
+    #[account(mut, address = economy_state.neptu_mint)]
+    pub neptu_mint: Option<Account<'info, Mint>>,
+
+    /// NEPTU held for the order, required for NEPTU orders
+    #[account(
+        mut,
+        seeds = [b"order_vault", order.key().as_ref()],
+        bump,
+    )]
+    pub order_vault: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut, address = economy_state.ecosystem_pool)]
+    pub ecosystem_pool: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"staking_pool"],
+        bump,
+    )]
+    pub staking_pool: Option<Account<'info, StakingPool>>,
+
+    /// Receives the stakers' yield share once staking is set up
+    #[account(
+        mut,
+        seeds = [b"stake_vault"],
+        bump,
+    )]
+    pub stake_vault: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: Economy authority PDA (order vault owner)
+    #[account(
+        seeds = [b"economy"],
+        bump,
+    )]
+    pub economy_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReclaimOrder<'info> {
+    #[account(mut, address = order.user @ NeptuError::Unauthorized)]
+    pub user: Signer<'info>,
+
+    /// CHECK: Paid the order rent, receives it back
+    #[account(mut, address = order.rent_payer)]
+    pub rent_payer: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"order", order.user.as_ref(), &order.order_id.to_le_bytes()],
+        bump,
+        close = rent_payer,
+    )]
+    pub order: Account<'info, Order>,
+
+    /// NEPTU held for the order, required for NEPTU orders
+    #[account(
+        mut,
+        seeds = [b"order_vault", order.key().as_ref()],
+        bump,
+    )]
+    pub order_vault: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::authority = user,
+    )]
+    pub user_neptu_account: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: Economy authority PDA (order vault owner)
+    #[account(
+        seeds = [b"economy"],
+        bump,
+    )]
+    pub economy_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,1211 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::associated_token::{get_associated_token_address, AssociatedToken};
+use anchor_spl::token::{transfer, Mint, Token, TokenAccount, Transfer};
+
+use crate::{constants::*, errors::NeptuError, events::*, helpers::*, state::*};
+
+pub(crate) fn pay_with_sol(ctx: Context<PayWithSol>, reading_type: ReadingType) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let new_claim_record = ctx.accounts.claim_record.user == Pubkey::default();
+    let new_profile = ctx.accounts.user_profile.first_activity_at == 0;
+    let new_neptu_account = open_neptu_account(
+        &ctx.accounts.user_neptu_account,
+        &ctx.accounts.fee_payer,
+        &ctx.accounts.user,
+        &ctx.accounts.neptu_mint,
+        &ctx.accounts.token_program,
+        &ctx.accounts.associated_token_program,
+        &ctx.accounts.system_program,
+    )?;
+    let config = &ctx.accounts.pricing_config;
+    let tier = stake_benefits(
+        ctx.accounts.staking_pool.as_deref(),
+        &ctx.accounts.stake_position,
+        now,
+    );
+    let loyalty = loyalty_benefits(&ctx.accounts.loyalty_program, &ctx.accounts.user_profile);
+    let list_price = config.resolve_sol_price(&reading_type, &ctx.accounts.price_feed)?;
+    let sol_price = loyalty.discounted(tier.discounted(list_price));
+
+    let user = ctx.accounts.user.key();
+    let pool_balance = ctx.accounts.rewards_pool.amount;
+    let state = &mut ctx.accounts.economy_state;
+    let claim_record = &mut ctx.accounts.claim_record;
+    claim_record.user = user;
+    let base_reward = tier.boosted(config.get_sol_reward(&reading_type)?)?;
+    let neptu_reward = grant_payment_reward(
+        state,
+        claim_record,
+        &mut ctx.accounts.pending_reward,
+        base_reward,
+        pool_balance,
+        now,
+    )?;
+    let referral_bonus = settle_referral(
+        state,
+        user,
+        &mut ctx.accounts.referral,
+        &mut ctx.accounts.referrer_stats,
+        &mut ctx.accounts.referrer_claim_record,
+        &ctx.accounts.referrer_neptu_account,
+        base_reward,
+        Some(pool_balance - neptu_reward),
+        now,
+    )?;
+
+    let profile = &mut ctx.accounts.user_profile;
+    profile.record_reading(user, reading_type, now)?;
+    profile.total_sol_spent = profile
+        .total_sol_spent
+        .checked_add(sol_price)
+        .ok_or(NeptuError::Overflow)?;
+    profile.record_earned(neptu_reward)?;
+    profile.refresh_loyalty_tier(&ctx.accounts.loyalty_program);
+    if let Some(receipt) = &mut ctx.accounts.receipt {
+        profile.receipts_paid = profile
+            .receipts_paid
+            .checked_add(1)
+            .ok_or(NeptuError::Overflow)?;
+        issue_receipt(
+            receipt,
+            Receipt {
+                payer: user,
+                beneficiary: user,
+                reading_type,
+                currency: PaymentCurrency::Sol,
+                amount: sol_price,
+                issued_at: now,
+                message_hash: None,
+                burned: 0,
+                refunded: false,
+                reward: neptu_reward,
+                reward_recipient: user,
+                staker_yield: 0,
+                referral_bonus,
+                quantity: 1,
+            },
+        );
+    }
+    state.total_sol_collected = state
+        .total_sol_collected
+        .checked_add(sol_price)
+        .ok_or(NeptuError::Overflow)?;
+    sponsor_rent(
+        &mut ctx.accounts.rent_sponsorship,
+        &mut ctx.accounts.sponsored_rent,
+        &ctx.accounts.fee_payer,
+        user,
+        &[
+            (SPONSOR_NEPTU_ACCOUNT, TokenAccount::LEN, new_neptu_account),
+            (SPONSOR_CLAIM_RECORD, ClaimRecord::SIZE, new_claim_record),
+            (SPONSOR_PROFILE, UserProfile::SIZE, new_profile),
+        ],
+    )?;
+
+    msg!(
+        "Pay with SOL: {} lamports for {:?}, reward: {} NEPTU",
+        sol_price,
+        reading_type,
+        neptu_reward
+    );
+
+    // Transfer SOL from user to treasury vault
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.user.to_account_info(),
+                to: ctx.accounts.treasury_vault.to_account_info(),
+            },
+        ),
+        sol_price,
+    )?;
+    ctx.accounts
+        .treasury_vault
+        .record_deposit(user, sol_price)?;
+    msg!("Transferred {} lamports to treasury vault", sol_price);
+
+    if neptu_reward == 0 {
+        msg!("No NEPTU reward paid for this payment");
+        return Ok(());
+    }
+
+    // Transfer NEPTU reward from rewards pool to user (NOT minting)
+    let seeds = &[b"economy".as_ref(), &[ctx.bumps.economy_authority]];
+    let signer_seeds = &[&seeds[..]];
+
+    transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.rewards_pool.to_account_info(),
+                to: ctx.accounts.user_neptu_account.to_account_info(),
+                authority: ctx.accounts.economy_authority.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        neptu_reward,
+    )?;
+    msg!("Transferred {} NEPTU reward to user", neptu_reward);
+
+    if let Some(referrer_account) = &ctx.accounts.referrer_neptu_account {
+        if referral_bonus > 0 {
+            transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.rewards_pool.to_account_info(),
+                        to: referrer_account.to_account_info(),
+                        authority: ctx.accounts.economy_authority.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                referral_bonus,
+            )?;
+            msg!("Transferred {} NEPTU referral bonus", referral_bonus);
+        }
+    }
+
+    Ok(())
+}
+
+pub(crate) fn pay_with_neptu(ctx: Context<PayWithNeptu>, reading_type: ReadingType) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let new_profile = ctx.accounts.user_profile.first_activity_at == 0;
+    let config = &ctx.accounts.pricing_config;
+    let tier = stake_benefits(
+        ctx.accounts.staking_pool.as_deref(),
+        &ctx.accounts.stake_position,
+        now,
+    );
+    let loyalty = loyalty_benefits(&ctx.accounts.loyalty_program, &ctx.accounts.user_profile);
+    let neptu_price = loyalty.discounted(tier.discounted(config.get_neptu_price(&reading_type)));
+    let user = ctx.accounts.user.key();
+    let split = settle_neptu_payment(
+        config,
+        &mut ctx.accounts.economy_state,
+        ctx.accounts.staking_pool.as_deref_mut(),
+        NeptuPaymentAccounts {
+            token_program: &ctx.accounts.token_program,
+            neptu_mint: &ctx.accounts.neptu_mint,
+            from: &ctx.accounts.user_neptu_account,
+            authority: ctx.accounts.user.to_account_info(),
+            ecosystem_pool: &ctx.accounts.ecosystem_pool,
+            stake_vault: ctx.accounts.stake_vault.as_ref(),
+        },
+        neptu_price,
+        &[],
+    )?;
+
+    let pool_balance = ctx.accounts.rewards_pool.as_ref().map(|pool| pool.amount);
+    let state = &mut ctx.accounts.economy_state;
+    let referral_bonus = settle_referral(
+        state,
+        user,
+        &mut ctx.accounts.referral,
+        &mut ctx.accounts.referrer_stats,
+        &mut ctx.accounts.referrer_claim_record,
+        &ctx.accounts.referrer_neptu_account,
+        neptu_price,
+        pool_balance,
+        now,
+    )?;
+
+    let profile = &mut ctx.accounts.user_profile;
+    profile.record_reading(user, reading_type, now)?;
+    profile.total_neptu_spent = profile
+        .total_neptu_spent
+        .checked_add(neptu_price)
+        .ok_or(NeptuError::Overflow)?;
+    profile.total_neptu_burned = profile
+        .total_neptu_burned
+        .checked_add(split.burned)
+        .ok_or(NeptuError::Overflow)?;
+    profile.refresh_loyalty_tier(&ctx.accounts.loyalty_program);
+    if let Some(receipt) = &mut ctx.accounts.receipt {
+        profile.receipts_paid = profile
+            .receipts_paid
+            .checked_add(1)
+            .ok_or(NeptuError::Overflow)?;
+        issue_receipt(
+            receipt,
+            Receipt {
+                payer: user,
+                beneficiary: user,
+                reading_type,
+                currency: PaymentCurrency::Neptu,
+                amount: neptu_price,
+                issued_at: now,
+                message_hash: None,
+                burned: split.burned,
+                refunded: false,
+                reward: 0,
+                reward_recipient: user,
+                staker_yield: split.staker_yield,
+                referral_bonus,
+                quantity: 1,
+            },
+        );
+    }
+    sponsor_rent(
+        &mut ctx.accounts.rent_sponsorship,
+        &mut ctx.accounts.sponsored_rent,
+        &ctx.accounts.fee_payer,
+        user,
+        &[(SPONSOR_PROFILE, UserProfile::SIZE, new_profile)],
+    )?;
+
+    msg!(
+        "Pay with NEPTU: {} total, {} burned, {} recycled",
+        neptu_price,
+        split.burned,
+        split.recycled
+    );
+
+    if let (Some(rewards_pool), Some(referrer_account)) = (
+        &ctx.accounts.rewards_pool,
+        &ctx.accounts.referrer_neptu_account,
+    ) {
+        if referral_bonus > 0 {
+            let seeds = &[b"economy".as_ref(), &[ctx.bumps.economy_authority]];
+            let signer_seeds = &[&seeds[..]];
+            transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: rewards_pool.to_account_info(),
+                        to: referrer_account.to_account_info(),
+                        authority: ctx.accounts.economy_authority.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                referral_bonus,
+            )?;
+            msg!("Transferred {} NEPTU referral bonus", referral_bonus);
+        }
+    }
+
+    Ok(())
+}
+
+pub(crate) fn pay_with_token(ctx: Context<PayWithToken>, reading_type: ReadingType) -> Result<()> {
+    let price = ctx.accounts.payment_mint.price(reading_type)?;
+    let now = Clock::get()?.unix_timestamp;
+    let user = ctx.accounts.user.key();
+
+    let state = &mut ctx.accounts.economy_state;
+    let claim_record = &mut ctx.accounts.claim_record;
+    claim_record.user = user;
+    let neptu_reward = grant_payment_reward(
+        state,
+        claim_record,
+        &mut ctx.accounts.pending_reward,
+        ctx.accounts.pricing_config.get_sol_reward(&reading_type)?,
+        ctx.accounts.rewards_pool.amount,
+        now,
+    )?;
+
+    let payment_mint = &mut ctx.accounts.payment_mint;
+    payment_mint.total_collected = payment_mint
+        .total_collected
+        .checked_add(price)
+        .ok_or(NeptuError::Overflow)?;
+
+    let profile = &mut ctx.accounts.user_profile;
+    profile.record_reading(user, reading_type, now)?;
+    profile.record_earned(neptu_reward)?;
+    if let Some(receipt) = &mut ctx.accounts.receipt {
+        profile.receipts_paid = profile
+            .receipts_paid
+            .checked_add(1)
+            .ok_or(NeptuError::Overflow)?;
+        issue_receipt(
+            receipt,
+            Receipt {
+                payer: user,
+                beneficiary: user,
+                reading_type,
+                currency: PaymentCurrency::Token(payment_mint.mint),
+                amount: price,
+                issued_at: now,
+                message_hash: None,
+                burned: 0,
+                refunded: false,
+                reward: neptu_reward,
+                reward_recipient: user,
+                staker_yield: 0,
+                referral_bonus: 0,
+                quantity: 1,
+            },
+        );
+    }
+
+    transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.user_token_account.to_account_info(),
+                to: ctx.accounts.treasury_token_vault.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        price,
+    )?;
+
+    emit!(TokenPaymentReceived {
+        user,
+        mint: payment_mint.mint,
+        reading_type,
+        amount: price,
+        neptu_reward,
+    });
+    msg!(
+        "Pay with token {}: {} for {:?}, reward: {} NEPTU",
+        payment_mint.mint,
+        price,
+        reading_type,
+        neptu_reward
+    );
+
+    if neptu_reward == 0 {
+        msg!("No NEPTU reward paid for this payment");
+        return Ok(());
+    }
+
+    let seeds = &[b"economy".as_ref(), &[ctx.bumps.economy_authority]];
+    let signer_seeds = &[&seeds[..]];
+
+    transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.rewards_pool.to_account_info(),
+                to: ctx.accounts.user_neptu_account.to_account_info(),
+                authority: ctx.accounts.economy_authority.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        neptu_reward,
+    )?;
+    msg!("Transferred {} NEPTU reward to user", neptu_reward);
+
+    Ok(())
+}
+
+pub(crate) fn pay_hybrid(
+    ctx: Context<PayHybrid>,
+    reading_type: ReadingType,
+    neptu_amount: u64,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let config = &ctx.accounts.pricing_config;
+    let tier = stake_benefits(
+        ctx.accounts.staking_pool.as_deref(),
+        &ctx.accounts.stake_position,
+        now,
+    );
+    let loyalty = loyalty_benefits(&ctx.accounts.loyalty_program, &ctx.accounts.user_profile);
+    let neptu_price = loyalty.discounted(tier.discounted(config.get_neptu_price(&reading_type)));
+    let sol_price = loyalty.discounted(
+        tier.discounted(config.resolve_sol_price(&reading_type, &ctx.accounts.price_feed)?),
+    );
+    let (sol_amount, reward) = hybrid_shares(
+        neptu_price,
+        sol_price,
+        tier.boosted(config.get_sol_reward(&reading_type)?)?,
+        neptu_amount,
+    )?;
+
+    let user = ctx.accounts.user.key();
+    let split = settle_neptu_payment(
+        config,
+        &mut ctx.accounts.economy_state,
+        ctx.accounts.staking_pool.as_deref_mut(),
+        NeptuPaymentAccounts {
+            token_program: &ctx.accounts.token_program,
+            neptu_mint: &ctx.accounts.neptu_mint,
+            from: &ctx.accounts.user_neptu_account,
+            authority: ctx.accounts.user.to_account_info(),
+            ecosystem_pool: &ctx.accounts.ecosystem_pool,
+            stake_vault: ctx.accounts.stake_vault.as_ref(),
+        },
+        neptu_amount,
+        &[],
+    )?;
+    let pool_balance = ctx.accounts.rewards_pool.amount;
+    let state = &mut ctx.accounts.economy_state;
+    let claim_record = &mut ctx.accounts.claim_record;
+    claim_record.user = user;
+    let neptu_reward = grant_payment_reward(
+        state,
+        claim_record,
+        &mut ctx.accounts.pending_reward,
+        reward,
+        pool_balance,
+        now,
+    )?;
+    let referral_bonus = settle_referral(
+        state,
+        user,
+        &mut ctx.accounts.referral,
+        &mut ctx.accounts.referrer_stats,
+        &mut ctx.accounts.referrer_claim_record,
+        &ctx.accounts.referrer_neptu_account,
+        neptu_amount
+            .checked_add(reward)
+            .ok_or(NeptuError::Overflow)?,
+        Some(pool_balance - neptu_reward),
+        now,
+    )?;
+    state.total_sol_collected = state
+        .total_sol_collected
+        .checked_add(sol_amount)
+        .ok_or(NeptuError::Overflow)?;
+
+    let profile = &mut ctx.accounts.user_profile;
+    profile.record_reading(user, reading_type, now)?;
+    profile.total_sol_spent = profile
+        .total_sol_spent
+        .checked_add(sol_amount)
+        .ok_or(NeptuError::Overflow)?;
+    profile.total_neptu_spent = profile
+        .total_neptu_spent
+        .checked_add(neptu_amount)
+        .ok_or(NeptuError::Overflow)?;
+    profile.total_neptu_burned = profile
+        .total_neptu_burned
+        .checked_add(split.burned)
+        .ok_or(NeptuError::Overflow)?;
+    profile.record_earned(neptu_reward)?;
+    profile.refresh_loyalty_tier(&ctx.accounts.loyalty_program);
+    match (&ctx.accounts.sol_receipt, &ctx.accounts.neptu_receipt) {
+        (Some(sol_receipt), Some(neptu_receipt)) => {
+            let fee_payer = ctx.accounts.fee_payer.to_account_info();
+            let receipt = Receipt {
+                payer: user,
+                beneficiary: user,
+                reading_type,
+                currency: PaymentCurrency::Sol,
+                amount: sol_amount,
+                issued_at: now,
+                message_hash: None,
+                burned: 0,
+                refunded: false,
+                reward: neptu_reward,
+                reward_recipient: user,
+                staker_yield: 0,
+                referral_bonus,
+                quantity: 1,
+            };
+            issue_receipt_at(
+                sol_receipt,
+                &fee_payer,
+                &ctx.accounts.system_program,
+                profile,
+                receipt.clone(),
+            )?;
+            issue_receipt_at(
+                neptu_receipt,
+                &fee_payer,
+                &ctx.accounts.system_program,
+                profile,
+                Receipt {
+                    currency: PaymentCurrency::Neptu,
+                    amount: neptu_amount,
+                    burned: split.burned,
+                    reward: 0,
+                    staker_yield: split.staker_yield,
+                    referral_bonus: 0,
+                    ..receipt
+                },
+            )?;
+        }
+        (None, None) => {}
+        _ => return err!(NeptuError::ReceiptAccountsMissing),
+    }
+
+    msg!(
+        "Pay hybrid: {} NEPTU + {} lamports for {:?}, reward: {} NEPTU",
+        neptu_amount,
+        sol_amount,
+        reading_type,
+        neptu_reward
+    );
+
+    if sol_amount > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.user.to_account_info(),
+                    to: ctx.accounts.treasury_vault.to_account_info(),
+                },
+            ),
+            sol_amount,
+        )?;
+        ctx.accounts
+            .treasury_vault
+            .record_deposit(user, sol_amount)?;
+    }
+
+    if neptu_reward > 0 {
+        let seeds = &[b"economy".as_ref(), &[ctx.bumps.economy_authority]];
+        let signer_seeds = &[&seeds[..]];
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.rewards_pool.to_account_info(),
+                    to: ctx.accounts.user_neptu_account.to_account_info(),
+                    authority: ctx.accounts.economy_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            neptu_reward,
+        )?;
+        msg!("Transferred {} NEPTU reward to user", neptu_reward);
+    }
+
+    if let Some(referrer_account) = &ctx.accounts.referrer_neptu_account {
+        if referral_bonus > 0 {
+            let seeds = &[b"economy".as_ref(), &[ctx.bumps.economy_authority]];
+            transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.rewards_pool.to_account_info(),
+                        to: referrer_account.to_account_info(),
+                        authority: ctx.accounts.economy_authority.to_account_info(),
+                    },
+                    &[&seeds[..]],
+                ),
+                referral_bonus,
+            )?;
+            msg!("Transferred {} NEPTU referral bonus", referral_bonus);
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct PayHybrid<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// Pays rent and fees; may be a sponsor other than the user
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+
+    #[account(
+        seeds = [b"pricing_config"],
+        bump,
+    )]
+    pub pricing_config: Account<'info, PricingConfig>,
+
+    /// CHECK: Pyth SOL/USD price update, must be `pricing_config.price_feed`;
+    /// required for USD-priced readings
+    pub price_feed: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        mut,
+        seeds = [b"economy_state"],
+        bump,
+        constraint = !economy_state.paused @ NeptuError::ProgramPaused,
+    )]
+    pub economy_state: Account<'info, EconomyState>,
+
+    /// Per-user emission budget tracking
+    #[account(
+        init_if_needed,
+        seeds = [b"claim", user.key().as_ref()],
+        bump,
+        payer = fee_payer,
+        space = ClaimRecord::SIZE,
+    )]
+    pub claim_record: Account<'info, ClaimRecord>,
+
+    #[account(
+        init_if_needed,
+        seeds = [b"pending_reward", user.key().as_ref()],
+        bump,
+        payer = fee_payer,
+        space = PendingReward::SIZE,
+    )]
+    pub pending_reward: Option<Account<'info, PendingReward>>,
+
+    #[account(
+        init_if_needed,
+        seeds = [b"profile", user.key().as_ref()],
+        bump,
+        payer = fee_payer,
+        space = UserProfile::SIZE,
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    /// Applies the profile's loyalty tier discount when given
+    #[account(
+        seeds = [b"loyalty"],
+        bump,
+    )]
+    pub loyalty_program: Option<Account<'info, LoyaltyProgram>>,
+
+    /// Treasury vault receives the SOL share
+    #[account(
+        mut,
+        seeds = [b"treasury_vault"],
+        bump = treasury_vault.bump,
+    )]
+    pub treasury_vault: Account<'info, TreasuryVault>,
+
+    #[account(mut, address = economy_state.neptu_mint)]
+    pub neptu_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = neptu_mint,
+        associated_token::authority = user,
+    )]
+    pub user_neptu_account: Account<'info, TokenAccount>,
+
+    #[account(mut, address = economy_state.ecosystem_pool)]
+    pub ecosystem_pool: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"staking_pool"],
+        bump,
+    )]
+    pub staking_pool: Option<Account<'info, StakingPool>>,
+
+    #[account(
+        seeds = [b"stake", user.key().as_ref()],
+        bump,
+    )]
+    pub stake_position: Option<Account<'info, StakePosition>>,
+
+    /// Receives the stakers' yield share of the recycled amount once staking is set up
+    #[account(
+        mut,
+        seeds = [b"stake_vault"],
+        bump,
+    )]
+    pub stake_vault: Option<Account<'info, TokenAccount>>,
+
+    /// Rewards pool: ATA owned by economy_authority PDA
+    #[account(
+        mut,
+        associated_token::mint = neptu_mint,
+        associated_token::authority = economy_authority,
+    )]
+    pub rewards_pool: Account<'info, TokenAccount>,
+
+    /// CHECK: Economy authority PDA (rewards pool owner)
+    #[account(
+        seeds = [b"economy"],
+        bump,
+    )]
+    pub economy_authority: UncheckedAccount<'info>,
+
+    /// CHECK: SOL share receipt, created by the handler at the payer's next receipt index
+    #[account(mut)]
+    pub sol_receipt: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: NEPTU share receipt, created at the index after `sol_receipt`
+    #[account(mut)]
+    pub neptu_receipt: Option<UncheckedAccount<'info>>,
+
+    /// Referral binding of the payer, set once on first payment
+    #[account(
+        init_if_needed,
+        seeds = [b"referral", user.key().as_ref()],
+        bump,
+        payer = fee_payer,
+        space = Referral::SIZE,
+    )]
+    pub referral: Account<'info, Referral>,
+
+    /// Stats of the payer's referrer; binds the referrer on first payment and is
+    /// required once bound
+    #[account(
+        mut,
+        seeds = [b"referrer", referrer_stats.referrer.as_ref()],
+        bump,
+    )]
+    pub referrer_stats: Option<Account<'info, ReferrerStats>>,
+
+    /// Referrer's claim record, charged against the referrer's emission budget
+    #[account(
+        mut,
+        seeds = [b"claim", referrer_claim_record.user.as_ref()],
+        bump,
+    )]
+    pub referrer_claim_record: Option<Account<'info, ClaimRecord>>,
+
+    /// Referrer's NEPTU account receiving the bonus
+    #[account(
+        mut,
+        token::mint = neptu_mint,
+    )]
+    pub referrer_neptu_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PayWithToken<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// Pays rent and fees; may be a sponsor other than the user
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+
+    #[account(
+        seeds = [b"pricing_config"],
+        bump,
+    )]
+    pub pricing_config: Account<'info, PricingConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"economy_state"],
+        bump,
+        constraint = !economy_state.paused @ NeptuError::ProgramPaused,
+    )]
+    pub economy_state: Account<'info, EconomyState>,
+
+    #[account(
+        mut,
+        seeds = [b"payment_mint", payment_mint.mint.as_ref()],
+        bump,
+    )]
+    pub payment_mint: Account<'info, PaymentMint>,
+
+    #[account(
+        mut,
+        token::mint = payment_mint.mint,
+        token::authority = user,
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut, address = payment_mint.treasury_token_vault)]
+    pub treasury_token_vault: Account<'info, TokenAccount>,
+
+    /// Per-user emission budget tracking
+    #[account(
+        init_if_needed,
+        seeds = [b"claim", user.key().as_ref()],
+        bump,
+        payer = fee_payer,
+        space = ClaimRecord::SIZE,
+    )]
+    pub claim_record: Account<'info, ClaimRecord>,
+
+    #[account(
+        init_if_needed,
+        seeds = [b"pending_reward", user.key().as_ref()],
+        bump,
+        payer = fee_payer,
+        space = PendingReward::SIZE,
+    )]
+    pub pending_reward: Option<Account<'info, PendingReward>>,
+
+    #[account(
+        init_if_needed,
+        seeds = [b"profile", user.key().as_ref()],
+        bump,
+        payer = fee_payer,
+        space = UserProfile::SIZE,
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    /// Receipt of this payment, needed to refund it later
+    #[account(
+        init,
+        seeds = [
+            b"receipt",
+            user.key().as_ref(),
+            &user_profile.receipts_paid.to_le_bytes(),
+        ],
+        bump,
+        payer = fee_payer,
+        space = Receipt::SIZE,
+    )]
+    pub receipt: Option<Account<'info, Receipt>>,
+
+    #[account(address = economy_state.neptu_mint)]
+    pub neptu_mint: Account<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = fee_payer,
+        associated_token::mint = neptu_mint,
+        associated_token::authority = user,
+    )]
+    pub user_neptu_account: Account<'info, TokenAccount>,
+
+    /// Rewards pool: ATA owned by economy_authority PDA
+    #[account(
+        mut,
+        associated_token::mint = neptu_mint,
+        associated_token::authority = economy_authority,
+    )]
+    pub rewards_pool: Account<'info, TokenAccount>,
+
+    /// CHECK: Economy authority PDA (rewards pool owner)
+    #[account(
+        seeds = [b"economy"],
+        bump,
+    )]
+    pub economy_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PayWithSol<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// Pays rent and fees; may be a sponsor other than the user
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+
+    #[account(
+        seeds = [b"pricing_config"],
+        bump,
+    )]
+    pub pricing_config: Account<'info, PricingConfig>,
+
+    /// CHECK: Pyth SOL/USD price update, must be `pricing_config.price_feed`;
+    /// required for USD-priced readings
+    pub price_feed: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        mut,
+        seeds = [b"economy_state"],
+        bump,
+        constraint = !economy_state.paused @ NeptuError::ProgramPaused,
+    )]
+    pub economy_state: Account<'info, EconomyState>,
+
+    /// Per-user emission budget tracking
+    #[account(
+        init_if_needed,
+        seeds = [b"claim", user.key().as_ref()],
+        bump,
+        payer = fee_payer,
+        space = ClaimRecord::SIZE,
+    )]
+    pub claim_record: Account<'info, ClaimRecord>,
+
+    /// Treasury vault receives SOL
+    #[account(
+        mut,
+        seeds = [b"treasury_vault"],
+        bump = treasury_vault.bump,
+    )]
+    pub treasury_vault: Account<'info, TreasuryVault>,
+
+    #[account(mut)]
+    pub neptu_mint: Account<'info, Mint>,
+
+    /// CHECK: the user's NEPTU ATA; opened by the handler when missing, so sponsored
+    /// rent only covers it when this instruction created it
+    #[account(
+        mut,
+        address = get_associated_token_address(&user.key(), &neptu_mint.key()),
+    )]
+    pub user_neptu_account: UncheckedAccount<'info>,
+
+    /// Rewards pool: ATA owned by economy_authority PDA
+    #[account(
+        mut,
+        associated_token::mint = neptu_mint,
+        associated_token::authority = economy_authority,
+    )]
+    pub rewards_pool: Account<'info, TokenAccount>,
+
+    /// CHECK: Economy authority PDA (rewards pool owner)
+    #[account(
+        seeds = [b"economy"],
+        bump,
+    )]
+    pub economy_authority: UncheckedAccount<'info>,
+
+    /// IOU record, required when the shortfall policy is Iou and the pool is short
+    #[account(
+        init_if_needed,
+        seeds = [b"pending_reward", user.key().as_ref()],
+        bump,
+        payer = fee_payer,
+        space = PendingReward::SIZE,
+    )]
+    pub pending_reward: Option<Account<'info, PendingReward>>,
+
+    /// Caller's stake tier applies a price discount / reward boost when both are given
+    #[account(
+        seeds = [b"staking_pool"],
+        bump,
+    )]
+    pub staking_pool: Option<Account<'info, StakingPool>>,
+
+    #[account(
+        seeds = [b"stake", user.key().as_ref()],
+        bump,
+    )]
+    pub stake_position: Option<Account<'info, StakePosition>>,
+
+    /// Lifetime stats of the user
+    #[account(
+        init_if_needed,
+        seeds = [b"profile", user.key().as_ref()],
+        bump,
+        payer = fee_payer,
+        space = UserProfile::SIZE,
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    /// Applies the profile's loyalty tier discount when given
+    #[account(
+        seeds = [b"loyalty"],
+        bump,
+    )]
+    pub loyalty_program: Option<Account<'info, LoyaltyProgram>>,
+
+    /// Receipt of this payment, needed to refund it later
+    #[account(
+        init,
+        seeds = [
+            b"receipt",
+            user.key().as_ref(),
+            &user_profile.receipts_paid.to_le_bytes(),
+        ],
+        bump,
+        payer = fee_payer,
+        space = Receipt::SIZE,
+    )]
+    pub receipt: Option<Account<'info, Receipt>>,
+
+    /// Referral binding of the payer, set once on first payment
+    #[account(
+        init_if_needed,
+        seeds = [b"referral", user.key().as_ref()],
+        bump,
+        payer = fee_payer,
+        space = Referral::SIZE,
+    )]
+    pub referral: Account<'info, Referral>,
+
+    /// Stats of the payer's referrer; binds the referrer on first payment and is
+    /// required once bound
+    #[account(
+        mut,
+        seeds = [b"referrer", referrer_stats.referrer.as_ref()],
+        bump,
+    )]
+    pub referrer_stats: Option<Account<'info, ReferrerStats>>,
+
+    /// Referrer's claim record, charged against the referrer's emission budget
+    #[account(
+        mut,
+        seeds = [b"claim", referrer_claim_record.user.as_ref()],
+        bump,
+    )]
+    pub referrer_claim_record: Option<Account<'info, ClaimRecord>>,
+
+    /// Referrer's NEPTU account receiving the bonus
+    #[account(
+        mut,
+        token::mint = neptu_mint,
+    )]
+    pub referrer_neptu_account: Option<Account<'info, TokenAccount>>,
+
+    /// Reimburses an approved fee payer's onboarding rent when given
+    #[account(
+        mut,
+        seeds = [b"rent_sponsorship"],
+        bump = rent_sponsorship.bump,
+    )]
+    pub rent_sponsorship: Option<Account<'info, RentSponsorship>>,
+
+    /// Onboarding rent already reimbursed for the user
+    #[account(
+        init_if_needed,
+        seeds = [b"sponsored_rent", user.key().as_ref()],
+        bump,
+        payer = fee_payer,
+        space = SponsoredRent::SIZE,
+    )]
+    pub sponsored_rent: Option<Account<'info, SponsoredRent>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PayWithNeptu<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// Pays rent and fees; may be a sponsor other than the user
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+
+    #[account(
+        seeds = [b"pricing_config"],
+        bump,
+    )]
+    pub pricing_config: Account<'info, PricingConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"economy_state"],
+        bump,
+        constraint = !economy_state.paused @ NeptuError::ProgramPaused,
+    )]
+    pub economy_state: Account<'info, EconomyState>,
+
+    #[account(mut, address = economy_state.neptu_mint)]
+    pub neptu_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = neptu_mint,
+        associated_token::authority = user,
+    )]
+    pub user_neptu_account: Account<'info, TokenAccount>,
+
+    #[account(mut, address = economy_state.ecosystem_pool)]
+    pub ecosystem_pool: Account<'info, TokenAccount>,
+
+    /// Caller's stake tier applies a price discount when the position is given
+    #[account(
+        mut,
+        seeds = [b"staking_pool"],
+        bump,
+    )]
+    pub staking_pool: Option<Account<'info, StakingPool>>,
+
+    #[account(
+        seeds = [b"stake", user.key().as_ref()],
+        bump,
+    )]
+    pub stake_position: Option<Account<'info, StakePosition>>,
+
+    /// Lifetime stats of the user
+    #[account(
+        init_if_needed,
+        seeds = [b"profile", user.key().as_ref()],
+        bump,
+        payer = fee_payer,
+        space = UserProfile::SIZE,
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    /// Applies the profile's loyalty tier discount when given
+    #[account(
+        seeds = [b"loyalty"],
+        bump,
+    )]
+    pub loyalty_program: Option<Account<'info, LoyaltyProgram>>,
+
+    /// Receipt of this payment, needed to refund it later
+    #[account(
+        init,
+        seeds = [
+            b"receipt",
+            user.key().as_ref(),
+            &user_profile.receipts_paid.to_le_bytes(),
+        ],
+        bump,
+        payer = fee_payer,
+        space = Receipt::SIZE,
+    )]
+    pub receipt: Option<Account<'info, Receipt>>,
+
+    /// Receives the stakers' yield share of the recycled amount once staking is set up
+    #[account(
+        mut,
+        seeds = [b"stake_vault"],
+        bump,
+    )]
+    pub stake_vault: Option<Account<'info, TokenAccount>>,
+
+    /// Rewards pool funding the referral bonus
+    #[account(
+        mut,
+        associated_token::mint = neptu_mint,
+        associated_token::authority = economy_authority,
+    )]
+    pub rewards_pool: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: Economy authority PDA (rewards pool owner)
+    #[account(
+        seeds = [b"economy"],
+        bump,
+    )]
+    pub economy_authority: UncheckedAccount<'info>,
+
+    /// Referral binding of the payer, set once on first payment
+    #[account(
+        init_if_needed,
+        seeds = [b"referral", user.key().as_ref()],
+        bump,
+        payer = fee_payer,
+        space = Referral::SIZE,
+    )]
+    pub referral: Account<'info, Referral>,
+
+    /// Stats of the payer's referrer; binds the referrer on first payment and is
+    /// required once bound
+    #[account(
+        mut,
+        seeds = [b"referrer", referrer_stats.referrer.as_ref()],
+        bump,
+    )]
+    pub referrer_stats: Option<Account<'info, ReferrerStats>>,
+
+    /// Referrer's claim record, charged against the referrer's emission budget
+    #[account(
+        mut,
+        seeds = [b"claim", referrer_claim_record.user.as_ref()],
+        bump,
+    )]
+    pub referrer_claim_record: Option<Account<'info, ClaimRecord>>,
+
+    /// Referrer's NEPTU account receiving the bonus
+    #[account(
+        mut,
+        token::mint = neptu_mint,
+    )]
+    pub referrer_neptu_account: Option<Account<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Reimburses an approved fee payer's onboarding rent when given
+    #[account(
+        mut,
+        seeds = [b"rent_sponsorship"],
+        bump = rent_sponsorship.bump,
+    )]
+    pub rent_sponsorship: Option<Account<'info, RentSponsorship>>,
+
+    /// Onboarding rent already reimbursed for the user
+    #[account(
+        init_if_needed,
+        seeds = [b"sponsored_rent", user.key().as_ref()],
+        bump,
+        payer = fee_payer,
+        space = SponsoredRent::SIZE,
+    )]
+    pub sponsored_rent: Option<Account<'info, SponsoredRent>>,
+
+    pub token_program: Program<'info, Token>,
+}
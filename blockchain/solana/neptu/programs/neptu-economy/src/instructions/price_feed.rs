@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::NeptuError, state::*};
+
+pub(crate) fn create_price_feed(ctx: Context<CreatePriceFeed>, expo: i32) -> Result<()> {
+    let feed = &mut ctx.accounts.price_feed;
+    feed.write_authority = ctx.accounts.authority.key();
+    feed.verification_level = PythVerificationLevel::Full;
+    feed.price_message = PythPriceMessage {
+        exponent: expo,
+        ..Default::default()
+    };
+    msg!("Price feed created: {}", feed.key());
+    Ok(())
+}
+
+pub(crate) fn update_price_feed(
+    ctx: Context<UpdatePriceFeed>,
+    price: i64,
+    conf: u64,
+    publish_time: i64,
+) -> Result<()> {
+    let feed = &mut ctx.accounts.price_feed;
+    let message = &mut feed.price_message;
+    require!(
+        publish_time >= message.publish_time,
+        NeptuError::InvalidPriceFeed
+    );
+    message.prev_publish_time = message.publish_time;
+    message.price = price;
+    message.conf = conf;
+    message.publish_time = publish_time;
+    feed.posted_slot = Clock::get()?.slot;
+    msg!(
+        "Price feed updated: {} x 10^{}",
+        price,
+        feed.price_message.exponent
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CreatePriceFeed<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init,
+        seeds = [b"price_feed", authority.key().as_ref()],
+        bump,
+        payer = authority,
+        space = PriceUpdateV2::SIZE,
+    )]
+    pub price_feed: Account<'info, PriceUpdateV2>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdatePriceFeed<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = price_feed.write_authority == authority.key() @ NeptuError::Unauthorized,
+    )]
+    pub price_feed: Account<'info, PriceUpdateV2>,
+}
@@ -0,0 +1,230 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::{constants::*, errors::NeptuError, state::*};
+
+pub(crate) fn initialize_pricing(ctx: Context<InitializePricing>) -> Result<()> {
+    msg!("Initializing Pricing Config");
+    let config = &mut ctx.accounts.pricing_config;
+    config.authority = ctx.accounts.authority.key();
+    config.potensi_sol_price = defaults::POTENSI_SOL;
+    config.peluang_sol_price = defaults::PELUANG_SOL;
+    config.ai_chat_sol_price = defaults::AI_CHAT_SOL;
+    config.compatibility_sol_price = defaults::COMPATIBILITY_SOL;
+    config.potensi_neptu_price = defaults::POTENSI_NEPTU;
+    config.peluang_neptu_price = defaults::PELUANG_NEPTU;
+    config.ai_chat_neptu_price = defaults::AI_CHAT_NEPTU;
+    config.compatibility_neptu_price = defaults::COMPATIBILITY_NEPTU;
+    config.burn_rate_bps = BURN_RATE_BPS as u16;
+    config.reward_multiplier_bps = BPS_DENOMINATOR as u16;
+    config.usd_prices_cents = [0; 4];
+    config.price_feed = Pubkey::default();
+    config.max_price_age = defaults::MAX_PRICE_AGE;
+    config.max_price_conf_bps = defaults::MAX_PRICE_CONF_BPS;
+    config.max_sol_usage_rate = defaults::MAX_SOL_USAGE_RATE;
+    config.max_neptu_usage_rate = defaults::MAX_NEPTU_USAGE_RATE;
+    msg!("Pricing config initialized");
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn update_pricing(
+    ctx: Context<UpdatePricing>,
+    potensi_sol: Option<u64>,
+    peluang_sol: Option<u64>,
+    ai_chat_sol: Option<u64>,
+    compatibility_sol: Option<u64>,
+    potensi_neptu: Option<u64>,
+    peluang_neptu: Option<u64>,
+    ai_chat_neptu: Option<u64>,
+    compatibility_neptu: Option<u64>,
+) -> Result<()> {
+    let config = &mut ctx.accounts.pricing_config;
+
+    if let Some(v) = potensi_sol {
+        config.potensi_sol_price = v;
+    }
+    if let Some(v) = peluang_sol {
+        config.peluang_sol_price = v;
+    }
+    if let Some(v) = ai_chat_sol {
+        config.ai_chat_sol_price = v;
+    }
+    if let Some(v) = compatibility_sol {
+        config.compatibility_sol_price = v;
+    }
+    if let Some(v) = potensi_neptu {
+        config.potensi_neptu_price = v;
+    }
+    if let Some(v) = peluang_neptu {
+        config.peluang_neptu_price = v;
+    }
+    if let Some(v) = ai_chat_neptu {
+        config.ai_chat_neptu_price = v;
+    }
+    if let Some(v) = compatibility_neptu {
+        config.compatibility_neptu_price = v;
+    }
+
+    msg!("Pricing updated");
+    Ok(())
+}
+
+pub(crate) fn set_payment_mint(
+    ctx: Context<SetPaymentMint>,
+    prices: [u64; 4],
+    enabled: bool,
+) -> Result<()> {
+    let payment_mint = &mut ctx.accounts.payment_mint;
+    payment_mint.mint = ctx.accounts.mint.key();
+    payment_mint.treasury_token_vault = ctx.accounts.treasury_token_vault.key();
+    payment_mint.prices = prices;
+    payment_mint.enabled = enabled;
+    msg!(
+        "Payment mint {} {}: prices {:?}",
+        payment_mint.mint,
+        if enabled { "enabled" } else { "disabled" },
+        prices
+    );
+    Ok(())
+}
+
+pub(crate) fn update_usd_pricing(
+    ctx: Context<UpdatePricing>,
+    usd_prices_cents: [u64; 4],
+    price_feed: Pubkey,
+    max_price_age: i64,
+    max_price_conf_bps: u16,
+) -> Result<()> {
+    require!(max_price_age > 0, NeptuError::InvalidAmount);
+    require!(
+        u64::from(max_price_conf_bps) <= BPS_DENOMINATOR,
+        NeptuError::InvalidAmount
+    );
+    let config = &mut ctx.accounts.pricing_config;
+    config.usd_prices_cents = usd_prices_cents;
+    config.price_feed = price_feed;
+    config.max_price_age = max_price_age;
+    config.max_price_conf_bps = max_price_conf_bps;
+    msg!(
+        "USD pricing updated: {:?} cents via feed {}",
+        usd_prices_cents,
+        price_feed
+    );
+    Ok(())
+}
+
+pub(crate) fn update_usage_rate_caps(
+    ctx: Context<UpdatePricing>,
+    max_sol_usage_rate: u64,
+    max_neptu_usage_rate: u64,
+) -> Result<()> {
+    let config = &mut ctx.accounts.pricing_config;
+    config.max_sol_usage_rate = max_sol_usage_rate;
+    config.max_neptu_usage_rate = max_neptu_usage_rate;
+    msg!(
+        "Usage rate caps set: {} lamports / {} NEPTU per unit",
+        max_sol_usage_rate,
+        max_neptu_usage_rate
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializePricing<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init,
+        seeds = [b"pricing_config"],
+        bump,
+        payer = authority,
+        space = PricingConfig::SIZE,
+    )]
+    pub pricing_config: Account<'info, PricingConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdatePricing<'info> {
+    #[account(
+        constraint = roles.authorize(
+            &pricing_config.authority,
+            &authority.key(),
+            Role::PricingManager,
+        ) @ NeptuError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"pricing_config"],
+        bump,
+    )]
+    pub pricing_config: Account<'info, PricingConfig>,
+
+    #[account(
+        seeds = [b"roles"],
+        bump,
+    )]
+    pub roles: Account<'info, Roles>,
+}
+
+#[derive(Accounts)]
+pub struct SetPaymentMint<'info> {
+    #[account(
+        mut,
+        constraint = roles.authorize(
+            &economy_state.authority,
+            &authority.key(),
+            Role::CatalogManager,
+        ) @ NeptuError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"roles"],
+        bump,
+    )]
+    pub roles: Account<'info, Roles>,
+
+    #[account(
+        seeds = [b"economy_state"],
+        bump,
+    )]
+    pub economy_state: Account<'info, EconomyState>,
+
+    /// NEPTU has its own burn/recycle payment path
+    #[account(constraint = mint.key() != economy_state.neptu_mint @ NeptuError::PaymentMintNotAccepted)]
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        seeds = [b"payment_mint", mint.key().as_ref()],
+        bump,
+        payer = authority,
+        space = PaymentMint::SIZE,
+    )]
+    pub payment_mint: Account<'info, PaymentMint>,
+
+    #[account(
+        seeds = [b"treasury_vault"],
+        bump = treasury_vault.bump,
+    )]
+    pub treasury_vault: Account<'info, TreasuryVault>,
+
+    #[account(
+        init_if_needed,
+        seeds = [b"treasury_token", mint.key().as_ref()],
+        bump,
+        payer = authority,
+        token::mint = mint,
+        token::authority = treasury_vault,
+    )]
+    pub treasury_token_vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+
+pub(crate) fn register_referrer(ctx: Context<RegisterReferrer>) -> Result<()> {
+    let stats = &mut ctx.accounts.referrer_stats;
+    stats.referrer = ctx.accounts.referrer.key();
+    stats.referee_count = 0;
+    stats.referred_payments = 0;
+    stats.total_earned = 0;
+    stats.clawback = 0;
+    ctx.accounts.claim_record.user = stats.referrer;
+    msg!("Referrer registered: {}", stats.referrer);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RegisterReferrer<'info> {
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+
+    /// Pays rent and fees; may be a sponsor other than the referrer
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+
+    #[account(
+        init,
+        seeds = [b"referrer", referrer.key().as_ref()],
+        bump,
+        payer = fee_payer,
+        space = ReferrerStats::SIZE,
+    )]
+    pub referrer_stats: Account<'info, ReferrerStats>,
+
+    /// Tracks the referrer's bonus emissions per epoch
+    #[account(
+        init_if_needed,
+        seeds = [b"claim", referrer.key().as_ref()],
+        bump,
+        payer = fee_payer,
+        space = ClaimRecord::SIZE,
+    )]
+    pub claim_record: Account<'info, ClaimRecord>,
+
+    pub system_program: Program<'info, System>,
+}
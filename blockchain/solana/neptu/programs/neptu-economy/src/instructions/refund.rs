@@ -0,0 +1,345 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer, Token, TokenAccount, Transfer};
+
+use crate::{errors::NeptuError, events::*, state::*};
+
+pub(crate) fn refund_payment(ctx: Context<RefundPayment>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let receipt = &mut ctx.accounts.receipt;
+    receipt.refunded = true;
+    let receipt = Receipt::clone(receipt);
+    let (payer, currency, amount) = (receipt.payer, receipt.currency, receipt.amount);
+
+    let seeds = &[b"economy".as_ref(), &[ctx.bumps.economy_authority]];
+    let signer_seeds = &[&seeds[..]];
+    let state = &mut ctx.accounts.economy_state;
+    let profile = &mut ctx.accounts.payer_profile;
+    let mut reissued = 0;
+    match currency {
+        PaymentCurrency::Sol => {
+            let vault = &mut ctx.accounts.treasury_vault;
+            vault.spend(amount, now)?;
+            let vault_info = vault.to_account_info();
+            let rent_floor = Rent::get()?.minimum_balance(vault_info.data_len());
+            require!(
+                vault_info.lamports().saturating_sub(amount) >= rent_floor,
+                NeptuError::InsufficientVaultBalance
+            );
+            vault_info.sub_lamports(amount)?;
+            ctx.accounts.payer.add_lamports(amount)?;
+
+            state.total_sol_collected = state.total_sol_collected.saturating_sub(amount);
+            profile.total_sol_spent = profile.total_sol_spent.saturating_sub(amount);
+        }
+        PaymentCurrency::Neptu => {
+            let (Some(ecosystem_pool), Some(payer_neptu_account)) = (
+                &ctx.accounts.ecosystem_pool,
+                &ctx.accounts.payer_neptu_account,
+            ) else {
+                return err!(NeptuError::TokenAccountMissing);
+            };
+            // The stakers' share went to the stake vault, not the ecosystem pool
+            let (from_ecosystem, forfeited) = receipt.neptu_refund_sources();
+            if from_ecosystem > 0 {
+                transfer(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        Transfer {
+                            from: ecosystem_pool.to_account_info(),
+                            to: payer_neptu_account.to_account_info(),
+                            authority: ctx.accounts.economy_authority.to_account_info(),
+                        },
+                        signer_seeds,
+                    ),
+                    from_ecosystem,
+                )?;
+            }
+
+            if state.refund_burn_policy == RefundBurnPolicy::Reissue && forfeited > 0 {
+                let rewards_pool = ctx
+                    .accounts
+                    .rewards_pool
+                    .as_ref()
+                    .ok_or(NeptuError::TokenAccountMissing)?;
+                transfer(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        Transfer {
+                            from: rewards_pool.to_account_info(),
+                            to: payer_neptu_account.to_account_info(),
+                            authority: ctx.accounts.economy_authority.to_account_info(),
+                        },
+                        signer_seeds,
+                    ),
+                    forfeited,
+                )?;
+                reissued = forfeited;
+                state.total_neptu_burned = state.total_neptu_burned.saturating_sub(receipt.burned);
+                profile.total_neptu_burned =
+                    profile.total_neptu_burned.saturating_sub(receipt.burned);
+            }
+
+            state.total_neptu_recycled = state
+                .total_neptu_recycled
+                .saturating_sub(amount - receipt.burned);
+            profile.total_neptu_spent = profile.total_neptu_spent.saturating_sub(amount);
+        }
+        PaymentCurrency::Token(mint) => {
+            let (Some(payment_mint), Some(token_vault), Some(payer_token_account)) = (
+                &mut ctx.accounts.payment_mint,
+                &ctx.accounts.treasury_token_vault,
+                &ctx.accounts.payer_token_account,
+            ) else {
+                return err!(NeptuError::TokenAccountMissing);
+            };
+            require_keys_eq!(payment_mint.mint, mint, NeptuError::PaymentMintNotAccepted);
+            require_keys_eq!(
+                token_vault.key(),
+                payment_mint.treasury_token_vault,
+                NeptuError::PaymentMintNotAccepted
+            );
+            let vault = &ctx.accounts.treasury_vault;
+            payment_mint.spend(amount, vault.period_duration, now)?;
+            payment_mint.total_collected = payment_mint.total_collected.saturating_sub(amount);
+
+            let vault_seeds = &[b"treasury_vault".as_ref(), &[vault.bump]];
+            transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: token_vault.to_account_info(),
+                        to: payer_token_account.to_account_info(),
+                        authority: vault.to_account_info(),
+                    },
+                    &[&vault_seeds[..]],
+                ),
+                amount,
+            )?;
+        }
+    }
+
+    let gifted = receipt.beneficiary != payer;
+    if gifted {
+        profile.gifts_sent = profile.gifts_sent.saturating_sub(1);
+    }
+
+    if receipt.reward > 0 {
+        let (Some(recipient), Some(reward_account), Some(claim_record), Some(rewards_pool)) = (
+            &ctx.accounts.reward_recipient,
+            &ctx.accounts.reward_neptu_account,
+            &mut ctx.accounts.reward_claim_record,
+            &ctx.accounts.rewards_pool,
+        ) else {
+            return err!(NeptuError::RefundAccountsMissing);
+        };
+        transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: reward_account.to_account_info(),
+                    to: rewards_pool.to_account_info(),
+                    authority: recipient.to_account_info(),
+                },
+            ),
+            receipt.reward,
+        )?;
+        state.total_neptu_rewarded = state.total_neptu_rewarded.saturating_sub(receipt.reward);
+        state.release_emission(claim_record, receipt.reward, receipt.issued_at, now);
+        if receipt.reward_recipient == payer {
+            profile.total_neptu_earned = profile.total_neptu_earned.saturating_sub(receipt.reward);
+        }
+    }
+
+    if receipt.referral_bonus > 0 {
+        let (Some(referral), Some(stats)) =
+            (&ctx.accounts.referral, &mut ctx.accounts.referrer_stats)
+        else {
+            return err!(NeptuError::RefundAccountsMissing);
+        };
+        require_keys_eq!(
+            stats.referrer,
+            referral.referrer,
+            NeptuError::InvalidReferrer
+        );
+        stats.clawback = stats
+            .clawback
+            .checked_add(receipt.referral_bonus)
+            .ok_or(NeptuError::Overflow)?;
+        stats.referred_payments = stats.referred_payments.saturating_sub(1);
+    }
+
+    if gifted {
+        let beneficiary_profile = ctx
+            .accounts
+            .beneficiary_profile
+            .as_mut()
+            .ok_or(NeptuError::RefundAccountsMissing)?;
+        beneficiary_profile.remove_readings(receipt.reading_type, u32::from(receipt.quantity));
+        beneficiary_profile.gifts_received = beneficiary_profile.gifts_received.saturating_sub(1);
+        if receipt.reward > 0 && receipt.reward_recipient != payer {
+            beneficiary_profile.total_neptu_earned = beneficiary_profile
+                .total_neptu_earned
+                .saturating_sub(receipt.reward);
+        }
+        beneficiary_profile.refresh_loyalty_tier(&ctx.accounts.loyalty_program);
+    } else {
+        profile.remove_readings(receipt.reading_type, u32::from(receipt.quantity));
+    }
+    profile.refresh_loyalty_tier(&ctx.accounts.loyalty_program);
+
+    emit!(PaymentRefunded {
+        receipt: ctx.accounts.receipt.key(),
+        payer,
+        currency,
+        amount,
+        reissued,
+        reward_returned: receipt.reward,
+        referral_clawback: receipt.referral_bonus,
+        by: ctx.accounts.authority.key(),
+    });
+    msg!("Refunded {} {:?} to {}", amount, currency, payer);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RefundPayment<'info> {
+    #[account(
+        constraint = roles.authorize(
+            &economy_state.authority,
+            &authority.key(),
+            Role::Refunds,
+        ) @ NeptuError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"roles"],
+        bump,
+    )]
+    pub roles: Account<'info, Roles>,
+
+    #[account(
+        mut,
+        seeds = [b"economy_state"],
+        bump,
+        constraint = !economy_state.paused @ NeptuError::ProgramPaused,
+    )]
+    pub economy_state: Account<'info, EconomyState>,
+
+    #[account(
+        mut,
+        constraint = !receipt.refunded @ NeptuError::AlreadyRefunded,
+    )]
+    pub receipt: Account<'info, Receipt>,
+
+    /// CHECK: Receipt payer, receives the refund
+    #[account(mut, address = receipt.payer)]
+    pub payer: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"profile", receipt.payer.as_ref()],
+        bump,
+    )]
+    pub payer_profile: Account<'info, UserProfile>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury_vault"],
+        bump = treasury_vault.bump,
+    )]
+    pub treasury_vault: Account<'info, TreasuryVault>,
+
+    /// Source of NEPTU refunds (recycled part)
+    #[account(mut, address = economy_state.ecosystem_pool)]
+    pub ecosystem_pool: Option<Account<'info, TokenAccount>>,
+
+    /// Source of re-issued burned NEPTU
+    #[account(
+        mut,
+        constraint = rewards_pool.owner == economy_authority.key()
+            && rewards_pool.mint == economy_state.neptu_mint @ NeptuError::Unauthorized
+    )]
+    pub rewards_pool: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::authority = payer,
+    )]
+    pub payer_neptu_account: Option<Account<'info, TokenAccount>>,
+
+    /// Accepted payment mint of a token receipt, with its spending limit
+    #[account(
+        mut,
+        seeds = [b"payment_mint", payment_mint.mint.as_ref()],
+        bump,
+    )]
+    pub payment_mint: Option<Account<'info, PaymentMint>>,
+
+    /// Source of token refunds
+    #[account(mut)]
+    pub treasury_token_vault: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::authority = payer,
+    )]
+    pub payer_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Gift beneficiary's stats; required when the receipt is a gift
+    #[account(
+        mut,
+        seeds = [b"profile", receipt.beneficiary.as_ref()],
+        bump,
+    )]
+    pub beneficiary_profile: Option<Account<'info, UserProfile>>,
+
+    /// Re-derives loyalty tiers after the reversal when given
+    #[account(
+        seeds = [b"loyalty"],
+        bump,
+    )]
+    pub loyalty_program: Option<Account<'info, LoyaltyProgram>>,
+
+    /// Holder of the payment's NEPTU reward; required (and signs) when it is non-zero
+    #[account(address = receipt.reward_recipient @ NeptuError::Unauthorized)]
+    pub reward_recipient: Option<Signer<'info>>,
+
+    /// Returns the reward to the rewards pool
+    #[account(
+        mut,
+        constraint = reward_neptu_account.owner == receipt.reward_recipient @ NeptuError::Unauthorized,
+    )]
+    pub reward_neptu_account: Option<Account<'info, TokenAccount>>,
+
+    /// Emission budget the reward was charged to
+    #[account(
+        mut,
+        seeds = [b"claim", receipt.reward_recipient.as_ref()],
+        bump,
+    )]
+    pub reward_claim_record: Option<Account<'info, ClaimRecord>>,
+
+    /// Payer's referral binding; required when a referral bonus was paid
+    #[account(
+        seeds = [b"referral", receipt.payer.as_ref()],
+        bump,
+    )]
+    pub referral: Option<Account<'info, Referral>>,
+
+    #[account(
+        mut,
+        seeds = [b"referrer", referrer_stats.referrer.as_ref()],
+        bump,
+    )]
+    pub referrer_stats: Option<Account<'info, ReferrerStats>>,
+
+    /// CHECK: Economy authority PDA (pool owner)
+    #[account(
+        seeds = [b"economy"],
+        bump,
+    )]
+    pub economy_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
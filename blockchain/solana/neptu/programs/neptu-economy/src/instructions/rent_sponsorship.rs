@@ -0,0 +1,117 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::{errors::NeptuError, state::*};
+
+pub(crate) fn configure_rent_sponsorship(
+    ctx: Context<ConfigureRentSponsorship>,
+    sponsor: Pubkey,
+    per_user_limit: u64,
+) -> Result<()> {
+    let sponsorship = &mut ctx.accounts.rent_sponsorship;
+    sponsorship.sponsor = sponsor;
+    sponsorship.per_user_limit = per_user_limit;
+    sponsorship.bump = ctx.bumps.rent_sponsorship;
+    msg!(
+        "Rent sponsorship: sponsor {}, {} lamports per user",
+        sponsor,
+        per_user_limit
+    );
+    Ok(())
+}
+
+pub(crate) fn fund_rent_sponsorship(ctx: Context<FundRentSponsorship>, amount: u64) -> Result<()> {
+    require!(amount > 0, NeptuError::InvalidAmount);
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.funder.to_account_info(),
+                to: ctx.accounts.rent_sponsorship.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+    msg!("Funded rent sponsorship with {} lamports", amount);
+    Ok(())
+}
+
+pub(crate) fn withdraw_rent_sponsorship(
+    ctx: Context<WithdrawRentSponsorship>,
+    amount: u64,
+) -> Result<()> {
+    let info = ctx.accounts.rent_sponsorship.to_account_info();
+    let rent_floor = Rent::get()?.minimum_balance(info.data_len());
+    require!(
+        info.lamports().saturating_sub(amount) >= rent_floor,
+        NeptuError::InsufficientVaultBalance
+    );
+    info.sub_lamports(amount)?;
+    ctx.accounts.authority.add_lamports(amount)?;
+    msg!("Withdrew {} lamports from rent sponsorship", amount);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ConfigureRentSponsorship<'info> {
+    #[account(
+        mut,
+        constraint = authority.key() == economy_state.authority @ NeptuError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"economy_state"],
+        bump,
+    )]
+    pub economy_state: Account<'info, EconomyState>,
+
+    #[account(
+        init_if_needed,
+        seeds = [b"rent_sponsorship"],
+        bump,
+        payer = authority,
+        space = RentSponsorship::SIZE,
+    )]
+    pub rent_sponsorship: Account<'info, RentSponsorship>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FundRentSponsorship<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"rent_sponsorship"],
+        bump = rent_sponsorship.bump,
+    )]
+    pub rent_sponsorship: Account<'info, RentSponsorship>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawRentSponsorship<'info> {
+    #[account(
+        mut,
+        constraint = authority.key() == economy_state.authority @ NeptuError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"economy_state"],
+        bump,
+        constraint = !economy_state.paused @ NeptuError::ProgramPaused,
+    )]
+    pub economy_state: Account<'info, EconomyState>,
+
+    #[account(
+        mut,
+        seeds = [b"rent_sponsorship"],
+        bump = rent_sponsorship.bump,
+    )]
+    pub rent_sponsorship: Account<'info, RentSponsorship>,
+}
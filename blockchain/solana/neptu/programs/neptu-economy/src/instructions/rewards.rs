@@ -0,0 +1,371 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::{get_associated_token_address, AssociatedToken};
+use anchor_spl::token::{transfer, Mint, Token, TokenAccount, Transfer};
+
+use crate::{constants::*, errors::NeptuError, events::*, helpers::*, state::*};
+
+pub(crate) fn claim_rewards(
+    ctx: Context<ClaimRewards>,
+    amount: u64,
+    nonce: u64,
+    expires_at: i64,
+    _signature: [u8; 64],
+) -> Result<()> {
+    // In production, verify the Ed25519 signature from backend
+    // The signature proves user earned this amount
+    // For now, the backend co-signs the transaction as claim signer
+
+    msg!(
+        "Claiming {} NEPTU rewards, nonce: {}, expires at: {}",
+        amount,
+        nonce,
+        expires_at
+    );
+
+    require!(amount > 0, NeptuError::InvalidAmount);
+    require!(
+        Clock::get()?.unix_timestamp <= expires_at,
+        NeptuError::AuthorizationExpired
+    );
+
+    let new_claim_record = ctx.accounts.claim_record.user == Pubkey::default();
+    let new_profile = ctx.accounts.user_profile.first_activity_at == 0;
+    let new_neptu_account = open_neptu_account(
+        &ctx.accounts.user_neptu_account,
+        &ctx.accounts.fee_payer,
+        &ctx.accounts.user,
+        &ctx.accounts.neptu_mint,
+        &ctx.accounts.token_program,
+        &ctx.accounts.associated_token_program,
+        &ctx.accounts.system_program,
+    )?;
+
+    // Verify nonce hasn't been used (prevent replay)
+    let claim_record = &mut ctx.accounts.claim_record;
+    claim_record.user = ctx.accounts.user.key();
+    claim_record.consume_nonce(nonce)?;
+    sponsor_rent(
+        &mut ctx.accounts.rent_sponsorship,
+        &mut ctx.accounts.sponsored_rent,
+        &ctx.accounts.fee_payer,
+        ctx.accounts.user.key(),
+        &[
+            (SPONSOR_NEPTU_ACCOUNT, TokenAccount::LEN, new_neptu_account),
+            (SPONSOR_CLAIM_RECORD, ClaimRecord::SIZE, new_claim_record),
+            (SPONSOR_PROFILE, UserProfile::SIZE, new_profile),
+        ],
+    )?;
+
+    // Authorized amount is scaled by the current reward curve
+    let state = &mut ctx.accounts.economy_state;
+    let pool_balance = ctx.accounts.rewards_pool.amount;
+    let payout = state.reward_curve.apply(amount, pool_balance)?;
+    if payout > pool_balance {
+        msg!(
+            "Rewards pool holds {} NEPTU, claim needs {}",
+            pool_balance,
+            payout
+        );
+        return err!(NeptuError::InsufficientBalance);
+    }
+    claim_record.total_claimed = claim_record
+        .total_claimed
+        .checked_add(payout)
+        .ok_or(NeptuError::Overflow)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let grant = state.reserve_emission(claim_record, payout, now)?;
+    require!(grant.granted == payout, NeptuError::EmissionBudgetExceeded);
+    if let Some(scope) = grant.exhausted {
+        emit!(EmissionBudgetExhausted {
+            scope,
+            epoch: state.current_epoch,
+            user: claim_record.user,
+            requested: payout,
+            granted: grant.granted,
+            next_epoch_at: state.next_epoch_at(),
+        });
+    }
+    state.total_neptu_rewarded = state
+        .total_neptu_rewarded
+        .checked_add(payout)
+        .ok_or(NeptuError::Overflow)?;
+
+    let profile = &mut ctx.accounts.user_profile;
+    profile.touch(claim_record.user, now);
+    profile.record_earned(payout)?;
+
+    // Transfer NEPTU from rewards pool to user (NOT minting)
+    let seeds = &[b"economy".as_ref(), &[ctx.bumps.economy_authority]];
+    let signer_seeds = &[&seeds[..]];
+
+    transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.rewards_pool.to_account_info(),
+                to: ctx.accounts.user_neptu_account.to_account_info(),
+                authority: ctx.accounts.economy_authority.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        payout,
+    )?;
+    msg!("Transferred {} NEPTU from rewards pool to user", payout);
+
+    Ok(())
+}
+
+pub(crate) fn claim_pending_reward(ctx: Context<ClaimPendingReward>) -> Result<()> {
+    let pending = &mut ctx.accounts.pending_reward;
+    let pool_balance = ctx.accounts.rewards_pool.amount;
+    require!(pending.amount > 0, NeptuError::InvalidAmount);
+    if pool_balance == 0 {
+        msg!("Rewards pool is empty, {} NEPTU still owed", pending.amount);
+        return err!(NeptuError::InsufficientBalance);
+    }
+
+    let amount = pending.amount.min(pool_balance);
+    pending.amount -= amount;
+    pending.total_paid = pending
+        .total_paid
+        .checked_add(amount)
+        .ok_or(NeptuError::Overflow)?;
+
+    let state = &mut ctx.accounts.economy_state;
+    state.total_neptu_rewarded = state
+        .total_neptu_rewarded
+        .checked_add(amount)
+        .ok_or(NeptuError::Overflow)?;
+
+    let profile = &mut ctx.accounts.user_profile;
+    profile.touch(ctx.accounts.user.key(), Clock::get()?.unix_timestamp);
+    profile.record_earned(amount)?;
+
+    let seeds = &[b"economy".as_ref(), &[ctx.bumps.economy_authority]];
+    let signer_seeds = &[&seeds[..]];
+
+    transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.rewards_pool.to_account_info(),
+                to: ctx.accounts.user_neptu_account.to_account_info(),
+                authority: ctx.accounts.economy_authority.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+    )?;
+    msg!(
+        "Paid {} NEPTU pending reward, {} still owed",
+        amount,
+        pending.amount
+    );
+
+    Ok(())
+}
+
+pub(crate) fn get_reward_rate(ctx: Context<GetRewardRate>) -> Result<RewardRate> {
+    let curve = &ctx.accounts.economy_state.reward_curve;
+    let config = &ctx.accounts.pricing_config;
+    let pool_balance = ctx.accounts.rewards_pool.amount;
+
+    Ok(RewardRate {
+        rate_bps: curve.rate_bps(pool_balance),
+        pool_balance,
+        potensi_reward: curve.apply(config.get_sol_reward(&ReadingType::Potensi)?, pool_balance)?,
+        peluang_reward: curve.apply(config.get_sol_reward(&ReadingType::Peluang)?, pool_balance)?,
+        ai_chat_reward: curve.apply(config.get_sol_reward(&ReadingType::AiChat)?, pool_balance)?,
+        compatibility_reward: curve.apply(
+            config.get_sol_reward(&ReadingType::Compatibility)?,
+            pool_balance,
+        )?,
+    })
+}
+
+#[derive(Accounts)]
+pub struct GetRewardRate<'info> {
+    #[account(
+        seeds = [b"economy_state"],
+        bump,
+    )]
+    pub economy_state: Account<'info, EconomyState>,
+
+    #[account(
+        seeds = [b"pricing_config"],
+        bump,
+    )]
+    pub pricing_config: Account<'info, PricingConfig>,
+
+    #[account(address = economy_state.neptu_mint)]
+    pub neptu_mint: Account<'info, Mint>,
+
+    /// Rewards pool: ATA owned by economy_authority PDA
+    #[account(
+        associated_token::mint = neptu_mint,
+        associated_token::authority = economy_authority,
+    )]
+    pub rewards_pool: Account<'info, TokenAccount>,
+
+    /// CHECK: Economy authority PDA (rewards pool owner)
+    #[account(
+        seeds = [b"economy"],
+        bump,
+    )]
+    pub economy_authority: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimPendingReward<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// Pays rent and fees; may be a sponsor other than the user
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"pending_reward", user.key().as_ref()],
+        bump,
+    )]
+    pub pending_reward: Account<'info, PendingReward>,
+
+    /// Lifetime stats of the user
+    #[account(
+        init_if_needed,
+        seeds = [b"profile", user.key().as_ref()],
+        bump,
+        payer = fee_payer,
+        space = UserProfile::SIZE,
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    #[account(
+        mut,
+        seeds = [b"economy_state"],
+        bump,
+        constraint = !economy_state.paused @ NeptuError::ProgramPaused,
+    )]
+    pub economy_state: Account<'info, EconomyState>,
+
+    #[account(address = economy_state.neptu_mint)]
+    pub neptu_mint: Account<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = fee_payer,
+        associated_token::mint = neptu_mint,
+        associated_token::authority = user,
+    )]
+    pub user_neptu_account: Account<'info, TokenAccount>,
+
+    /// Rewards pool: ATA owned by economy_authority PDA
+    #[account(
+        mut,
+        associated_token::mint = neptu_mint,
+        associated_token::authority = economy_authority,
+    )]
+    pub rewards_pool: Account<'info, TokenAccount>,
+
+    /// CHECK: Economy authority PDA (rewards pool owner)
+    #[account(
+        seeds = [b"economy"],
+        bump,
+    )]
+    pub economy_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimRewards<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// Pays rent and fees; may be a sponsor other than the user
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+
+    /// Backend co-signer authorizing the claim
+    #[account(address = economy_state.claim_signer @ NeptuError::Unauthorized)]
+    pub claim_signer: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        seeds = [b"claim", user.key().as_ref()],
+        bump,
+        payer = fee_payer,
+        space = ClaimRecord::SIZE,
+    )]
+    pub claim_record: Account<'info, ClaimRecord>,
+
+    /// Lifetime stats of the user
+    #[account(
+        init_if_needed,
+        seeds = [b"profile", user.key().as_ref()],
+        bump,
+        payer = fee_payer,
+        space = UserProfile::SIZE,
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    #[account(
+        mut,
+        seeds = [b"economy_state"],
+        bump,
+        constraint = !economy_state.paused @ NeptuError::ProgramPaused,
+    )]
+    pub economy_state: Account<'info, EconomyState>,
+
+    #[account(mut)]
+    pub neptu_mint: Account<'info, Mint>,
+
+    /// CHECK: the user's NEPTU ATA; opened by the handler when missing, so sponsored
+    /// rent only covers it when this instruction created it
+    #[account(
+        mut,
+        address = get_associated_token_address(&user.key(), &neptu_mint.key()),
+    )]
+    pub user_neptu_account: UncheckedAccount<'info>,
+
+    /// Rewards pool: ATA owned by economy_authority PDA
+    #[account(
+        mut,
+        associated_token::mint = neptu_mint,
+        associated_token::authority = economy_authority,
+    )]
+    pub rewards_pool: Account<'info, TokenAccount>,
+
+    /// CHECK: Economy authority PDA (rewards pool owner)
+    #[account(
+        seeds = [b"economy"],
+        bump,
+    )]
+    pub economy_authority: UncheckedAccount<'info>,
+
+    /// Reimburses an approved fee payer's onboarding rent when given
+    #[account(
+        mut,
+        seeds = [b"rent_sponsorship"],
+        bump = rent_sponsorship.bump,
+    )]
+    pub rent_sponsorship: Option<Account<'info, RentSponsorship>>,
+
+    /// Onboarding rent already reimbursed for the user
+    #[account(
+        init_if_needed,
+        seeds = [b"sponsored_rent", user.key().as_ref()],
+        bump,
+        payer = fee_payer,
+        space = SponsoredRent::SIZE,
+    )]
+    pub sponsored_rent: Option<Account<'info, SponsoredRent>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,554 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{
+    approve, revoke, transfer, Approve, Mint, Revoke, Token, TokenAccount, Transfer,
+};
+
+use crate::{constants::*, errors::NeptuError, events::*, helpers::*, state::*};
+
+pub(crate) fn create_session(
+    ctx: Context<CreateSession>,
+    session_key: Pubkey,
+    expires_at: i64,
+    allowed_instructions: u8,
+    allowed_readings: u8,
+    sol_limit: u64,
+    neptu_limit: u64,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        expires_at > now && expires_at - now <= MAX_SESSION_DURATION,
+        NeptuError::InvalidSession
+    );
+    require!(
+        allowed_instructions != 0
+            && allowed_instructions & !(SESSION_PAY_WITH_SOL | SESSION_PAY_WITH_NEPTU) == 0
+            && allowed_readings != 0
+            && allowed_readings < 1 << 4,
+        NeptuError::InvalidSession
+    );
+
+    let owner = ctx.accounts.owner.key();
+    let session = &mut ctx.accounts.session;
+    session.owner = owner;
+    session.session_key = session_key;
+    session.expires_at = expires_at;
+    session.allowed_instructions = allowed_instructions;
+    session.allowed_readings = allowed_readings;
+    session.sol_limit = sol_limit;
+    session.sol_spent = 0;
+    session.neptu_limit = neptu_limit;
+    session.neptu_spent = 0;
+    session.rent_payer = ctx.accounts.fee_payer.key();
+
+    if sol_limit > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.owner.to_account_info(),
+                    to: ctx.accounts.session.to_account_info(),
+                },
+            ),
+            sol_limit,
+        )?;
+    }
+    if neptu_limit > 0 {
+        let owner_neptu_account = ctx
+            .accounts
+            .owner_neptu_account
+            .as_ref()
+            .ok_or(NeptuError::TokenAccountMissing)?;
+        approve(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Approve {
+                    to: owner_neptu_account.to_account_info(),
+                    delegate: ctx.accounts.session.to_account_info(),
+                    authority: ctx.accounts.owner.to_account_info(),
+                },
+            ),
+            neptu_limit,
+        )?;
+    }
+
+    emit!(SessionCreated {
+        owner,
+        session_key,
+        expires_at,
+        allowed_instructions,
+        allowed_readings,
+        sol_limit,
+        neptu_limit,
+    });
+    msg!(
+        "Session key {} authorized until {}",
+        session_key,
+        expires_at
+    );
+    Ok(())
+}
+
+pub(crate) fn revoke_session(ctx: Context<RevokeSession>) -> Result<()> {
+    let session = &ctx.accounts.session;
+    let unspent = session.sol_limit - session.sol_spent;
+    session.sub_lamports(unspent)?;
+    ctx.accounts.owner.add_lamports(unspent)?;
+    if let Some(owner_neptu_account) = &ctx.accounts.owner_neptu_account {
+        if owner_neptu_account.delegate == Some(session.key()).into() {
+            revoke(CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Revoke {
+                    source: owner_neptu_account.to_account_info(),
+                    authority: ctx.accounts.owner.to_account_info(),
+                },
+            ))?;
+        }
+    }
+
+    emit!(SessionRevoked {
+        owner: session.owner,
+        session_key: session.session_key,
+        sol_spent: session.sol_spent,
+        neptu_spent: session.neptu_spent,
+    });
+    msg!("Session key {} revoked", session.session_key);
+    Ok(())
+}
+
+pub(crate) fn session_pay_with_sol(
+    ctx: Context<SessionPayWithSol>,
+    reading_type: ReadingType,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let config = &ctx.accounts.pricing_config;
+    let tier = stake_benefits(
+        ctx.accounts.staking_pool.as_deref(),
+        &ctx.accounts.stake_position,
+        now,
+    );
+    let loyalty = loyalty_benefits(&ctx.accounts.loyalty_program, &ctx.accounts.user_profile);
+    let list_price = config.resolve_sol_price(&reading_type, &ctx.accounts.price_feed)?;
+    let sol_price = loyalty.discounted(tier.discounted(list_price));
+
+    let session = &mut ctx.accounts.session;
+    session.authorize(
+        now,
+        SESSION_PAY_WITH_SOL,
+        reading_type,
+        PaymentCurrency::Sol,
+        sol_price,
+    )?;
+    let owner = session.owner;
+
+    let pool_balance = ctx.accounts.rewards_pool.amount;
+    let state = &mut ctx.accounts.economy_state;
+    let claim_record = &mut ctx.accounts.claim_record;
+    claim_record.user = owner;
+    let neptu_reward = grant_payment_reward(
+        state,
+        claim_record,
+        &mut ctx.accounts.pending_reward,
+        tier.boosted(config.get_sol_reward(&reading_type)?)?,
+        pool_balance,
+        now,
+    )?;
+    state.total_sol_collected = state
+        .total_sol_collected
+        .checked_add(sol_price)
+        .ok_or(NeptuError::Overflow)?;
+
+    let profile = &mut ctx.accounts.user_profile;
+    profile.record_reading(owner, reading_type, now)?;
+    profile.total_sol_spent = profile
+        .total_sol_spent
+        .checked_add(sol_price)
+        .ok_or(NeptuError::Overflow)?;
+    profile.record_earned(neptu_reward)?;
+    profile.refresh_loyalty_tier(&ctx.accounts.loyalty_program);
+
+    msg!(
+        "Session pay with SOL: {} lamports for {:?}, reward: {} NEPTU",
+        sol_price,
+        reading_type,
+        neptu_reward
+    );
+
+    ctx.accounts.session.sub_lamports(sol_price)?;
+    let vault = &mut ctx.accounts.treasury_vault;
+    vault.add_lamports(sol_price)?;
+    vault.record_deposit(owner, sol_price)?;
+
+    if neptu_reward > 0 {
+        let seeds = &[b"economy".as_ref(), &[ctx.bumps.economy_authority]];
+        let signer_seeds = &[&seeds[..]];
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.rewards_pool.to_account_info(),
+                    to: ctx.accounts.owner_neptu_account.to_account_info(),
+                    authority: ctx.accounts.economy_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            neptu_reward,
+        )?;
+        msg!("Transferred {} NEPTU reward to owner", neptu_reward);
+    }
+    Ok(())
+}
+
+pub(crate) fn session_pay_with_neptu(
+    ctx: Context<SessionPayWithNeptu>,
+    reading_type: ReadingType,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let config = &ctx.accounts.pricing_config;
+    let tier = stake_benefits(
+        ctx.accounts.staking_pool.as_deref(),
+        &ctx.accounts.stake_position,
+        now,
+    );
+    let loyalty = loyalty_benefits(&ctx.accounts.loyalty_program, &ctx.accounts.user_profile);
+    let neptu_price = loyalty.discounted(tier.discounted(config.get_neptu_price(&reading_type)));
+
+    let session = &mut ctx.accounts.session;
+    session.authorize(
+        now,
+        SESSION_PAY_WITH_NEPTU,
+        reading_type,
+        PaymentCurrency::Neptu,
+        neptu_price,
+    )?;
+    let (owner, session_key) = (session.owner, session.session_key);
+
+    let seeds = &[
+        b"session".as_ref(),
+        owner.as_ref(),
+        session_key.as_ref(),
+        &[ctx.bumps.session],
+    ];
+    let split = settle_neptu_payment(
+        config,
+        &mut ctx.accounts.economy_state,
+        ctx.accounts.staking_pool.as_deref_mut(),
+        NeptuPaymentAccounts {
+            token_program: &ctx.accounts.token_program,
+            neptu_mint: &ctx.accounts.neptu_mint,
+            from: &ctx.accounts.owner_neptu_account,
+            authority: ctx.accounts.session.to_account_info(),
+            ecosystem_pool: &ctx.accounts.ecosystem_pool,
+            stake_vault: ctx.accounts.stake_vault.as_ref(),
+        },
+        neptu_price,
+        &[&seeds[..]],
+    )?;
+
+    let profile = &mut ctx.accounts.user_profile;
+    profile.record_reading(owner, reading_type, now)?;
+    profile.total_neptu_spent = profile
+        .total_neptu_spent
+        .checked_add(neptu_price)
+        .ok_or(NeptuError::Overflow)?;
+    profile.total_neptu_burned = profile
+        .total_neptu_burned
+        .checked_add(split.burned)
+        .ok_or(NeptuError::Overflow)?;
+    profile.refresh_loyalty_tier(&ctx.accounts.loyalty_program);
+
+    msg!(
+        "Session pay with NEPTU: {} total, {} burned, {} recycled",
+        neptu_price,
+        split.burned,
+        split.recycled
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(session_key: Pubkey)]
+pub struct CreateSession<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// Pays rent and fees; may be a sponsor other than the owner
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+
+    #[account(
+        seeds = [b"economy_state"],
+        bump,
+        constraint = !economy_state.paused @ NeptuError::ProgramPaused,
+    )]
+    pub economy_state: Account<'info, EconomyState>,
+
+    #[account(
+        init,
+        seeds = [b"session", owner.key().as_ref(), session_key.as_ref()],
+        bump,
+        payer = fee_payer,
+        space = Session::SIZE,
+    )]
+    pub session: Account<'info, Session>,
+
+    /// Owner's NEPTU account delegated to the session
+    #[account(
+        mut,
+        associated_token::mint = economy_state.neptu_mint,
+        associated_token::authority = owner,
+    )]
+    pub owner_neptu_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeSession<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: Only used to derive the session address
+    pub session_key: UncheckedAccount<'info>,
+
+    /// CHECK: Paid the session rent, receives it back
+    #[account(mut, address = session.rent_payer)]
+    pub rent_payer: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"session", owner.key().as_ref(), session_key.key().as_ref()],
+        bump,
+        has_one = owner,
+        close = rent_payer,
+    )]
+    pub session: Account<'info, Session>,
+
+    #[account(
+        mut,
+        token::authority = owner,
+    )]
+    pub owner_neptu_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct SessionPayWithSol<'info> {
+    #[account(mut)]
+    pub session_key: Signer<'info>,
+
+    /// Pays rent and fees; may be a sponsor other than the session key
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+
+    /// CHECK: Session owner, checked against the session
+    pub owner: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"session", owner.key().as_ref(), session_key.key().as_ref()],
+        bump,
+        has_one = owner,
+        has_one = session_key,
+    )]
+    pub session: Account<'info, Session>,
+
+    #[account(
+        seeds = [b"pricing_config"],
+        bump,
+    )]
+    pub pricing_config: Account<'info, PricingConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"economy_state"],
+        bump,
+        constraint = !economy_state.paused @ NeptuError::ProgramPaused,
+    )]
+    pub economy_state: Account<'info, EconomyState>,
+
+    /// CHECK: Pyth SOL/USD price update, must be `pricing_config.price_feed`;
+    /// required for USD-priced readings
+    pub price_feed: Option<UncheckedAccount<'info>>,
+
+    /// Per-user emission budget tracking
+    #[account(
+        init_if_needed,
+        seeds = [b"claim", owner.key().as_ref()],
+        bump,
+        payer = fee_payer,
+        space = ClaimRecord::SIZE,
+    )]
+    pub claim_record: Account<'info, ClaimRecord>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury_vault"],
+        bump = treasury_vault.bump,
+    )]
+    pub treasury_vault: Account<'info, TreasuryVault>,
+
+    #[account(address = economy_state.neptu_mint)]
+    pub neptu_mint: Account<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = fee_payer,
+        associated_token::mint = neptu_mint,
+        associated_token::authority = owner,
+    )]
+    pub owner_neptu_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = neptu_mint,
+        associated_token::authority = economy_authority,
+    )]
+    pub rewards_pool: Account<'info, TokenAccount>,
+
+    /// CHECK: Economy authority PDA (rewards pool owner)
+    #[account(
+        seeds = [b"economy"],
+        bump,
+    )]
+    pub economy_authority: UncheckedAccount<'info>,
+
+    /// IOU record, required when the shortfall policy is Iou and the pool is short
+    #[account(
+        init_if_needed,
+        seeds = [b"pending_reward", owner.key().as_ref()],
+        bump,
+        payer = fee_payer,
+        space = PendingReward::SIZE,
+    )]
+    pub pending_reward: Option<Account<'info, PendingReward>>,
+
+    /// Owner's stake tier applies a price discount / reward boost when both are given
+    #[account(
+        seeds = [b"staking_pool"],
+        bump,
+    )]
+    pub staking_pool: Option<Account<'info, StakingPool>>,
+
+    #[account(
+        seeds = [b"stake", owner.key().as_ref()],
+        bump,
+    )]
+    pub stake_position: Option<Account<'info, StakePosition>>,
+
+    /// Lifetime stats of the owner
+    #[account(
+        init_if_needed,
+        seeds = [b"profile", owner.key().as_ref()],
+        bump,
+        payer = fee_payer,
+        space = UserProfile::SIZE,
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    /// Applies the profile's loyalty tier discount when given
+    #[account(
+        seeds = [b"loyalty"],
+        bump,
+    )]
+    pub loyalty_program: Option<Account<'info, LoyaltyProgram>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SessionPayWithNeptu<'info> {
+    #[account(mut)]
+    pub session_key: Signer<'info>,
+
+    /// Pays rent and fees; may be a sponsor other than the session key
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+
+    /// CHECK: Session owner, checked against the session
+    pub owner: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"session", owner.key().as_ref(), session_key.key().as_ref()],
+        bump,
+        has_one = owner,
+        has_one = session_key,
+    )]
+    pub session: Account<'info, Session>,
+
+    #[account(
+        seeds = [b"pricing_config"],
+        bump,
+    )]
+    pub pricing_config: Account<'info, PricingConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"economy_state"],
+        bump,
+        constraint = !economy_state.paused @ NeptuError::ProgramPaused,
+    )]
+    pub economy_state: Account<'info, EconomyState>,
+
+    #[account(mut, address = economy_state.neptu_mint)]
+    pub neptu_mint: Account<'info, Mint>,
+
+    /// Owner's NEPTU account, delegated to the session
+    #[account(
+        mut,
+        associated_token::mint = neptu_mint,
+        associated_token::authority = owner,
+    )]
+    pub owner_neptu_account: Account<'info, TokenAccount>,
+
+    #[account(mut, address = economy_state.ecosystem_pool)]
+    pub ecosystem_pool: Account<'info, TokenAccount>,
+
+    /// Owner's stake tier applies a price discount when the position is given
+    #[account(
+        mut,
+        seeds = [b"staking_pool"],
+        bump,
+    )]
+    pub staking_pool: Option<Account<'info, StakingPool>>,
+
+    #[account(
+        seeds = [b"stake", owner.key().as_ref()],
+        bump,
+    )]
+    pub stake_position: Option<Account<'info, StakePosition>>,
+
+    /// Receives the stakers' yield share of the recycled amount once staking is set up
+    #[account(
+        mut,
+        seeds = [b"stake_vault"],
+        bump,
+    )]
+    pub stake_vault: Option<Account<'info, TokenAccount>>,
+
+    /// Lifetime stats of the owner
+    #[account(
+        init_if_needed,
+        seeds = [b"profile", owner.key().as_ref()],
+        bump,
+        payer = fee_payer,
+        space = UserProfile::SIZE,
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    /// Applies the profile's loyalty tier discount when given
+    #[account(
+        seeds = [b"loyalty"],
+        bump,
+    )]
+    pub loyalty_program: Option<Account<'info, LoyaltyProgram>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
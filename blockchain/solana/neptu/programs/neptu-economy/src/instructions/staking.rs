@@ -0,0 +1,440 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{burn, transfer, Burn, Mint, Token, TokenAccount, Transfer};
+
+use crate::{constants::*, errors::NeptuError, events::*, state::*};
+
+pub(crate) fn initialize_staking(
+    ctx: Context<InitializeStaking>,
+    tiers: Vec<StakeTier>,
+    early_unstake_penalty_bps: u16,
+) -> Result<()> {
+    let pool = &mut ctx.accounts.staking_pool;
+    pool.set_tiers(tiers, early_unstake_penalty_bps)?;
+    pool.stake_vault = ctx.accounts.stake_vault.key();
+    pool.total_staked = 0;
+    pool.total_penalties_burned = 0;
+    pool.yield_bps = 0;
+    pool.acc_yield_per_share = 0;
+    pool.total_yield_distributed = 0;
+    pool.total_yield_claimed = 0;
+    msg!("Staking initialized with {} tiers", pool.tiers.len());
+    Ok(())
+}
+
+pub(crate) fn update_staking_tiers(
+    ctx: Context<UpdateStakingTiers>,
+    tiers: Vec<StakeTier>,
+    early_unstake_penalty_bps: u16,
+) -> Result<()> {
+    let pool = &mut ctx.accounts.staking_pool;
+    pool.set_tiers(tiers, early_unstake_penalty_bps)?;
+    msg!("Staking tiers updated: {} tiers", pool.tiers.len());
+    Ok(())
+}
+
+pub(crate) fn update_staking_yield(ctx: Context<UpdateStakingTiers>, yield_bps: u16) -> Result<()> {
+    require!(
+        u64::from(yield_bps) <= BPS_DENOMINATOR,
+        NeptuError::InvalidAmount
+    );
+    ctx.accounts.staking_pool.yield_bps = yield_bps;
+    msg!("Staking yield set to {} bps of recycled NEPTU", yield_bps);
+    Ok(())
+}
+
+pub(crate) fn claim_stake_yield(ctx: Context<ClaimStakeYield>) -> Result<()> {
+    let pool = &mut ctx.accounts.staking_pool;
+    let position = &mut ctx.accounts.stake_position;
+    position.settle_yield(pool.acc_yield_per_share)?;
+
+    let amount = position.pending_yield;
+    require!(amount > 0, NeptuError::InvalidAmount);
+    position.pending_yield = 0;
+    pool.total_yield_claimed = pool
+        .total_yield_claimed
+        .checked_add(amount)
+        .ok_or(NeptuError::Overflow)?;
+
+    let profile = &mut ctx.accounts.user_profile;
+    profile.touch(position.owner, Clock::get()?.unix_timestamp);
+    profile.record_earned(amount)?;
+
+    let seeds = &[b"economy".as_ref(), &[ctx.bumps.economy_authority]];
+    let signer_seeds = &[&seeds[..]];
+
+    transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.stake_vault.to_account_info(),
+                to: ctx.accounts.user_neptu_account.to_account_info(),
+                authority: ctx.accounts.economy_authority.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+    )?;
+
+    emit!(StakeYieldClaimed {
+        owner: position.owner,
+        amount,
+    });
+    msg!("Claimed {} NEPTU staking yield", amount);
+    Ok(())
+}
+
+pub(crate) fn stake(ctx: Context<Stake>, amount: u64, lock_duration: i64) -> Result<()> {
+    require!(amount > 0 && lock_duration >= 0, NeptuError::InvalidAmount);
+
+    transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.user_neptu_account.to_account_info(),
+                to: ctx.accounts.stake_vault.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let acc_yield_per_share = ctx.accounts.staking_pool.acc_yield_per_share;
+    let position = &mut ctx.accounts.stake_position;
+    if position.amount == 0 {
+        position.owner = ctx.accounts.user.key();
+        position.staked_at = now;
+    }
+    position.settle_yield(acc_yield_per_share)?;
+    position.amount = position
+        .amount
+        .checked_add(amount)
+        .ok_or(NeptuError::Overflow)?;
+    position.reset_yield_debt(acc_yield_per_share)?;
+    position.lock_duration = position.lock_duration.max(lock_duration);
+    position.lock_until = position
+        .lock_until
+        .max(now.checked_add(lock_duration).ok_or(NeptuError::Overflow)?);
+
+    let pool = &mut ctx.accounts.staking_pool;
+    pool.total_staked = pool
+        .total_staked
+        .checked_add(amount)
+        .ok_or(NeptuError::Overflow)?;
+
+    let tier = pool.tier_index(position, now);
+    emit!(Staked {
+        owner: position.owner,
+        amount,
+        total: position.amount,
+        lock_until: position.lock_until,
+        tier,
+    });
+    msg!(
+        "Staked {} NEPTU, position {} locked until {}",
+        amount,
+        position.amount,
+        position.lock_until
+    );
+    Ok(())
+}
+
+pub(crate) fn unstake(ctx: Context<Unstake>, amount: u64) -> Result<()> {
+    let position = &mut ctx.accounts.stake_position;
+    require!(
+        amount > 0 && amount <= position.amount,
+        NeptuError::InvalidAmount
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let pool = &mut ctx.accounts.staking_pool;
+    position.settle_yield(pool.acc_yield_per_share)?;
+    let penalty = if now < position.lock_until {
+        ((amount as u128 * pool.early_unstake_penalty_bps as u128) / BPS_DENOMINATOR as u128) as u64
+    } else {
+        0
+    };
+    let returned = amount - penalty;
+
+    position.amount -= amount;
+    position.reset_yield_debt(pool.acc_yield_per_share)?;
+    if position.amount == 0 {
+        position.lock_duration = 0;
+        position.lock_until = 0;
+    }
+    pool.total_staked -= amount;
+
+    let seeds = &[b"economy".as_ref(), &[ctx.bumps.economy_authority]];
+    let signer_seeds = &[&seeds[..]];
+
+    if penalty > 0 {
+        burn(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.neptu_mint.to_account_info(),
+                    from: ctx.accounts.stake_vault.to_account_info(),
+                    authority: ctx.accounts.economy_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            penalty,
+        )?;
+        pool.total_penalties_burned = pool
+            .total_penalties_burned
+            .checked_add(penalty)
+            .ok_or(NeptuError::Overflow)?;
+        let state = &mut ctx.accounts.economy_state;
+        state.total_neptu_burned = state
+            .total_neptu_burned
+            .checked_add(penalty)
+            .ok_or(NeptuError::Overflow)?;
+        msg!("Burned {} NEPTU early unstake penalty", penalty);
+    }
+
+    transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.stake_vault.to_account_info(),
+                to: ctx.accounts.user_neptu_account.to_account_info(),
+                authority: ctx.accounts.economy_authority.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        returned,
+    )?;
+
+    emit!(Unstaked {
+        owner: position.owner,
+        amount,
+        penalty_burned: penalty,
+        remaining: position.amount,
+    });
+    msg!("Unstaked {} NEPTU, {} returned", amount, returned);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeStaking<'info> {
+    #[account(
+        mut,
+        constraint = authority.key() == economy_state.authority @ NeptuError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"economy_state"],
+        bump,
+    )]
+    pub economy_state: Account<'info, EconomyState>,
+
+    #[account(
+        init,
+        seeds = [b"staking_pool"],
+        bump,
+        payer = authority,
+        space = StakingPool::SIZE,
+    )]
+    pub staking_pool: Account<'info, StakingPool>,
+
+    #[account(address = economy_state.neptu_mint)]
+    pub neptu_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        seeds = [b"stake_vault"],
+        bump,
+        payer = authority,
+        token::mint = neptu_mint,
+        token::authority = economy_authority,
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+
+    /// CHECK: Economy authority PDA (vault owner)
+    #[account(
+        seeds = [b"economy"],
+        bump,
+    )]
+    pub economy_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateStakingTiers<'info> {
+    #[account(
+        constraint = authority.key() == economy_state.authority @ NeptuError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"economy_state"],
+        bump,
+    )]
+    pub economy_state: Account<'info, EconomyState>,
+
+    #[account(
+        mut,
+        seeds = [b"staking_pool"],
+        bump,
+    )]
+    pub staking_pool: Account<'info, StakingPool>,
+}
+
+#[derive(Accounts)]
+pub struct Stake<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// Pays rent and fees; may be a sponsor other than the user
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"staking_pool"],
+        bump,
+    )]
+    pub staking_pool: Account<'info, StakingPool>,
+
+    #[account(
+        init_if_needed,
+        seeds = [b"stake", user.key().as_ref()],
+        bump,
+        payer = fee_payer,
+        space = StakePosition::SIZE,
+    )]
+    pub stake_position: Account<'info, StakePosition>,
+
+    #[account(
+        mut,
+        token::authority = user,
+    )]
+    pub user_neptu_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = staking_pool.stake_vault,
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Unstake<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"staking_pool"],
+        bump,
+    )]
+    pub staking_pool: Account<'info, StakingPool>,
+
+    #[account(
+        mut,
+        seeds = [b"stake", user.key().as_ref()],
+        bump,
+    )]
+    pub stake_position: Account<'info, StakePosition>,
+
+    #[account(
+        mut,
+        seeds = [b"economy_state"],
+        bump,
+    )]
+    pub economy_state: Account<'info, EconomyState>,
+
+    #[account(mut, address = economy_state.neptu_mint)]
+    pub neptu_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = neptu_mint,
+        token::authority = user,
+    )]
+    pub user_neptu_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = staking_pool.stake_vault,
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+
+    /// CHECK: Economy authority PDA (vault owner)
+    #[account(
+        seeds = [b"economy"],
+        bump,
+    )]
+    pub economy_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimStakeYield<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// Pays rent and fees; may be a sponsor other than the user
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+
+    #[account(
+        seeds = [b"economy_state"],
+        bump,
+        constraint = !economy_state.paused @ NeptuError::ProgramPaused,
+    )]
+    pub economy_state: Account<'info, EconomyState>,
+
+    #[account(
+        mut,
+        seeds = [b"staking_pool"],
+        bump,
+    )]
+    pub staking_pool: Account<'info, StakingPool>,
+
+    #[account(
+        mut,
+        seeds = [b"stake", user.key().as_ref()],
+        bump,
+    )]
+    pub stake_position: Account<'info, StakePosition>,
+
+    /// Lifetime stats of the user
+    #[account(
+        init_if_needed,
+        seeds = [b"profile", user.key().as_ref()],
+        bump,
+        payer = fee_payer,
+        space = UserProfile::SIZE,
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    #[account(
+        mut,
+        token::authority = user,
+    )]
+    pub user_neptu_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = staking_pool.stake_vault,
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+
+    /// CHECK: Economy authority PDA (vault owner)
+    #[account(
+        seeds = [b"economy"],
+        bump,
+    )]
+    pub economy_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
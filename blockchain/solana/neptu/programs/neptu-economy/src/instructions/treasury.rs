@@ -0,0 +1,782 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer, Mint, Token, TokenAccount, Transfer};
+
+use crate::{constants::*, errors::NeptuError, events::*, helpers::*, state::*};
+
+pub(crate) fn initialize_treasury_vault(
+    ctx: Context<InitializeTreasuryVault>,
+    approvers: Vec<Pubkey>,
+    threshold: u8,
+    spending_limit: u64,
+    period_duration: i64,
+) -> Result<()> {
+    let vault = &mut ctx.accounts.treasury_vault;
+    vault.apply_config(approvers, threshold, spending_limit, period_duration)?;
+    vault.period_start = Clock::get()?.unix_timestamp;
+    vault.bump = ctx.bumps.treasury_vault;
+
+    ctx.accounts.economy_state.treasury = vault.key();
+    msg!(
+        "Treasury vault initialized: {}-of-{} approvers",
+        vault.threshold,
+        vault.approvers.len()
+    );
+    Ok(())
+}
+
+pub(crate) fn propose_treasury_config(
+    ctx: Context<ProposeTreasuryConfig>,
+    approvers: Vec<Pubkey>,
+    threshold: u8,
+    spending_limit: u64,
+    period_duration: i64,
+) -> Result<()> {
+    validate_member_set(&approvers, threshold, MAX_TREASURY_APPROVERS)?;
+    require!(period_duration > 0, NeptuError::InvalidAmount);
+    open_config_proposal(
+        ctx.accounts,
+        None,
+        approvers,
+        threshold,
+        spending_limit,
+        period_duration,
+    )
+}
+
+pub(crate) fn propose_token_spending_limit(
+    ctx: Context<ProposeTreasuryConfig>,
+    mint: Pubkey,
+    spending_limit: u64,
+) -> Result<()> {
+    open_config_proposal(ctx.accounts, Some(mint), Vec::new(), 0, spending_limit, 0)
+}
+
+pub(crate) fn approve_treasury_config(ctx: Context<ApproveTreasuryConfig>) -> Result<()> {
+    let vault = &ctx.accounts.treasury_vault;
+    let index = vault.approver_index(&ctx.accounts.approver.key())?;
+
+    let proposal = &mut ctx.accounts.proposal;
+    require!(
+        proposal.executed_at == 0,
+        NeptuError::ProposalAlreadyExecuted
+    );
+    require!(
+        proposal.config_version == vault.config_version,
+        NeptuError::ProposalStale
+    );
+    require!(
+        proposal.approvals & (1 << index) == 0,
+        NeptuError::AlreadyApproved
+    );
+    proposal.approvals |= 1 << index;
+
+    msg!(
+        "Treasury config change {} approved ({}/{})",
+        proposal.id,
+        proposal.approvals.count_ones(),
+        vault.threshold
+    );
+    Ok(())
+}
+
+pub(crate) fn execute_treasury_config(ctx: Context<ExecuteTreasuryConfig>) -> Result<()> {
+    let vault = &mut ctx.accounts.treasury_vault;
+    vault.approver_index(&ctx.accounts.executor.key())?;
+
+    let proposal = &mut ctx.accounts.proposal;
+    require!(
+        proposal.executed_at == 0,
+        NeptuError::ProposalAlreadyExecuted
+    );
+    require!(
+        proposal.config_version == vault.config_version,
+        NeptuError::ProposalStale
+    );
+    require!(
+        proposal.approvals.count_ones() >= u32::from(vault.threshold),
+        NeptuError::NotEnoughApprovals
+    );
+    proposal.executed_at = Clock::get()?.unix_timestamp;
+
+    if let Some(mint) = proposal.mint {
+        let payment_mint = ctx
+            .accounts
+            .payment_mint
+            .as_mut()
+            .ok_or(NeptuError::PaymentMintNotAccepted)?;
+        require_keys_eq!(payment_mint.mint, mint, NeptuError::PaymentMintNotAccepted);
+        payment_mint.spending_limit = proposal.spending_limit;
+        msg!(
+            "Token spending limit of {} set to {} per {}s",
+            mint,
+            proposal.spending_limit,
+            vault.period_duration
+        );
+    } else {
+        vault.apply_config(
+            proposal.approvers.clone(),
+            proposal.threshold,
+            proposal.spending_limit,
+            proposal.period_duration,
+        )?;
+        msg!(
+            "Treasury config updated: {}-of-{}, limit {} per {}s",
+            vault.threshold,
+            vault.approvers.len(),
+            vault.spending_limit,
+            vault.period_duration
+        );
+    }
+    vault.config_version = vault
+        .config_version
+        .checked_add(1)
+        .ok_or(NeptuError::Overflow)?;
+    Ok(())
+}
+
+pub(crate) fn propose_withdrawal(
+    ctx: Context<ProposeWithdrawal>,
+    recipient: Pubkey,
+    amount: u64,
+    memo: [u8; 32],
+) -> Result<()> {
+    open_withdrawal(ctx.accounts, None, recipient, amount, memo)?;
+    msg!("Withdrawal proposed: {} lamports", amount);
+    Ok(())
+}
+
+pub(crate) fn propose_token_withdrawal(
+    ctx: Context<ProposeWithdrawal>,
+    mint: Pubkey,
+    recipient: Pubkey,
+    amount: u64,
+    memo: [u8; 32],
+) -> Result<()> {
+    open_withdrawal(ctx.accounts, Some(mint), recipient, amount, memo)?;
+    msg!("Token withdrawal proposed: {} of mint {}", amount, mint);
+    Ok(())
+}
+
+pub(crate) fn approve_withdrawal(ctx: Context<ApproveWithdrawal>) -> Result<()> {
+    let vault = &ctx.accounts.treasury_vault;
+    let approver = ctx.accounts.approver.key();
+    let index = vault.approver_index(&approver)?;
+
+    let proposal = &mut ctx.accounts.withdrawal;
+    require!(
+        proposal.executed_at == 0,
+        NeptuError::ProposalAlreadyExecuted
+    );
+    require!(
+        proposal.config_version == vault.config_version,
+        NeptuError::ProposalStale
+    );
+    require!(
+        proposal.approvals & (1 << index) == 0,
+        NeptuError::AlreadyApproved
+    );
+    proposal.approvals |= 1 << index;
+
+    emit!(WithdrawalApproved {
+        id: proposal.id,
+        approver,
+        approvals: proposal.approvals.count_ones() as u8,
+    });
+    msg!(
+        "Withdrawal {} approved ({}/{})",
+        proposal.id,
+        proposal.approvals.count_ones(),
+        vault.threshold
+    );
+    Ok(())
+}
+
+pub(crate) fn execute_withdrawal(ctx: Context<ExecuteWithdrawal>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let vault = &mut ctx.accounts.treasury_vault;
+    vault.approver_index(&ctx.accounts.executor.key())?;
+
+    let proposal = &mut ctx.accounts.withdrawal;
+    require!(
+        proposal.executed_at == 0,
+        NeptuError::ProposalAlreadyExecuted
+    );
+    require!(
+        proposal.config_version == vault.config_version,
+        NeptuError::ProposalStale
+    );
+    require!(
+        proposal.approvals.count_ones() >= u32::from(vault.threshold),
+        NeptuError::NotEnoughApprovals
+    );
+
+    if let Some(mint) = proposal.mint {
+        let (Some(payment_mint), Some(token_vault), Some(token_program)) = (
+            &mut ctx.accounts.payment_mint,
+            &ctx.accounts.treasury_token_vault,
+            &ctx.accounts.token_program,
+        ) else {
+            return err!(NeptuError::TokenAccountMissing);
+        };
+        require_keys_eq!(payment_mint.mint, mint, NeptuError::PaymentMintNotAccepted);
+        require_keys_eq!(
+            token_vault.key(),
+            payment_mint.treasury_token_vault,
+            NeptuError::PaymentMintNotAccepted
+        );
+        payment_mint.spend(proposal.amount, vault.period_duration, now)?;
+        let seeds = &[b"treasury_vault".as_ref(), &[vault.bump]];
+        let signer_seeds = &[&seeds[..]];
+        transfer(
+            CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                Transfer {
+                    from: token_vault.to_account_info(),
+                    to: ctx.accounts.recipient.to_account_info(),
+                    authority: vault.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            proposal.amount,
+        )?;
+    } else {
+        vault.spend(proposal.amount, now)?;
+        let vault_info = vault.to_account_info();
+        let rent_floor = Rent::get()?.minimum_balance(vault_info.data_len());
+        require!(
+            vault_info.lamports().saturating_sub(proposal.amount) >= rent_floor,
+            NeptuError::InsufficientVaultBalance
+        );
+        vault_info.sub_lamports(proposal.amount)?;
+        ctx.accounts.recipient.add_lamports(proposal.amount)?;
+    }
+    proposal.executed_at = now;
+
+    emit!(WithdrawalExecuted {
+        id: proposal.id,
+        recipient: proposal.recipient,
+        amount: proposal.amount,
+        period_spent: vault.period_spent,
+        total_withdrawn: vault.total_withdrawn,
+    });
+    msg!(
+        "Withdrawal {} executed: {} to {}",
+        proposal.id,
+        proposal.amount,
+        proposal.recipient
+    );
+    Ok(())
+}
+
+pub(crate) fn initialize_ecosystem_vault(ctx: Context<InitializeEcosystemVault>) -> Result<()> {
+    let state = &mut ctx.accounts.economy_state;
+    state.ecosystem_pool = ctx.accounts.ecosystem_vault.key();
+    msg!("Ecosystem vault initialized: {}", state.ecosystem_pool);
+    Ok(())
+}
+
+pub(crate) fn update_rebalance_policy(
+    ctx: Context<UpdateRebalancePolicy>,
+    threshold: Option<u64>,
+    bps: Option<u16>,
+) -> Result<()> {
+    let state = &mut ctx.accounts.economy_state;
+
+    if let Some(v) = threshold {
+        state.rebalance_threshold = v;
+    }
+    if let Some(v) = bps {
+        require!(u64::from(v) <= BPS_DENOMINATOR, NeptuError::InvalidAmount);
+        state.rebalance_bps = v;
+    }
+
+    msg!(
+        "Rebalance policy updated: threshold {}, {} bps",
+        state.rebalance_threshold,
+        state.rebalance_bps
+    );
+    Ok(())
+}
+
+pub(crate) fn rebalance_pools(
+    ctx: Context<RebalancePools>,
+    direction: RebalanceDirection,
+    amount: u64,
+) -> Result<()> {
+    let signer = ctx.accounts.signer.key();
+    let admin = ctx.accounts.economy_state.authority;
+    require!(
+        signer == admin
+            || ctx
+                .accounts
+                .roles
+                .as_ref()
+                .is_some_and(|roles| roles.has_role(&signer, Role::Treasurer)),
+        NeptuError::Unauthorized
+    );
+    require!(amount > 0, NeptuError::InvalidAmount);
+
+    ctx.accounts.move_funds(
+        direction,
+        amount,
+        RebalanceTrigger::Admin,
+        ctx.bumps.economy_authority,
+    )
+}
+
+pub(crate) fn crank_rebalance(ctx: Context<RebalancePools>) -> Result<()> {
+    let state = &ctx.accounts.economy_state;
+    let excess = ctx
+        .accounts
+        .ecosystem_pool
+        .amount
+        .saturating_sub(state.rebalance_threshold);
+    let amount = ((excess as u128 * state.rebalance_bps as u128) / BPS_DENOMINATOR as u128) as u64;
+    require!(amount > 0, NeptuError::RebalanceNotNeeded);
+
+    ctx.accounts.move_funds(
+        RebalanceDirection::EcosystemToRewards,
+        amount,
+        RebalanceTrigger::Crank,
+        ctx.bumps.economy_authority,
+    )
+}
+
+#[derive(Accounts)]
+pub struct UpdateRebalancePolicy<'info> {
+    #[account(
+        constraint = roles.authorize(
+            &economy_state.authority,
+            &authority.key(),
+            Role::Treasurer,
+        ) @ NeptuError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"economy_state"],
+        bump,
+    )]
+    pub economy_state: Account<'info, EconomyState>,
+
+    #[account(
+        seeds = [b"roles"],
+        bump,
+    )]
+    pub roles: Account<'info, Roles>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeTreasuryVault<'info> {
+    #[account(
+        mut,
+        constraint = authority.key() == economy_state.authority @ NeptuError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"economy_state"],
+        bump,
+    )]
+    pub economy_state: Account<'info, EconomyState>,
+
+    #[account(
+        init,
+        seeds = [b"treasury_vault"],
+        bump,
+        payer = authority,
+        space = TreasuryVault::SIZE,
+    )]
+    pub treasury_vault: Account<'info, TreasuryVault>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeTreasuryConfig<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury_vault"],
+        bump = treasury_vault.bump,
+    )]
+    pub treasury_vault: Account<'info, TreasuryVault>,
+
+    #[account(
+        init,
+        seeds = [b"treasury_config", treasury_vault.config_proposal_count.to_le_bytes().as_ref()],
+        bump,
+        payer = proposer,
+        space = TreasuryConfigProposal::SIZE,
+    )]
+    pub proposal: Account<'info, TreasuryConfigProposal>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveTreasuryConfig<'info> {
+    pub approver: Signer<'info>,
+
+    #[account(
+        seeds = [b"treasury_vault"],
+        bump = treasury_vault.bump,
+    )]
+    pub treasury_vault: Account<'info, TreasuryVault>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury_config", proposal.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub proposal: Account<'info, TreasuryConfigProposal>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteTreasuryConfig<'info> {
+    pub executor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury_vault"],
+        bump = treasury_vault.bump,
+    )]
+    pub treasury_vault: Account<'info, TreasuryVault>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury_config", proposal.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub proposal: Account<'info, TreasuryConfigProposal>,
+
+    /// Required when the proposal sets a payment mint's spending limit
+    #[account(
+        mut,
+        seeds = [b"payment_mint", payment_mint.mint.as_ref()],
+        bump,
+    )]
+    pub payment_mint: Option<Account<'info, PaymentMint>>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeWithdrawal<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury_vault"],
+        bump = treasury_vault.bump,
+    )]
+    pub treasury_vault: Account<'info, TreasuryVault>,
+
+    #[account(
+        init,
+        seeds = [b"withdrawal", treasury_vault.withdrawal_count.to_le_bytes().as_ref()],
+        bump,
+        payer = proposer,
+        space = WithdrawalProposal::SIZE,
+    )]
+    pub withdrawal: Account<'info, WithdrawalProposal>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveWithdrawal<'info> {
+    pub approver: Signer<'info>,
+
+    #[account(
+        seeds = [b"treasury_vault"],
+        bump = treasury_vault.bump,
+    )]
+    pub treasury_vault: Account<'info, TreasuryVault>,
+
+    #[account(
+        mut,
+        seeds = [b"withdrawal", withdrawal.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub withdrawal: Account<'info, WithdrawalProposal>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteWithdrawal<'info> {
+    pub executor: Signer<'info>,
+
+    #[account(
+        seeds = [b"economy_state"],
+        bump,
+        constraint = !economy_state.paused @ NeptuError::ProgramPaused,
+    )]
+    pub economy_state: Account<'info, EconomyState>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury_vault"],
+        bump = treasury_vault.bump,
+    )]
+    pub treasury_vault: Account<'info, TreasuryVault>,
+
+    #[account(
+        mut,
+        seeds = [b"withdrawal", withdrawal.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub withdrawal: Account<'info, WithdrawalProposal>,
+
+    /// CHECK: Receives SOL (or tokens, as a token account), must match the proposal
+    #[account(mut, address = withdrawal.recipient)]
+    pub recipient: UncheckedAccount<'info>,
+
+    /// Source of token withdrawals
+    #[account(
+        mut,
+        token::authority = treasury_vault,
+    )]
+    pub treasury_token_vault: Option<Account<'info, TokenAccount>>,
+
+    /// Spending limit of token withdrawals
+    #[account(
+        mut,
+        seeds = [b"payment_mint", payment_mint.mint.as_ref()],
+        bump,
+    )]
+    pub payment_mint: Option<Account<'info, PaymentMint>>,
+
+    pub token_program: Option<Program<'info, Token>>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeEcosystemVault<'info> {
+    #[account(
+        mut,
+        constraint = authority.key() == economy_state.authority @ NeptuError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"economy_state"],
+        bump,
+    )]
+    pub economy_state: Account<'info, EconomyState>,
+
+    #[account(address = economy_state.neptu_mint)]
+    pub neptu_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        seeds = [b"ecosystem_pool"],
+        bump,
+        payer = authority,
+        token::mint = neptu_mint,
+        token::authority = economy_authority,
+    )]
+    pub ecosystem_vault: Account<'info, TokenAccount>,
+
+    /// CHECK: Economy authority PDA (vault owner)
+    #[account(
+        seeds = [b"economy"],
+        bump,
+    )]
+    pub economy_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RebalancePools<'info> {
+    /// Treasurer for `rebalance_pools`, anyone for `crank_rebalance`
+    pub signer: Signer<'info>,
+
+    /// Needed only when a non-authority treasurer calls `rebalance_pools`
+    #[account(
+        seeds = [b"roles"],
+        bump,
+    )]
+    pub roles: Option<Account<'info, Roles>>,
+
+    #[account(
+        mut,
+        seeds = [b"economy_state"],
+        bump,
+        constraint = !economy_state.paused @ NeptuError::ProgramPaused,
+    )]
+    pub economy_state: Account<'info, EconomyState>,
+
+    #[account(address = economy_state.neptu_mint)]
+    pub neptu_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        address = economy_state.ecosystem_pool,
+        token::authority = economy_authority,
+    )]
+    pub ecosystem_pool: Account<'info, TokenAccount>,
+
+    /// Rewards pool: ATA owned by economy_authority PDA
+    #[account(
+        mut,
+        associated_token::mint = neptu_mint,
+        associated_token::authority = economy_authority,
+    )]
+    pub rewards_pool: Account<'info, TokenAccount>,
+
+    /// CHECK: Economy authority PDA (owner of both vaults)
+    #[account(
+        seeds = [b"economy"],
+        bump,
+    )]
+    pub economy_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> RebalancePools<'info> {
+    fn move_funds(
+        &mut self,
+        direction: RebalanceDirection,
+        amount: u64,
+        trigger: RebalanceTrigger,
+        authority_bump: u8,
+    ) -> Result<()> {
+        let (from, to) = match direction {
+            RebalanceDirection::EcosystemToRewards => (&self.ecosystem_pool, &self.rewards_pool),
+            RebalanceDirection::RewardsToEcosystem => (&self.rewards_pool, &self.ecosystem_pool),
+        };
+        require!(from.amount >= amount, NeptuError::InsufficientVaultBalance);
+
+        let seeds = &[b"economy".as_ref(), &[authority_bump]];
+        let signer_seeds = &[&seeds[..]];
+
+        transfer(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                Transfer {
+                    from: from.to_account_info(),
+                    to: to.to_account_info(),
+                    authority: self.economy_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+
+        let state = &mut self.economy_state;
+        match direction {
+            RebalanceDirection::EcosystemToRewards => {
+                state.total_rebalanced_to_rewards = state
+                    .total_rebalanced_to_rewards
+                    .checked_add(amount)
+                    .ok_or(NeptuError::Overflow)?;
+            }
+            RebalanceDirection::RewardsToEcosystem => {
+                state.total_rebalanced_to_ecosystem = state
+                    .total_rebalanced_to_ecosystem
+                    .checked_add(amount)
+                    .ok_or(NeptuError::Overflow)?;
+            }
+        }
+        state.rebalance_count = state
+            .rebalance_count
+            .checked_add(1)
+            .ok_or(NeptuError::Overflow)?;
+        state.last_rebalance_at = Clock::get()?.unix_timestamp;
+
+        self.ecosystem_pool.reload()?;
+        self.rewards_pool.reload()?;
+        emit!(PoolsRebalanced {
+            direction,
+            trigger,
+            amount,
+            ecosystem_balance: self.ecosystem_pool.amount,
+            rewards_balance: self.rewards_pool.amount,
+        });
+        msg!("Rebalanced {} NEPTU {:?}", amount, direction);
+        Ok(())
+    }
+}
+
+/// Record a treasury config proposal with the proposer's approval
+fn open_config_proposal(
+    accounts: &mut ProposeTreasuryConfig,
+    mint: Option<Pubkey>,
+    approvers: Vec<Pubkey>,
+    threshold: u8,
+    spending_limit: u64,
+    period_duration: i64,
+) -> Result<()> {
+    let vault = &mut accounts.treasury_vault;
+    let proposer = accounts.proposer.key();
+    let index = vault.approver_index(&proposer)?;
+
+    let proposal = &mut accounts.proposal;
+    proposal.id = vault.config_proposal_count;
+    proposal.proposer = proposer;
+    proposal.mint = mint;
+    proposal.approvers = approvers;
+    proposal.threshold = threshold;
+    proposal.spending_limit = spending_limit;
+    proposal.period_duration = period_duration;
+    proposal.approvals = 1 << index;
+    proposal.config_version = vault.config_version;
+    proposal.created_at = Clock::get()?.unix_timestamp;
+    proposal.executed_at = 0;
+
+    vault.config_proposal_count = vault
+        .config_proposal_count
+        .checked_add(1)
+        .ok_or(NeptuError::Overflow)?;
+
+    msg!("Treasury config change {} proposed", proposal.id);
+    Ok(())
+}
+
+/// Record a withdrawal proposal with the proposer's approval
+fn open_withdrawal(
+    accounts: &mut ProposeWithdrawal,
+    mint: Option<Pubkey>,
+    recipient: Pubkey,
+    amount: u64,
+    memo: [u8; 32],
+) -> Result<()> {
+    require!(amount > 0, NeptuError::InvalidAmount);
+
+    let vault = &mut accounts.treasury_vault;
+    let proposer = accounts.proposer.key();
+    let index = vault.approver_index(&proposer)?;
+
+    let proposal = &mut accounts.withdrawal;
+    proposal.id = vault.withdrawal_count;
+    proposal.proposer = proposer;
+    proposal.recipient = recipient;
+    proposal.amount = amount;
+    proposal.memo = memo;
+    proposal.approvals = 1 << index;
+    proposal.config_version = vault.config_version;
+    proposal.created_at = Clock::get()?.unix_timestamp;
+    proposal.executed_at = 0;
+    proposal.mint = mint;
+
+    vault.withdrawal_count = vault
+        .withdrawal_count
+        .checked_add(1)
+        .ok_or(NeptuError::Overflow)?;
+
+    emit!(WithdrawalProposed {
+        id: proposal.id,
+        proposer,
+        recipient,
+        amount,
+        memo,
+        mint,
+    });
+    Ok(())
+}
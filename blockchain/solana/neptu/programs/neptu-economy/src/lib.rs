@@ -1,40 +1,19 @@
 use anchor_lang::prelude::*;
-use anchor_lang::system_program;
-use anchor_spl::{
-    associated_token::AssociatedToken,
-    token::{burn, transfer, Burn, Mint, Token, TokenAccount, Transfer},
-};
 
-declare_id!("6Zxc4uCXKqWS6spnW7u9wA81PChgws6wbGAKJyi8PnvT");
-
-/// Reading type for pricing
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
-pub enum ReadingType {
-    Potensi,       // Personality potential reading
-    Peluang,       // Daily opportunity reading
-    AiChat,        // AI chat consultation
-    Compatibility, // Compatibility reading
-}
-
-/// Constants
-pub const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
-pub const NEPTU_DECIMALS: u64 = 1_000_000; // 6 decimals
+pub mod constants;
+pub mod errors;
+pub mod events;
+mod helpers;
+pub mod instructions;
+pub mod state;
 
-/// Burn rate: 50% = 5000 basis points
-pub const BURN_RATE_BPS: u64 = 5000;
+pub use constants::*;
+pub use errors::*;
+pub use events::*;
+pub use instructions::*;
+pub use state::*;
 
-/// Default pricing (used for initialization)
-pub mod defaults {
-    pub const POTENSI_SOL: u64 = 10_000_000; // 0.01 SOL
-    pub const PELUANG_SOL: u64 = 1_000_000; // 0.001 SOL
-    pub const AI_CHAT_SOL: u64 = 2_000_000; // 0.002 SOL
-    pub const COMPATIBILITY_SOL: u64 = 5_000_000; // 0.005 SOL
-
-    pub const POTENSI_NEPTU: u64 = 10_000_000; // 10 NEPTU
-    pub const PELUANG_NEPTU: u64 = 1_000_000; // 1 NEPTU
-    pub const AI_CHAT_NEPTU: u64 = 2_000_000; // 2 NEPTU
-    pub const COMPATIBILITY_NEPTU: u64 = 5_000_000; // 5 NEPTU
-}
+declare_id!("6Zxc4uCXKqWS6spnW7u9wA81PChgws6wbGAKJyi8PnvT");
 
 #[program]
 pub mod neptu_economy {
@@ -42,22 +21,11 @@ pub mod neptu_economy {
 
     /// Initialize pricing config with default values
     pub fn initialize_pricing(ctx: Context<InitializePricing>) -> Result<()> {
-        msg!("Initializing Pricing Config");
-        let config = &mut ctx.accounts.pricing_config;
-        config.authority = ctx.accounts.authority.key();
-        config.potensi_sol_price = defaults::POTENSI_SOL;
-        config.peluang_sol_price = defaults::PELUANG_SOL;
-        config.ai_chat_sol_price = defaults::AI_CHAT_SOL;
-        config.compatibility_sol_price = defaults::COMPATIBILITY_SOL;
-        config.potensi_neptu_price = defaults::POTENSI_NEPTU;
-        config.peluang_neptu_price = defaults::PELUANG_NEPTU;
-        config.ai_chat_neptu_price = defaults::AI_CHAT_NEPTU;
-        config.compatibility_neptu_price = defaults::COMPATIBILITY_NEPTU;
-        msg!("Pricing config initialized");
-        Ok(())
-    }
-
-    /// Update pricing (admin only)
+        instructions::initialize_pricing(ctx)
+    }
+
+    /// Update pricing (pricing manager)
+    #[allow(clippy::too_many_arguments)]
     pub fn update_pricing(
         ctx: Context<UpdatePricing>,
         potensi_sol: Option<u64>,
@@ -69,443 +37,714 @@ pub mod neptu_economy {
         ai_chat_neptu: Option<u64>,
         compatibility_neptu: Option<u64>,
     ) -> Result<()> {
-        let config = &mut ctx.accounts.pricing_config;
-
-        if let Some(v) = potensi_sol {
-            config.potensi_sol_price = v;
-        }
-        if let Some(v) = peluang_sol {
-            config.peluang_sol_price = v;
-        }
-        if let Some(v) = ai_chat_sol {
-            config.ai_chat_sol_price = v;
-        }
-        if let Some(v) = compatibility_sol {
-            config.compatibility_sol_price = v;
-        }
-        if let Some(v) = potensi_neptu {
-            config.potensi_neptu_price = v;
-        }
-        if let Some(v) = peluang_neptu {
-            config.peluang_neptu_price = v;
-        }
-        if let Some(v) = ai_chat_neptu {
-            config.ai_chat_neptu_price = v;
-        }
-        if let Some(v) = compatibility_neptu {
-            config.compatibility_neptu_price = v;
-        }
-
-        msg!("Pricing updated");
-        Ok(())
+        instructions::update_pricing(
+            ctx,
+            potensi_sol,
+            peluang_sol,
+            ai_chat_sol,
+            compatibility_sol,
+            potensi_neptu,
+            peluang_neptu,
+            ai_chat_neptu,
+            compatibility_neptu,
+        )
     }
 
     /// Pay with SOL to get a reading - receives NEPTU reward
-    /// User pays SOL fee, SOL goes to treasury, NEPTU transferred from rewards pool
+    /// Stake and loyalty discounts apply; the reward follows the curve and emission budget
     pub fn pay_with_sol(ctx: Context<PayWithSol>, reading_type: ReadingType) -> Result<()> {
-        let config = &ctx.accounts.pricing_config;
-        let sol_price = config.get_sol_price(&reading_type);
-        let neptu_reward = config.get_neptu_price(&reading_type);
-
-        msg!(
-            "Pay with SOL: {} lamports for {:?}, reward: {} NEPTU",
-            sol_price,
-            reading_type,
-            neptu_reward
-        );
-
-        // Transfer SOL from user to treasury
-        system_program::transfer(
-            CpiContext::new(
-                ctx.accounts.system_program.to_account_info(),
-                system_program::Transfer {
-                    from: ctx.accounts.user.to_account_info(),
-                    to: ctx.accounts.treasury.to_account_info(),
-                },
-            ),
-            sol_price,
-        )?;
-        msg!("Transferred {} lamports to treasury", sol_price);
-
-        // Transfer NEPTU reward from rewards pool to user (NOT minting)
-        let seeds = &[b"economy".as_ref(), &[ctx.bumps.economy_authority]];
-        let signer_seeds = &[&seeds[..]];
-
-        transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                Transfer {
-                    from: ctx.accounts.rewards_pool.to_account_info(),
-                    to: ctx.accounts.user_neptu_account.to_account_info(),
-                    authority: ctx.accounts.economy_authority.to_account_info(),
-                },
-                signer_seeds,
-            ),
-            neptu_reward,
-        )?;
-        msg!("Transferred {} NEPTU reward to user", neptu_reward);
-
-        Ok(())
+        instructions::pay_with_sol(ctx, reading_type)
     }
 
     /// Pay with NEPTU for a reading - 50% burned, 50% recycled
-    /// User pays fee, NEPTU burned and recycled
+    /// Stakers get their tier's discount and the yield share of the recycled amount
     pub fn pay_with_neptu(ctx: Context<PayWithNeptu>, reading_type: ReadingType) -> Result<()> {
-        let config = &ctx.accounts.pricing_config;
-        let neptu_price = config.get_neptu_price(&reading_type);
-        let burn_amount = (neptu_price * BURN_RATE_BPS) / 10000;
-        let recycle_amount = neptu_price - burn_amount;
-
-        msg!(
-            "Pay with NEPTU: {} total, {} burned, {} recycled",
-            neptu_price,
-            burn_amount,
-            recycle_amount
-        );
-
-        // Burn 50% of NEPTU
-        burn(
-            CpiContext::new(
-                ctx.accounts.token_program.to_account_info(),
-                Burn {
-                    mint: ctx.accounts.neptu_mint.to_account_info(),
-                    from: ctx.accounts.user_neptu_account.to_account_info(),
-                    authority: ctx.accounts.user.to_account_info(),
-                },
-            ),
-            burn_amount,
-        )?;
-        msg!("Burned {} NEPTU", burn_amount);
-
-        // Transfer 50% to ecosystem pool (recycled for future rewards)
-        transfer(
-            CpiContext::new(
-                ctx.accounts.token_program.to_account_info(),
-                Transfer {
-                    from: ctx.accounts.user_neptu_account.to_account_info(),
-                    to: ctx.accounts.ecosystem_pool.to_account_info(),
-                    authority: ctx.accounts.user.to_account_info(),
-                },
-            ),
-            recycle_amount,
-        )?;
-        msg!("Recycled {} NEPTU to ecosystem pool", recycle_amount);
-
-        Ok(())
+        instructions::pay_with_neptu(ctx, reading_type)
     }
 
     /// Claim accumulated gamification rewards
-    /// User initiates, backend signs authorization, NEPTU transferred from rewards pool
+    /// Backend-signed authorization with an expiry; nonces may land out of order
     pub fn claim_rewards(
         ctx: Context<ClaimRewards>,
         amount: u64,
         nonce: u64,
+        expires_at: i64,
         _signature: [u8; 64],
     ) -> Result<()> {
-        // In production, verify the Ed25519 signature from backend
-        // The signature proves user earned this amount
-        // For now, the backend co-signs the transaction as authority
-
-        msg!("Claiming {} NEPTU rewards, nonce: {}", amount, nonce);
-
-        require!(amount > 0, NeptuError::InvalidAmount);
-
-        // Verify nonce hasn't been used (prevent replay)
-        let claim_record = &mut ctx.accounts.claim_record;
-        require!(
-            claim_record.last_nonce < nonce,
-            NeptuError::NonceAlreadyUsed
-        );
-        claim_record.last_nonce = nonce;
-        claim_record.total_claimed = claim_record
-            .total_claimed
-            .checked_add(amount)
-            .ok_or(NeptuError::Overflow)?;
-
-        // Transfer NEPTU from rewards pool to user (NOT minting)
-        let seeds = &[b"economy".as_ref(), &[ctx.bumps.economy_authority]];
-        let signer_seeds = &[&seeds[..]];
-
-        transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                Transfer {
-                    from: ctx.accounts.rewards_pool.to_account_info(),
-                    to: ctx.accounts.user_neptu_account.to_account_info(),
-                    authority: ctx.accounts.economy_authority.to_account_info(),
-                },
-                signer_seeds,
-            ),
-            amount,
-        )?;
-        msg!("Transferred {} NEPTU from rewards pool to user", amount);
-
-        Ok(())
+        instructions::claim_rewards(ctx, amount, nonce, expires_at, _signature)
     }
 
     /// Initialize the economy program state
     pub fn initialize_economy(ctx: Context<InitializeEconomy>) -> Result<()> {
-        msg!("Initializing NEPTU Economy");
-        let state = &mut ctx.accounts.economy_state;
-        state.authority = ctx.accounts.authority.key();
-        state.neptu_mint = ctx.accounts.neptu_mint.key();
-        state.treasury = ctx.accounts.treasury.key();
-        state.ecosystem_pool = ctx.accounts.ecosystem_pool.key();
-        state.total_sol_collected = 0;
-        state.total_neptu_burned = 0;
-        state.total_neptu_rewarded = 0;
-        msg!("Economy initialized");
-        Ok(())
+        instructions::initialize_economy(ctx)
     }
-}
 
-/// Pricing configuration (admin-managed)
-#[account]
-pub struct PricingConfig {
-    pub authority: Pubkey,
-    pub potensi_sol_price: u64,
-    pub peluang_sol_price: u64,
-    pub ai_chat_sol_price: u64,
-    pub compatibility_sol_price: u64,
-    pub potensi_neptu_price: u64,
-    pub peluang_neptu_price: u64,
-    pub ai_chat_neptu_price: u64,
-    pub compatibility_neptu_price: u64,
-}
+    /// Update per-epoch emission budget (admin only)
+    pub fn update_emission_budget(
+        ctx: Context<UpdateEconomyConfig>,
+        epoch_duration: Option<i64>,
+        global_cap: Option<u64>,
+        user_cap: Option<u64>,
+    ) -> Result<()> {
+        instructions::update_emission_budget(ctx, epoch_duration, global_cap, user_cap)
+    }
 
-impl PricingConfig {
-    pub const SIZE: usize = 8 + 32 + (8 * 8); // discriminator + authority + 8 u64 prices
+    /// Hand economy and pricing administration to a new authority (admin only)
+    /// Typically the multisig signer PDA
+    pub fn set_authority(ctx: Context<SetAuthority>, new_authority: Pubkey) -> Result<()> {
+        instructions::set_authority(ctx, new_authority)
+    }
 
-    pub fn get_sol_price(&self, reading_type: &ReadingType) -> u64 {
-        match reading_type {
-            ReadingType::Potensi => self.potensi_sol_price,
-            ReadingType::Peluang => self.peluang_sol_price,
-            ReadingType::AiChat => self.ai_chat_sol_price,
-            ReadingType::Compatibility => self.compatibility_sol_price,
-        }
+    /// Create the roles registry (admin only)
+    pub fn initialize_roles(_ctx: Context<InitializeRoles>) -> Result<()> {
+        instructions::initialize_roles(_ctx)
     }
 
-    pub fn get_neptu_price(&self, reading_type: &ReadingType) -> u64 {
-        match reading_type {
-            ReadingType::Potensi => self.potensi_neptu_price,
-            ReadingType::Peluang => self.peluang_neptu_price,
-            ReadingType::AiChat => self.ai_chat_neptu_price,
-            ReadingType::Compatibility => self.compatibility_neptu_price,
-        }
+    /// Grant a role to a wallet (admin only)
+    pub fn grant_role(ctx: Context<UpdateRoles>, member: Pubkey, role: Role) -> Result<()> {
+        instructions::grant_role(ctx, member, role)
     }
-}
 
-/// Economy program state
-#[account]
-pub struct EconomyState {
-    pub authority: Pubkey,
-    pub neptu_mint: Pubkey,
-    pub treasury: Pubkey,
-    pub ecosystem_pool: Pubkey,
-    pub total_sol_collected: u64,
-    pub total_neptu_burned: u64,
-    pub total_neptu_rewarded: u64,
-}
+    /// Revoke a role from a wallet (admin only)
+    pub fn revoke_role(ctx: Context<UpdateRoles>, member: Pubkey, role: Role) -> Result<()> {
+        instructions::revoke_role(ctx, member, role)
+    }
 
-/// User claim record to prevent replay attacks
-#[account]
-pub struct ClaimRecord {
-    pub user: Pubkey,
-    pub last_nonce: u64,
-    pub total_claimed: u64,
-}
+    /// Set the backend key that co-signs reward claims (claim signer admin)
+    pub fn set_claim_signer(ctx: Context<SetClaimSigner>, claim_signer: Pubkey) -> Result<()> {
+        instructions::set_claim_signer(ctx, claim_signer)
+    }
 
-#[derive(Accounts)]
-pub struct InitializeEconomy<'info> {
-    #[account(mut)]
-    pub authority: Signer<'info>,
+    /// Set the backend key that attests reading delivery (claim signer admin)
+    pub fn set_fulfillment_signer(
+        ctx: Context<SetClaimSigner>,
+        fulfillment_signer: Pubkey,
+    ) -> Result<()> {
+        instructions::set_fulfillment_signer(ctx, fulfillment_signer)
+    }
 
-    #[account(
-        init,
-        seeds = [b"economy_state"],
-        bump,
-        payer = authority,
-        space = 8 + 32 + 32 + 32 + 32 + 8 + 8 + 8,
-    )]
-    pub economy_state: Account<'info, EconomyState>,
+    /// Pause or resume payments, claims and outgoing transfers (pauser)
+    pub fn set_paused(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+        instructions::set_paused(ctx, paused)
+    }
 
-    /// CHECK: Economy authority PDA
-    #[account(
-        seeds = [b"economy"],
-        bump,
-    )]
-    pub economy_authority: UncheckedAccount<'info>,
+    /// Create the built-in multisig (admin only)
+    /// Its signer PDA can then be made authority of any admin instruction
+    pub fn create_multisig(
+        ctx: Context<CreateMultisig>,
+        members: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        instructions::create_multisig(ctx, members, threshold)
+    }
 
-    pub neptu_mint: Account<'info, Mint>,
+    /// Change multisig members/threshold; only callable through an executed proposal
+    /// Bumps the nonce so pending proposals must be re-created
+    pub fn set_multisig_members(
+        ctx: Context<SetMultisigMembers>,
+        members: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        instructions::set_multisig_members(ctx, members, threshold)
+    }
 
-    /// CHECK: Treasury wallet
-    pub treasury: UncheckedAccount<'info>,
+    /// Propose an instruction for the multisig signer to execute (member only)
+    /// The proposer's approval is recorded immediately
+    pub fn propose_multisig_transaction(
+        ctx: Context<ProposeMultisigTransaction>,
+        program_id: Pubkey,
+        accounts: Vec<ProposalAccountMeta>,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        instructions::propose_multisig_transaction(ctx, program_id, accounts, data)
+    }
 
-    pub ecosystem_pool: Account<'info, TokenAccount>,
+    /// Approve a pending multisig proposal (member only)
+    pub fn approve_multisig_transaction(ctx: Context<ApproveMultisigTransaction>) -> Result<()> {
+        instructions::approve_multisig_transaction(ctx)
+    }
 
-    pub system_program: Program<'info, System>,
-}
+    /// Execute an approved multisig proposal, signing as the multisig signer PDA
+    /// Remaining accounts: every account referenced by the proposal plus the target program
+    pub fn execute_multisig_transaction<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteMultisigTransaction<'info>>,
+    ) -> Result<()> {
+        instructions::execute_multisig_transaction(ctx)
+    }
 
-#[derive(Accounts)]
-pub struct InitializePricing<'info> {
-    #[account(mut)]
-    pub authority: Signer<'info>,
-
-    #[account(
-        init,
-        seeds = [b"pricing_config"],
-        bump,
-        payer = authority,
-        space = PricingConfig::SIZE,
-    )]
-    pub pricing_config: Account<'info, PricingConfig>,
-
-    pub system_program: Program<'info, System>,
-}
+    /// Create the program-owned treasury vault that receives SOL payments (admin only)
+    pub fn initialize_treasury_vault(
+        ctx: Context<InitializeTreasuryVault>,
+        approvers: Vec<Pubkey>,
+        threshold: u8,
+        spending_limit: u64,
+        period_duration: i64,
+    ) -> Result<()> {
+        instructions::initialize_treasury_vault(
+            ctx,
+            approvers,
+            threshold,
+            spending_limit,
+            period_duration,
+        )
+    }
 
-#[derive(Accounts)]
-pub struct UpdatePricing<'info> {
-    #[account(
-        constraint = authority.key() == pricing_config.authority @ NeptuError::Unauthorized
-    )]
-    pub authority: Signer<'info>,
-
-    #[account(
-        mut,
-        seeds = [b"pricing_config"],
-        bump,
-    )]
-    pub pricing_config: Account<'info, PricingConfig>,
-}
+    /// Propose new treasury approvers and spending limits (approver only)
+    /// Takes effect through `execute_treasury_config` once M-of-N approvers agree
+    pub fn propose_treasury_config(
+        ctx: Context<ProposeTreasuryConfig>,
+        approvers: Vec<Pubkey>,
+        threshold: u8,
+        spending_limit: u64,
+        period_duration: i64,
+    ) -> Result<()> {
+        instructions::propose_treasury_config(
+            ctx,
+            approvers,
+            threshold,
+            spending_limit,
+            period_duration,
+        )
+    }
 
-#[derive(Accounts)]
-pub struct PayWithSol<'info> {
-    #[account(mut)]
-    pub user: Signer<'info>,
-
-    #[account(
-        seeds = [b"pricing_config"],
-        bump,
-    )]
-    pub pricing_config: Account<'info, PricingConfig>,
-
-    /// CHECK: Treasury wallet receives SOL
-    #[account(mut)]
-    pub treasury: UncheckedAccount<'info>,
-
-    #[account(mut)]
-    pub neptu_mint: Account<'info, Mint>,
-
-    #[account(
-        init_if_needed,
-        payer = user,
-        associated_token::mint = neptu_mint,
-        associated_token::authority = user,
-    )]
-    pub user_neptu_account: Account<'info, TokenAccount>,
-
-    /// Rewards pool: ATA owned by economy_authority PDA
-    #[account(
-        mut,
-        associated_token::mint = neptu_mint,
-        associated_token::authority = economy_authority,
-    )]
-    pub rewards_pool: Account<'info, TokenAccount>,
-
-    /// CHECK: Economy authority PDA (rewards pool owner)
-    #[account(
-        seeds = [b"economy"],
-        bump,
-    )]
-    pub economy_authority: UncheckedAccount<'info>,
-
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub system_program: Program<'info, System>,
-}
+    /// Propose a new per-period withdrawal limit for a payment mint (approver only)
+    pub fn propose_token_spending_limit(
+        ctx: Context<ProposeTreasuryConfig>,
+        mint: Pubkey,
+        spending_limit: u64,
+    ) -> Result<()> {
+        instructions::propose_token_spending_limit(ctx, mint, spending_limit)
+    }
 
-#[derive(Accounts)]
-pub struct PayWithNeptu<'info> {
-    #[account(mut)]
-    pub user: Signer<'info>,
+    /// Approve a pending treasury config change (approver only)
+    pub fn approve_treasury_config(ctx: Context<ApproveTreasuryConfig>) -> Result<()> {
+        instructions::approve_treasury_config(ctx)
+    }
 
-    #[account(
-        seeds = [b"pricing_config"],
-        bump,
-    )]
-    pub pricing_config: Account<'info, PricingConfig>,
+    /// Apply an approved treasury config change (approver only)
+    /// Bumps the config version, so every pending proposal must be re-created
+    pub fn execute_treasury_config(ctx: Context<ExecuteTreasuryConfig>) -> Result<()> {
+        instructions::execute_treasury_config(ctx)
+    }
 
-    #[account(mut)]
-    pub neptu_mint: Account<'info, Mint>,
+    /// Propose a SOL withdrawal from the treasury vault (approver only)
+    /// The proposer's approval is recorded immediately
+    pub fn propose_withdrawal(
+        ctx: Context<ProposeWithdrawal>,
+        recipient: Pubkey,
+        amount: u64,
+        memo: [u8; 32],
+    ) -> Result<()> {
+        instructions::propose_withdrawal(ctx, recipient, amount, memo)
+    }
 
-    #[account(
-        mut,
-        associated_token::mint = neptu_mint,
-        associated_token::authority = user,
-    )]
-    pub user_neptu_account: Account<'info, TokenAccount>,
+    /// Propose a withdrawal from a treasury token vault (approver only)
+    /// `recipient` is the token account receiving the tokens
+    pub fn propose_token_withdrawal(
+        ctx: Context<ProposeWithdrawal>,
+        mint: Pubkey,
+        recipient: Pubkey,
+        amount: u64,
+        memo: [u8; 32],
+    ) -> Result<()> {
+        instructions::propose_token_withdrawal(ctx, mint, recipient, amount, memo)
+    }
 
-    #[account(mut)]
-    pub ecosystem_pool: Account<'info, TokenAccount>,
+    /// Approve a pending treasury withdrawal (approver only)
+    pub fn approve_withdrawal(ctx: Context<ApproveWithdrawal>) -> Result<()> {
+        instructions::approve_withdrawal(ctx)
+    }
 
-    pub token_program: Program<'info, Token>,
-}
+    /// Execute a withdrawal once it has enough approvals and fits the period spending limit
+    pub fn execute_withdrawal(ctx: Context<ExecuteWithdrawal>) -> Result<()> {
+        instructions::execute_withdrawal(ctx)
+    }
 
-#[derive(Accounts)]
-pub struct ClaimRewards<'info> {
-    #[account(mut)]
-    pub user: Signer<'info>,
-
-    #[account(
-        init_if_needed,
-        seeds = [b"claim", user.key().as_ref()],
-        bump,
-        payer = user,
-        space = 8 + 32 + 8 + 8,
-    )]
-    pub claim_record: Account<'info, ClaimRecord>,
-
-    #[account(mut)]
-    pub neptu_mint: Account<'info, Mint>,
-
-    #[account(
-        init_if_needed,
-        payer = user,
-        associated_token::mint = neptu_mint,
-        associated_token::authority = user,
-    )]
-    pub user_neptu_account: Account<'info, TokenAccount>,
-
-    /// Rewards pool: ATA owned by economy_authority PDA
-    #[account(
-        mut,
-        associated_token::mint = neptu_mint,
-        associated_token::authority = economy_authority,
-    )]
-    pub rewards_pool: Account<'info, TokenAccount>,
-
-    /// CHECK: Economy authority PDA (rewards pool owner)
-    #[account(
-        seeds = [b"economy"],
-        bump,
-    )]
-    pub economy_authority: UncheckedAccount<'info>,
-
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub system_program: Program<'info, System>,
-}
+    /// Create the program-owned ecosystem vault and route recycled NEPTU to it (admin only)
+    /// Lets recycled NEPTU be moved back into the rewards pool by `rebalance_pools`
+    pub fn initialize_ecosystem_vault(ctx: Context<InitializeEcosystemVault>) -> Result<()> {
+        instructions::initialize_ecosystem_vault(ctx)
+    }
+
+    /// Update the ecosystem -> rewards rebalance policy (treasurer)
+    /// The crank may move `bps` of the ecosystem balance above `threshold`
+    pub fn update_rebalance_policy(
+        ctx: Context<UpdateRebalancePolicy>,
+        threshold: Option<u64>,
+        bps: Option<u16>,
+    ) -> Result<()> {
+        instructions::update_rebalance_policy(ctx, threshold, bps)
+    }
+
+    /// Move NEPTU between the ecosystem vault and the rewards pool (treasurer)
+    pub fn rebalance_pools(
+        ctx: Context<RebalancePools>,
+        direction: RebalanceDirection,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::rebalance_pools(ctx, direction, amount)
+    }
+
+    /// Permissionless crank: feed recycled NEPTU back into the rewards pool
+    /// once the ecosystem vault exceeds the configured threshold
+    pub fn crank_rebalance(ctx: Context<RebalancePools>) -> Result<()> {
+        instructions::crank_rebalance(ctx)
+    }
+
+    /// Update how rewards are handled when the rewards pool is short (admin only)
+    pub fn update_shortfall_policy(
+        ctx: Context<UpdateEconomyConfig>,
+        policy: RewardShortfallPolicy,
+    ) -> Result<()> {
+        instructions::update_shortfall_policy(ctx, policy)
+    }
+
+    /// Claim NEPTU owed from earlier payments once the rewards pool is refilled
+    /// Pays as much of the IOU as the pool currently holds
+    pub fn claim_pending_reward(ctx: Context<ClaimPendingReward>) -> Result<()> {
+        instructions::claim_pending_reward(ctx)
+    }
+
+    /// Update the reward emission curve (admin only)
+    pub fn update_reward_curve(
+        ctx: Context<UpdateEconomyConfig>,
+        curve: RewardCurve,
+    ) -> Result<()> {
+        instructions::update_reward_curve(ctx, curve)
+    }
+
+    /// View: current reward rate and per-reading SOL payment rewards
+    pub fn get_reward_rate(ctx: Context<GetRewardRate>) -> Result<RewardRate> {
+        instructions::get_reward_rate(ctx)
+    }
+
+    /// Create the NEPTU-holder governance config and vote vault (admin only)
+    pub fn initialize_governance(
+        ctx: Context<InitializeGovernance>,
+        quorum: u64,
+        proposal_threshold: u64,
+        voting_period: i64,
+        execution_delay: i64,
+    ) -> Result<()> {
+        instructions::initialize_governance(
+            ctx,
+            quorum,
+            proposal_threshold,
+            voting_period,
+            execution_delay,
+        )
+    }
+
+    /// Propose an economic parameter change (NEPTU holders above the proposal threshold)
+    pub fn create_governance_proposal(
+        ctx: Context<CreateGovernanceProposal>,
+        change: ParameterChange,
+    ) -> Result<()> {
+        instructions::create_governance_proposal(ctx, change)
+    }
+
+    /// Vote by locking NEPTU in the vote vault until voting ends
+    pub fn cast_vote(ctx: Context<CastVote>, support: bool, amount: u64) -> Result<()> {
+        instructions::cast_vote(ctx, support, amount)
+    }
+
+    /// Return locked NEPTU once voting has ended
+    pub fn withdraw_vote(ctx: Context<WithdrawVote>) -> Result<()> {
+        instructions::withdraw_vote(ctx)
+    }
+
+    /// Apply a passed proposal after its execution delay (permissionless)
+    pub fn execute_governance_proposal(ctx: Context<ExecuteGovernanceProposal>) -> Result<()> {
+        instructions::execute_governance_proposal(ctx)
+    }
+
+    /// Create the staking pool and its vault (admin only)
+    pub fn initialize_staking(
+        ctx: Context<InitializeStaking>,
+        tiers: Vec<StakeTier>,
+        early_unstake_penalty_bps: u16,
+    ) -> Result<()> {
+        instructions::initialize_staking(ctx, tiers, early_unstake_penalty_bps)
+    }
+
+    /// Replace staking tiers and early unstake penalty (admin only)
+    pub fn update_staking_tiers(
+        ctx: Context<UpdateStakingTiers>,
+        tiers: Vec<StakeTier>,
+        early_unstake_penalty_bps: u16,
+    ) -> Result<()> {
+        instructions::update_staking_tiers(ctx, tiers, early_unstake_penalty_bps)
+    }
+
+    /// Set the share of recycled NEPTU payments paid to stakers (admin only)
+    pub fn update_staking_yield(ctx: Context<UpdateStakingTiers>, yield_bps: u16) -> Result<()> {
+        instructions::update_staking_yield(ctx, yield_bps)
+    }
+
+    /// Claim accrued staking yield
+    pub fn claim_stake_yield(ctx: Context<ClaimStakeYield>) -> Result<()> {
+        instructions::claim_stake_yield(ctx)
+    }
+
+    /// Lock NEPTU for at least `lock_duration` seconds
+    /// Adding to a position never shortens its existing lock
+    pub fn stake(ctx: Context<Stake>, amount: u64, lock_duration: i64) -> Result<()> {
+        instructions::stake(ctx, amount, lock_duration)
+    }
+
+    /// Withdraw staked NEPTU; unstaking before the lock ends burns a penalty
+    pub fn unstake(ctx: Context<Unstake>, amount: u64) -> Result<()> {
+        instructions::unstake(ctx, amount)
+    }
+
+    /// Register as a referrer; payers can then be attributed to the caller
+    pub fn register_referrer(ctx: Context<RegisterReferrer>) -> Result<()> {
+        instructions::register_referrer(ctx)
+    }
+
+    /// Update referral bonus rate and per-referrer lifetime cap (admin only)
+    pub fn update_referral_policy(
+        ctx: Context<UpdateEconomyConfig>,
+        bonus_bps: Option<u16>,
+        referrer_cap: Option<u64>,
+    ) -> Result<()> {
+        instructions::update_referral_policy(ctx, bonus_bps, referrer_cap)
+    }
+
+    /// Create or replace loyalty tiers (pricing manager)
+    pub fn set_loyalty_tiers(ctx: Context<SetLoyaltyTiers>, tiers: Vec<LoyaltyTier>) -> Result<()> {
+        instructions::set_loyalty_tiers(ctx, tiers)
+    }
+
+    /// Gift a reading paid in SOL to another wallet
+    /// The beneficiary gets the receipt, and the reward when `reward_to_beneficiary` is set
+    pub fn gift_with_sol(
+        ctx: Context<GiftWithSol>,
+        reading_type: ReadingType,
+        reward_to_beneficiary: bool,
+        message_hash: Option<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::gift_with_sol(ctx, reading_type, reward_to_beneficiary, message_hash)
+    }
+
+    /// Gift a reading paid in NEPTU to another wallet (burned and recycled as for `pay_with_neptu`)
+    pub fn gift_with_neptu(
+        ctx: Context<GiftWithNeptu>,
+        reading_type: ReadingType,
+        message_hash: Option<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::gift_with_neptu(ctx, reading_type, message_hash)
+    }
+
+    /// Open a two-party compatibility reading; the initiator deposits their share of the SOL price
+    pub fn open_compatibility_request(
+        ctx: Context<OpenCompatibilityRequest>,
+        request_id: u64,
+        initiator_share_bps: u16,
+    ) -> Result<()> {
+        instructions::open_compatibility_request(ctx, request_id, initiator_share_bps)
+    }
+
+    /// Partner accepts and pays the remaining share
+    /// Both shares go to the treasury vault and each party gets a receipt
+    pub fn accept_compatibility_request(ctx: Context<AcceptCompatibilityRequest>) -> Result<()> {
+        instructions::accept_compatibility_request(ctx)
+    }
+
+    /// Partner declines, or initiator withdraws, a pending request
+    /// The initiator's deposit is refunded and the rent goes back to whoever paid it
+    pub fn close_compatibility_request(ctx: Context<CloseCompatibilityRequest>) -> Result<()> {
+        instructions::close_compatibility_request(ctx)
+    }
 
-#[error_code]
-pub enum NeptuError {
-    #[msg("Invalid amount")]
-    InvalidAmount,
-    #[msg("Nonce already used")]
-    NonceAlreadyUsed,
-    #[msg("Arithmetic overflow")]
-    Overflow,
-    #[msg("Insufficient balance")]
-    InsufficientBalance,
-    #[msg("Unauthorized")]
-    Unauthorized,
+    /// Set how long escrowed orders wait for delivery before users may reclaim (admin only)
+    pub fn update_escrow_timeout(ctx: Context<UpdateEconomyConfig>, timeout: i64) -> Result<()> {
+        instructions::update_escrow_timeout(ctx, timeout)
+    }
+
+    /// Pay for a reading in SOL held in escrow by the order until delivery
+    pub fn create_sol_order(
+        ctx: Context<CreateSolOrder>,
+        order_id: u64,
+        reading_type: ReadingType,
+    ) -> Result<()> {
+        instructions::create_sol_order(ctx, order_id, reading_type)
+    }
+
+    /// Pay for a reading in NEPTU held in an order vault until delivery
+    /// Burn and recycle happen on fulfillment
+    pub fn create_neptu_order(
+        ctx: Context<CreateNeptuOrder>,
+        order_id: u64,
+        reading_type: ReadingType,
+    ) -> Result<()> {
+        instructions::create_neptu_order(ctx, order_id, reading_type)
+    }
+
+    /// Backend attests delivery of an escrowed reading
+    /// SOL is discounted and rewarded as in `pay_with_sol`; NEPTU is burned and recycled
+    pub fn fulfill_order(ctx: Context<FulfillOrder>) -> Result<()> {
+        instructions::fulfill_order(ctx)
+    }
+
+    /// Reclaim an undelivered escrowed order after its timeout
+    /// Escrowed funds go back to the user and the rent to whoever paid it
+    pub fn reclaim_order(ctx: Context<ReclaimOrder>) -> Result<()> {
+        instructions::reclaim_order(ctx)
+    }
+
+    /// Choose whether NEPTU refunds re-issue the burned part (admin only)
+    pub fn update_refund_policy(
+        ctx: Context<UpdateEconomyConfig>,
+        policy: RefundBurnPolicy,
+    ) -> Result<()> {
+        instructions::update_refund_policy(ctx, policy)
+    }
+
+    /// Refund a receipted payment to its payer (refunds role)
+    /// The reward holder co-signs to return the reward; referral bonuses are clawed back
+    pub fn refund_payment(ctx: Context<RefundPayment>) -> Result<()> {
+        instructions::refund_payment(ctx)
+    }
+
+    /// Accept an SPL token as payment, or update its prices (catalog manager)
+    pub fn set_payment_mint(
+        ctx: Context<SetPaymentMint>,
+        prices: [u64; 4],
+        enabled: bool,
+    ) -> Result<()> {
+        instructions::set_payment_mint(ctx, prices, enabled)
+    }
+
+    /// Pay for a reading with an allowlisted SPL token - receives NEPTU reward
+    /// Reward is limited as for `pay_with_sol`; an optional receipt makes it refundable
+    pub fn pay_with_token(ctx: Context<PayWithToken>, reading_type: ReadingType) -> Result<()> {
+        instructions::pay_with_token(ctx, reading_type)
+    }
+
+    /// Configure USD-pegged SOL prices (pricing manager)
+    /// A stale or uncertain Pyth `price_feed` falls back to the fixed lamport price
+    pub fn update_usd_pricing(
+        ctx: Context<UpdatePricing>,
+        usd_prices_cents: [u64; 4],
+        price_feed: Pubkey,
+        max_price_age: i64,
+        max_price_conf_bps: u16,
+    ) -> Result<()> {
+        instructions::update_usd_pricing(
+            ctx,
+            usd_prices_cents,
+            price_feed,
+            max_price_age,
+            max_price_conf_bps,
+        )
+    }
+
+    /// Create a writable SOL/USD price feed owned by the caller (`mock-price-feed` only)
+    /// Stands in for a Pyth price update on localnet
+    #[cfg(feature = "mock-price-feed")]
+    pub fn create_price_feed(ctx: Context<CreatePriceFeed>, expo: i32) -> Result<()> {
+        instructions::create_price_feed(ctx, expo)
+    }
+
+    /// Publish a new price to a mock feed (feed authority only)
+    #[cfg(feature = "mock-price-feed")]
+    pub fn update_price_feed(
+        ctx: Context<UpdatePriceFeed>,
+        price: i64,
+        conf: u64,
+        publish_time: i64,
+    ) -> Result<()> {
+        instructions::update_price_feed(ctx, price, conf, publish_time)
+    }
+
+    /// Pay for a reading partly in NEPTU and partly in SOL
+    /// Only the SOL share earns a reward; each share gets a receipt when both accounts are passed
+    pub fn pay_hybrid(
+        ctx: Context<PayHybrid>,
+        reading_type: ReadingType,
+        neptu_amount: u64,
+    ) -> Result<()> {
+        instructions::pay_hybrid(ctx, reading_type, neptu_amount)
+    }
+
+    /// Buy several readings with one SOL transfer and one reward transfer
+    /// Receipt PDAs in `remaining_accounts`, one per line item, make the items refundable
+    pub fn pay_batch_with_sol<'info>(
+        ctx: Context<'_, '_, 'info, 'info, PayBatchWithSol<'info>>,
+        items: Vec<LineItem>,
+    ) -> Result<()> {
+        instructions::pay_batch_with_sol(ctx, items)
+    }
+
+    /// Buy several readings with NEPTU in one burn and one recycle transfer
+    /// Receipt PDAs in `remaining_accounts`, one per line item, make the items refundable
+    pub fn pay_batch_with_neptu<'info>(
+        ctx: Context<'_, '_, 'info, 'info, PayBatchWithNeptu<'info>>,
+        items: Vec<LineItem>,
+    ) -> Result<()> {
+        instructions::pay_batch_with_neptu(ctx, items)
+    }
+
+    /// Replace bulk discount tiers for reading credits (pricing manager)
+    pub fn set_credit_discounts(
+        ctx: Context<SetCreditDiscounts>,
+        tiers: Vec<CreditDiscountTier>,
+    ) -> Result<()> {
+        instructions::set_credit_discounts(ctx, tiers)
+    }
+
+    /// Prepay `quantity` readings of one type in SOL or NEPTU
+    /// Payment is escrowed with the credits and accounted for as credits are consumed
+    pub fn buy_credits(
+        ctx: Context<BuyCredits>,
+        reading_type: ReadingType,
+        currency: PaymentCurrency,
+        quantity: u32,
+    ) -> Result<()> {
+        instructions::buy_credits(ctx, reading_type, currency, quantity)
+    }
+
+    /// Spend credits for delivered readings (fulfillment signer)
+    /// The escrow is then settled like individual payments
+    pub fn consume_credit(ctx: Context<ConsumeCredit>, quantity: u32) -> Result<()> {
+        instructions::consume_credit(ctx, quantity)
+    }
+
+    /// Set the notice period before unused metered deposits can be withdrawn (admin only)
+    pub fn update_metering_notice(ctx: Context<UpdateEconomyConfig>, period: i64) -> Result<()> {
+        instructions::update_metering_notice(ctx, period)
+    }
+
+    /// Cap the per-unit rate usage reports may charge metered deposits (pricing manager)
+    pub fn update_usage_rate_caps(
+        ctx: Context<UpdatePricing>,
+        max_sol_usage_rate: u64,
+        max_neptu_usage_rate: u64,
+    ) -> Result<()> {
+        instructions::update_usage_rate_caps(ctx, max_sol_usage_rate, max_neptu_usage_rate)
+    }
+
+    /// Deposit SOL or NEPTU to pay for metered AI chat / voice usage
+    /// A deposit cancels any pending withdrawal request
+    pub fn deposit_metered(
+        ctx: Context<DepositMetered>,
+        currency: PaymentCurrency,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::deposit_metered(ctx, currency, amount)
+    }
+
+    /// Settle a usage report against the user's metered deposit (fulfillment signer)
+    /// Usage beyond the deposit is reported as unpaid
+    pub fn settle_usage(
+        ctx: Context<SettleUsage>,
+        units: u64,
+        rate: u64,
+        period_start: i64,
+        period_end: i64,
+    ) -> Result<()> {
+        instructions::settle_usage(ctx, units, rate, period_start, period_end)
+    }
+
+    /// Start the notice period for withdrawing the unused metered deposit
+    /// Usage periods starting after the request can no longer be settled
+    pub fn request_metered_withdrawal(ctx: Context<RequestMeteredWithdrawal>) -> Result<()> {
+        instructions::request_metered_withdrawal(ctx)
+    }
+
+    /// Withdraw the unused metered deposit once the notice period has elapsed
+    pub fn withdraw_metered(ctx: Context<WithdrawMetered>) -> Result<()> {
+        instructions::withdraw_metered(ctx)
+    }
+
+    /// Authorize an ephemeral session key to pay on the owner's behalf
+    /// The SOL limit is deposited; the NEPTU limit is approved as a token delegation
+    pub fn create_session(
+        ctx: Context<CreateSession>,
+        session_key: Pubkey,
+        expires_at: i64,
+        allowed_instructions: u8,
+        allowed_readings: u8,
+        sol_limit: u64,
+        neptu_limit: u64,
+    ) -> Result<()> {
+        instructions::create_session(
+            ctx,
+            session_key,
+            expires_at,
+            allowed_instructions,
+            allowed_readings,
+            sol_limit,
+            neptu_limit,
+        )
+    }
+
+    /// Revoke a session key, returning unspent SOL and clearing the NEPTU delegation
+    /// The session rent goes back to whoever paid it
+    pub fn revoke_session(ctx: Context<RevokeSession>) -> Result<()> {
+        instructions::revoke_session(ctx)
+    }
+
+    /// Pay for a reading in SOL with a session key, drawing from the session deposit
+    /// Referral bonuses and receipts still require the owner's signature
+    pub fn session_pay_with_sol(
+        ctx: Context<SessionPayWithSol>,
+        reading_type: ReadingType,
+    ) -> Result<()> {
+        instructions::session_pay_with_sol(ctx, reading_type)
+    }
+
+    /// Pay for a reading in NEPTU with a session key, debiting the owner's account
+    /// through the session's delegation
+    pub fn session_pay_with_neptu(
+        ctx: Context<SessionPayWithNeptu>,
+        reading_type: ReadingType,
+    ) -> Result<()> {
+        instructions::session_pay_with_neptu(ctx, reading_type)
+    }
+
+    /// Configure the rent sponsorship vault: the fee payer it reimburses and the
+    /// per-user reimbursement limit (admin only)
+    pub fn configure_rent_sponsorship(
+        ctx: Context<ConfigureRentSponsorship>,
+        sponsor: Pubkey,
+        per_user_limit: u64,
+    ) -> Result<()> {
+        instructions::configure_rent_sponsorship(ctx, sponsor, per_user_limit)
+    }
+
+    /// Add lamports to the rent sponsorship vault
+    pub fn fund_rent_sponsorship(ctx: Context<FundRentSponsorship>, amount: u64) -> Result<()> {
+        instructions::fund_rent_sponsorship(ctx, amount)
+    }
+
+    /// Withdraw unused lamports from the rent sponsorship vault (admin only)
+    pub fn withdraw_rent_sponsorship(
+        ctx: Context<WithdrawRentSponsorship>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::withdraw_rent_sponsorship(ctx, amount)
+    }
+
+    /// Grow a v0 pricing config to the current layout (admin only)
+    /// Authority and prices carry over; newer fields get their initialization defaults
+    pub fn migrate_pricing_config(ctx: Context<MigratePricingConfig>) -> Result<()> {
+        instructions::migrate_pricing_config(ctx)
+    }
+
+    /// Grow a v0 economy state to the current layout (admin only)
+    /// Keys and totals carry over; newer fields get their initialization defaults
+    pub fn migrate_economy_state(ctx: Context<MigrateEconomyState>) -> Result<()> {
+        instructions::migrate_economy_state(ctx)
+    }
+
+    /// Grow the caller's v0 claim record to the current layout
+    /// Every nonce up to the old `last_nonce` stays consumed
+    pub fn migrate_claim_record(ctx: Context<MigrateClaimRecord>) -> Result<()> {
+        instructions::migrate_claim_record(ctx)
+    }
 }
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::*, errors::NeptuError};
+
+/// User claim record to prevent replay attacks
+#[account]
+pub struct ClaimRecord {
+    pub user: Pubkey,
+    /// Lowest nonce still inside the replay window
+    pub nonce_base: u64,
+    /// Bit `i` set = nonce `nonce_base + i` already claimed
+    pub nonce_bitmap: [u64; NONCE_WINDOW_WORDS],
+    pub total_claimed: u64,
+    /// Emission epoch the per-user budget below belongs to
+    pub epoch: u64,
+    pub epoch_emitted: u64,
+}
+
+impl ClaimRecord {
+    pub const SIZE: usize = 8 + 32 + 8 + (8 * NONCE_WINDOW_WORDS) + 8 + 8 + 8; // discriminator + user + base + bitmap + total + epoch budget
+
+    /// Mark a nonce as used, sliding the window forward when the nonce is past its end.
+    /// Nonces below the window are rejected since their state has been discarded.
+    pub fn consume_nonce(&mut self, nonce: u64) -> Result<()> {
+        require!(nonce >= self.nonce_base, NeptuError::NonceOutOfWindow);
+
+        let window_end = self
+            .nonce_base
+            .checked_add(NONCE_WINDOW)
+            .ok_or(NeptuError::Overflow)?;
+        if nonce >= window_end {
+            let shift = nonce - window_end + 1;
+            self.shift_window(shift);
+            self.nonce_base += shift;
+        }
+
+        let offset = nonce - self.nonce_base;
+        let word = (offset / 64) as usize;
+        let bit = 1u64 << (offset % 64);
+        require!(
+            self.nonce_bitmap[word] & bit == 0,
+            NeptuError::NonceAlreadyUsed
+        );
+        self.nonce_bitmap[word] |= bit;
+        Ok(())
+    }
+
+    /// Drop the lowest `shift` bits of the bitmap
+    fn shift_window(&mut self, shift: u64) {
+        if shift >= NONCE_WINDOW {
+            self.nonce_bitmap = [0; NONCE_WINDOW_WORDS];
+            return;
+        }
+        let word_shift = (shift / 64) as usize;
+        let bit_shift = (shift % 64) as u32;
+        for i in 0..NONCE_WINDOW_WORDS {
+            let src = i + word_shift;
+            let low = self.nonce_bitmap.get(src).copied().unwrap_or(0);
+            let high = self.nonce_bitmap.get(src + 1).copied().unwrap_or(0);
+            self.nonce_bitmap[i] = if bit_shift == 0 {
+                low
+            } else {
+                (low >> bit_shift) | (high << (64 - bit_shift))
+            };
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+/// Pending two-party compatibility reading; holds the initiator's deposit
+#[account]
+pub struct CompatibilityRequest {
+    pub initiator: Pubkey,
+    pub partner: Pubkey,
+    /// Chosen by the initiator; lets a pair open any number of requests
+    pub request_id: u64,
+    /// Compatibility SOL price when opened
+    pub price: u64,
+    /// Lamports deposited by the initiator, partner pays the rest
+    pub initiator_share: u64,
+    pub created_at: i64,
+    /// Paid the request's rent, gets it back when the request closes
+    pub rent_payer: Pubkey,
+}
+
+impl CompatibilityRequest {
+    pub const SIZE: usize = 8 + 32 + 32 + 8 + 8 + 8 + 8 + 32;
+}
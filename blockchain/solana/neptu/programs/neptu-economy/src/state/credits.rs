@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::*, errors::NeptuError, state::*};
+
+/// Bulk discount for buying at least `min_quantity` credits at once
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct CreditDiscountTier {
+    pub min_quantity: u32,
+    pub discount_bps: u16,
+}
+
+impl CreditDiscountTier {
+    pub const SIZE: usize = 4 + 2;
+}
+
+/// Bulk discount tiers for reading credits
+#[account]
+pub struct CreditPolicy {
+    /// Ordered from lowest to highest tier
+    pub tiers: Vec<CreditDiscountTier>,
+}
+
+impl CreditPolicy {
+    pub const SIZE: usize = 8 + 4 + (CreditDiscountTier::SIZE * MAX_CREDIT_TIERS);
+
+    pub fn set_tiers(&mut self, tiers: Vec<CreditDiscountTier>) -> Result<()> {
+        require!(
+            tiers.len() <= MAX_CREDIT_TIERS,
+            NeptuError::InvalidCreditTiers
+        );
+        for pair in tiers.windows(2) {
+            require!(
+                pair[1].min_quantity > pair[0].min_quantity,
+                NeptuError::InvalidCreditTiers
+            );
+        }
+        for tier in &tiers {
+            require!(
+                u64::from(tier.discount_bps) <= BPS_DENOMINATOR,
+                NeptuError::InvalidCreditTiers
+            );
+        }
+        self.tiers = tiers;
+        Ok(())
+    }
+
+    pub fn discount_bps(&self, quantity: u32) -> u16 {
+        self.tiers
+            .iter()
+            .rev()
+            .find(|t| quantity >= t.min_quantity)
+            .map_or(0, |t| t.discount_bps)
+    }
+}
+
+/// Prepaid readings of one type and currency; SOL credits hold their lamports
+#[account]
+pub struct Credits {
+    pub user: Pubkey,
+    pub reading_type: ReadingType,
+    pub currency: PaymentCurrency,
+    /// Unspent credits
+    pub balance: u32,
+    /// Payment still escrowed for unspent credits
+    pub escrowed: u64,
+    pub total_bought: u64,
+    pub total_consumed: u64,
+}
+
+impl Credits {
+    pub const SIZE: usize = 8 + 32 + 1 + 1 + 4 + 8 + 8 + 8;
+
+    pub fn add(&mut self, quantity: u32, amount: u64) -> Result<()> {
+        self.balance = self
+            .balance
+            .checked_add(quantity)
+            .ok_or(NeptuError::Overflow)?;
+        self.escrowed = self
+            .escrowed
+            .checked_add(amount)
+            .ok_or(NeptuError::Overflow)?;
+        self.total_bought = self
+            .total_bought
+            .checked_add(u64::from(quantity))
+            .ok_or(NeptuError::Overflow)?;
+        Ok(())
+    }
+
+    /// Spend credits; returns their share of the escrowed payment at the average price
+    pub fn consume(&mut self, quantity: u32) -> Result<u64> {
+        require!(
+            quantity > 0 && quantity <= self.balance,
+            NeptuError::InsufficientCredits
+        );
+        let amount = ((self.escrowed as u128 * quantity as u128) / self.balance as u128) as u64;
+        self.balance -= quantity;
+        self.escrowed -= amount;
+        self.total_consumed = self
+            .total_consumed
+            .checked_add(u64::from(quantity))
+            .ok_or(NeptuError::Overflow)?;
+        Ok(amount)
+    }
+}
@@ -0,0 +1,310 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::*, errors::NeptuError, state::*};
+
+/// Economy program state
+#[account]
+pub struct EconomyState {
+    pub authority: Pubkey,
+    pub neptu_mint: Pubkey,
+    pub treasury: Pubkey,
+    pub ecosystem_pool: Pubkey,
+    pub total_sol_collected: u64,
+    pub total_neptu_burned: u64,
+    pub total_neptu_rewarded: u64,
+    /// Emission epoch length in seconds
+    pub epoch_duration: i64,
+    /// Max NEPTU emitted from the rewards pool per epoch (all users)
+    pub epoch_global_cap: u64,
+    /// Max NEPTU emitted to a single user per epoch
+    pub epoch_user_cap: u64,
+    pub current_epoch: u64,
+    pub epoch_emitted: u64,
+    pub reward_curve: RewardCurve,
+    pub shortfall_policy: RewardShortfallPolicy,
+    pub total_neptu_recycled: u64,
+    /// Ecosystem vault balance above which the rebalance crank may run
+    pub rebalance_threshold: u64,
+    /// Share of the excess above threshold moved per crank (basis points)
+    pub rebalance_bps: u16,
+    pub total_rebalanced_to_rewards: u64,
+    pub total_rebalanced_to_ecosystem: u64,
+    pub rebalance_count: u64,
+    pub last_rebalance_at: i64,
+    /// Pauses payments and claims
+    pub paused: bool,
+    /// Backend key that co-signs reward claims
+    pub claim_signer: Pubkey,
+    /// Referrer bonus as a share of the referee's payment reward / NEPTU price
+    pub referral_bonus_bps: u16,
+    /// Lifetime NEPTU bonus cap per referrer
+    pub referral_cap: u64,
+    pub total_referral_bonuses: u64,
+    /// Backend key that attests delivery of escrowed readings
+    pub fulfillment_signer: Pubkey,
+    /// Seconds after which an undelivered escrowed order can be reclaimed
+    pub escrow_timeout: i64,
+    pub refund_burn_policy: RefundBurnPolicy,
+    /// Seconds between a metered withdrawal request and the withdrawal
+    pub metering_notice_period: i64,
+    /// Start of the current epoch numbering; epochs count from `epoch_base` here
+    pub epoch_start: i64,
+    pub epoch_base: u64,
+}
+
+/// What a NEPTU refund does with the burned part of the payment
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RefundBurnPolicy {
+    Reissue, // Pay the burned amount back from the rewards pool
+    Forfeit, // Refund only the recycled part
+}
+
+/// Direction of a vault rebalance
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RebalanceDirection {
+    EcosystemToRewards,
+    RewardsToEcosystem,
+}
+
+/// Who triggered a vault rebalance
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RebalanceTrigger {
+    Admin,
+    Crank,
+}
+
+/// What `pay_with_sol` does when the rewards pool can't cover the reward
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RewardShortfallPolicy {
+    Fail,    // Reject the payment
+    Partial, // Pay whatever the pool holds
+    Iou,     // Pay what the pool holds, owe the rest via PendingReward
+}
+
+impl RewardShortfallPolicy {
+    /// Reward to grant given the pool balance; Fail rejects when the pool is short
+    pub fn cover(&self, reward: u64, pool_balance: u64) -> Result<u64> {
+        if reward <= pool_balance {
+            return Ok(reward);
+        }
+        match self {
+            RewardShortfallPolicy::Fail => {
+                msg!(
+                    "Rewards pool holds {} NEPTU, reward needs {}",
+                    pool_balance,
+                    reward
+                );
+                err!(NeptuError::InsufficientBalance)
+            }
+            RewardShortfallPolicy::Partial => Ok(pool_balance),
+            RewardShortfallPolicy::Iou => Ok(reward),
+        }
+    }
+}
+
+/// Shape of the reward decay as the rewards pool drains
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RewardCurveKind {
+    Flat,    // Always 100% of base reward
+    Halving, // Halve each time `halving_step` NEPTU has left the pool
+    Linear,  // Proportional to remaining pool balance
+}
+
+/// Reward curve computed from the rewards pool balance
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RewardCurve {
+    pub kind: RewardCurveKind,
+    /// Pool balance at (or above) which rewards are paid in full
+    pub reference_balance: u64,
+    /// Halving only: pool depletion between halvings
+    pub halving_step: u64,
+    /// Minimum reward rate in basis points
+    pub floor_bps: u16,
+}
+
+impl RewardCurve {
+    pub const SIZE: usize = 1 + 8 + 8 + 2;
+
+    /// Current reward rate in basis points for the given pool balance
+    pub fn rate_bps(&self, pool_balance: u64) -> u64 {
+        let rate = match self.kind {
+            RewardCurveKind::Flat => BPS_DENOMINATOR,
+            RewardCurveKind::Halving => {
+                let depleted = self.reference_balance.saturating_sub(pool_balance);
+                let halvings = depleted / self.halving_step.max(1);
+                if halvings >= 64 {
+                    0
+                } else {
+                    BPS_DENOMINATOR >> halvings
+                }
+            }
+            RewardCurveKind::Linear => {
+                let scaled = (pool_balance as u128 * BPS_DENOMINATOR as u128)
+                    / self.reference_balance.max(1) as u128;
+                scaled.min(BPS_DENOMINATOR as u128) as u64
+            }
+        };
+        rate.max(u64::from(self.floor_bps)).min(BPS_DENOMINATOR)
+    }
+
+    /// Scale a base reward by the current rate
+    pub fn apply(&self, amount: u64, pool_balance: u64) -> Result<u64> {
+        let scaled = (amount as u128)
+            .checked_mul(self.rate_bps(pool_balance) as u128)
+            .ok_or(NeptuError::Overflow)?
+            / BPS_DENOMINATOR as u128;
+        Ok(scaled as u64)
+    }
+}
+
+/// Return data of `get_reward_rate`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct RewardRate {
+    pub rate_bps: u64,
+    pub pool_balance: u64,
+    pub potensi_reward: u64,
+    pub peluang_reward: u64,
+    pub ai_chat_reward: u64,
+    pub compatibility_reward: u64,
+}
+
+/// Which emission budget limited a reward
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BudgetScope {
+    Global,
+    User,
+}
+
+/// Result of reserving rewards against the emission budget
+pub struct EmissionGrant {
+    pub granted: u64,
+    /// Set when this grant used up (or was clipped by) a budget
+    pub exhausted: Option<BudgetScope>,
+}
+
+impl EconomyState {
+    pub const SIZE: usize = 8
+        + (32 * 4) // authority, mint, treasury, ecosystem pool
+        + (8 * 3) // totals
+        + 8 + (8 * 4) // epoch config/state
+        + RewardCurve::SIZE
+        + 1 // shortfall policy
+        + 8 // total recycled
+        + 8 + 2 + (8 * 3) + 8 // rebalance policy + counters
+        + 1 // paused
+        + 32 // claim signer
+        + 2 + 8 + 8 // referral policy + total
+        + 32 + 8 // fulfillment signer, escrow timeout
+        + 1 // refund burn policy
+        + 8 // metering notice period
+        + 8 + 8; // epoch numbering anchor
+
+    fn epoch_at(&self, now: i64) -> u64 {
+        let elapsed = now.saturating_sub(self.epoch_start).max(0);
+        self.epoch_base + (elapsed / self.epoch_duration) as u64
+    }
+
+    /// Reset the global budget when a new epoch has started
+    pub fn roll_epoch(&mut self, now: i64) {
+        let epoch = self.epoch_at(now);
+        if epoch != self.current_epoch {
+            self.current_epoch = epoch;
+            self.epoch_emitted = 0;
+        }
+    }
+
+    pub fn next_epoch_at(&self) -> i64 {
+        let offset = (self.current_epoch - self.epoch_base + 1) as i64;
+        self.epoch_start
+            .saturating_add(offset.saturating_mul(self.epoch_duration))
+    }
+
+    /// Change the epoch length; numbering restarts one past the current epoch
+    pub fn set_epoch_duration(&mut self, duration: i64, now: i64) {
+        self.roll_epoch(now);
+        self.epoch_duration = duration;
+        self.epoch_start = now;
+        self.epoch_base = self.current_epoch + 1;
+        self.current_epoch = self.epoch_base;
+        self.epoch_emitted = 0;
+    }
+
+    /// Reserve up to `requested` NEPTU against the global and per-user epoch budgets
+    pub fn reserve_emission(
+        &mut self,
+        record: &mut ClaimRecord,
+        requested: u64,
+        now: i64,
+    ) -> Result<EmissionGrant> {
+        self.roll_epoch(now);
+        if record.epoch != self.current_epoch {
+            record.epoch = self.current_epoch;
+            record.epoch_emitted = 0;
+        }
+
+        let global_left = self.epoch_global_cap.saturating_sub(self.epoch_emitted);
+        let user_left = self.epoch_user_cap.saturating_sub(record.epoch_emitted);
+        let granted = requested.min(global_left).min(user_left);
+
+        let exhausted = if requested == 0 {
+            None
+        } else if granted == global_left {
+            Some(BudgetScope::Global)
+        } else if granted == user_left {
+            Some(BudgetScope::User)
+        } else {
+            None
+        };
+
+        self.epoch_emitted = self
+            .epoch_emitted
+            .checked_add(granted)
+            .ok_or(NeptuError::Overflow)?;
+        record.epoch_emitted = record
+            .epoch_emitted
+            .checked_add(granted)
+            .ok_or(NeptuError::Overflow)?;
+
+        Ok(EmissionGrant { granted, exhausted })
+    }
+
+    /// Give back the budget of a returned reward if it was emitted this epoch
+    pub fn release_emission(
+        &mut self,
+        record: &mut ClaimRecord,
+        amount: u64,
+        emitted_at: i64,
+        now: i64,
+    ) {
+        self.roll_epoch(now);
+        if emitted_at < self.next_epoch_at().saturating_sub(self.epoch_duration) {
+            return;
+        }
+        self.epoch_emitted = self.epoch_emitted.saturating_sub(amount);
+        if record.epoch == self.current_epoch {
+            record.epoch_emitted = record.epoch_emitted.saturating_sub(amount);
+        }
+    }
+}
+
+/// NEPTU rewards owed to a user after the rewards pool ran short
+#[account]
+pub struct PendingReward {
+    pub user: Pubkey,
+    pub amount: u64,
+    pub total_deferred: u64,
+    pub total_paid: u64,
+}
+
+impl PendingReward {
+    pub const SIZE: usize = 8 + 32 + 8 + 8 + 8; // discriminator + user + amount + totals
+
+    pub fn record(&mut self, owed: u64) -> Result<()> {
+        self.amount = self.amount.checked_add(owed).ok_or(NeptuError::Overflow)?;
+        self.total_deferred = self
+            .total_deferred
+            .checked_add(owed)
+            .ok_or(NeptuError::Overflow)?;
+        Ok(())
+    }
+}
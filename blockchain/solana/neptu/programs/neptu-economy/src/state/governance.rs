@@ -0,0 +1,124 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::*, errors::NeptuError, state::*};
+
+/// NEPTU-holder governance parameters
+#[account]
+pub struct GovernanceConfig {
+    /// Min total NEPTU voted (for + against) for a proposal to pass
+    pub quorum: u64,
+    /// Min NEPTU balance to create a proposal
+    pub proposal_threshold: u64,
+    pub voting_period: i64,
+    /// Delay between end of voting and execution
+    pub execution_delay: i64,
+    pub proposal_count: u64,
+    pub vote_vault: Pubkey,
+}
+
+impl GovernanceConfig {
+    pub const SIZE: usize = 8 + 8 + 8 + 8 + 8 + 8 + 32;
+}
+
+/// Economic parameter a governance proposal changes
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ParameterChange {
+    BurnRate {
+        bps: u16,
+    },
+    Price {
+        reading_type: ReadingType,
+        currency: PaymentCurrency,
+        amount: u64,
+    },
+    RewardMultiplier {
+        bps: u16,
+    },
+    EmissionBudget {
+        global_cap: u64,
+        user_cap: u64,
+    },
+}
+
+impl ParameterChange {
+    pub const SIZE: usize = 1 + 1 + (1 + 32) + 8; // tag + largest variant (Price in a Token)
+
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            ParameterChange::BurnRate { bps } => {
+                require!(
+                    u64::from(*bps) <= BPS_DENOMINATOR,
+                    NeptuError::InvalidAmount
+                )
+            }
+            ParameterChange::Price {
+                currency, amount, ..
+            } => {
+                require!(
+                    !matches!(currency, PaymentCurrency::Token(_)),
+                    NeptuError::PaymentMintNotAccepted
+                );
+                require!(*amount > 0, NeptuError::InvalidAmount)
+            }
+            ParameterChange::RewardMultiplier { bps } => {
+                require!(
+                    u64::from(*bps) <= 2 * BPS_DENOMINATOR,
+                    NeptuError::InvalidAmount
+                )
+            }
+            ParameterChange::EmissionBudget { .. } => {}
+        }
+        Ok(())
+    }
+
+    pub fn apply(&self, pricing: &mut PricingConfig, state: &mut EconomyState) -> Result<()> {
+        match *self {
+            ParameterChange::BurnRate { bps } => pricing.burn_rate_bps = bps,
+            ParameterChange::Price {
+                reading_type,
+                currency,
+                amount,
+            } => pricing.set_price(&reading_type, currency, amount)?,
+            ParameterChange::RewardMultiplier { bps } => pricing.reward_multiplier_bps = bps,
+            ParameterChange::EmissionBudget {
+                global_cap,
+                user_cap,
+            } => {
+                state.epoch_global_cap = global_cap;
+                state.epoch_user_cap = user_cap;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Governance proposal voted on by NEPTU holders
+#[account]
+pub struct GovernanceProposal {
+    pub id: u64,
+    pub proposer: Pubkey,
+    pub change: ParameterChange,
+    pub votes_for: u64,
+    pub votes_against: u64,
+    pub created_at: i64,
+    pub voting_ends_at: i64,
+    pub executable_at: i64,
+    pub executed: bool,
+}
+
+impl GovernanceProposal {
+    pub const SIZE: usize = 8 + 8 + 32 + ParameterChange::SIZE + 8 + 8 + 8 + 8 + 8 + 1;
+}
+
+/// NEPTU locked by a voter on one proposal
+#[account]
+pub struct VoteRecord {
+    pub voter: Pubkey,
+    pub proposal: Pubkey,
+    pub amount: u64,
+    pub support: bool,
+}
+
+impl VoteRecord {
+    pub const SIZE: usize = 8 + 32 + 32 + 8 + 1;
+}
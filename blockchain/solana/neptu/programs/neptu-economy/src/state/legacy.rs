@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::*, state::*};
+
+/// Pricing config as deployed before it grew in place; see `migrate_pricing_config`
+#[derive(AnchorDeserialize)]
+pub struct PricingConfigV0 {
+    pub authority: Pubkey,
+    pub potensi_sol_price: u64,
+    pub peluang_sol_price: u64,
+    pub ai_chat_sol_price: u64,
+    pub compatibility_sol_price: u64,
+    pub potensi_neptu_price: u64,
+    pub peluang_neptu_price: u64,
+    pub ai_chat_neptu_price: u64,
+    pub compatibility_neptu_price: u64,
+}
+
+impl PricingConfigV0 {
+    pub const SIZE: usize = 8 + 32 + (8 * 8);
+}
+
+/// Economy state as deployed before it grew in place; see `migrate_economy_state`
+#[derive(AnchorDeserialize)]
+pub struct EconomyStateV0 {
+    pub authority: Pubkey,
+    pub neptu_mint: Pubkey,
+    pub treasury: Pubkey,
+    pub ecosystem_pool: Pubkey,
+    pub total_sol_collected: u64,
+    pub total_neptu_burned: u64,
+    pub total_neptu_rewarded: u64,
+}
+
+impl EconomyStateV0 {
+    pub const SIZE: usize = 8 + (32 * 4) + (8 * 3);
+}
+
+impl From<EconomyStateV0> for EconomyState {
+    fn from(v0: EconomyStateV0) -> Self {
+        EconomyState {
+            authority: v0.authority,
+            neptu_mint: v0.neptu_mint,
+            treasury: v0.treasury,
+            ecosystem_pool: v0.ecosystem_pool,
+            total_sol_collected: v0.total_sol_collected,
+            total_neptu_burned: v0.total_neptu_burned,
+            total_neptu_rewarded: v0.total_neptu_rewarded,
+            epoch_duration: defaults::EPOCH_DURATION,
+            epoch_global_cap: defaults::EPOCH_GLOBAL_CAP,
+            epoch_user_cap: defaults::EPOCH_USER_CAP,
+            current_epoch: 0,
+            epoch_emitted: 0,
+            reward_curve: RewardCurve {
+                kind: RewardCurveKind::Halving,
+                reference_balance: defaults::REWARD_CURVE_REFERENCE,
+                halving_step: defaults::REWARD_CURVE_HALVING_STEP,
+                floor_bps: defaults::REWARD_CURVE_FLOOR_BPS,
+            },
+            shortfall_policy: RewardShortfallPolicy::Fail,
+            total_neptu_recycled: 0,
+            rebalance_threshold: u64::MAX,
+            rebalance_bps: 0,
+            total_rebalanced_to_rewards: 0,
+            total_rebalanced_to_ecosystem: 0,
+            rebalance_count: 0,
+            last_rebalance_at: 0,
+            paused: false,
+            claim_signer: v0.authority,
+            referral_bonus_bps: 0,
+            referral_cap: 0,
+            total_referral_bonuses: 0,
+            fulfillment_signer: v0.authority,
+            escrow_timeout: defaults::ESCROW_TIMEOUT,
+            refund_burn_policy: RefundBurnPolicy::Reissue,
+            metering_notice_period: defaults::METERING_NOTICE_PERIOD,
+            epoch_start: 0,
+            epoch_base: 0,
+        }
+    }
+}
+
+/// Claim record with a single high-water nonce; see `migrate_claim_record`
+#[derive(AnchorDeserialize)]
+pub struct ClaimRecordV0 {
+    pub user: Pubkey,
+    pub last_nonce: u64,
+    pub total_claimed: u64,
+}
+
+impl ClaimRecordV0 {
+    pub const SIZE: usize = 8 + 32 + 8 + 8;
+}
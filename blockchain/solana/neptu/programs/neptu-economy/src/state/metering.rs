@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::NeptuError, state::*};
+
+/// Prepaid balance for metered usage; SOL deposits hold their lamports
+#[account]
+pub struct MeteringAccount {
+    pub user: Pubkey,
+    pub currency: PaymentCurrency,
+    /// Unsettled deposit
+    pub balance: u64,
+    pub total_deposited: u64,
+    pub total_settled: u64,
+    /// End of the last settled usage period
+    pub last_period_end: i64,
+    /// 0 when no withdrawal is pending
+    pub withdrawal_requested_at: i64,
+}
+
+impl MeteringAccount {
+    pub const SIZE: usize = 8 + 32 + 1 + 8 + 8 + 8 + 8 + 8;
+
+    /// Charge a usage period against the balance; returns (charged, unpaid)
+    pub fn settle(
+        &mut self,
+        units: u64,
+        rate: u64,
+        period_start: i64,
+        period_end: i64,
+    ) -> Result<(u64, u64)> {
+        require!(
+            period_end > period_start && period_start >= self.last_period_end,
+            NeptuError::InvalidUsagePeriod
+        );
+        require!(
+            self.withdrawal_requested_at == 0 || period_start < self.withdrawal_requested_at,
+            NeptuError::WithdrawalPending
+        );
+        let charge = units.checked_mul(rate).ok_or(NeptuError::Overflow)?;
+        let charged = charge.min(self.balance);
+        self.balance -= charged;
+        self.total_settled = self
+            .total_settled
+            .checked_add(charged)
+            .ok_or(NeptuError::Overflow)?;
+        self.last_period_end = period_end;
+        Ok((charged, charge - charged))
+    }
+}
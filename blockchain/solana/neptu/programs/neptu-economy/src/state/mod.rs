@@ -0,0 +1,39 @@
+mod claim;
+mod compatibility;
+mod credits;
+mod economy;
+mod governance;
+mod legacy;
+mod metering;
+mod multisig;
+mod order;
+mod price_feed;
+mod pricing;
+mod profile;
+mod receipt;
+mod referral;
+mod rent_sponsorship;
+mod roles;
+mod session;
+mod staking;
+mod treasury;
+
+pub use claim::*;
+pub use compatibility::*;
+pub use credits::*;
+pub use economy::*;
+pub use governance::*;
+pub use legacy::*;
+pub use metering::*;
+pub use multisig::*;
+pub use order::*;
+pub use price_feed::*;
+pub use pricing::*;
+pub use profile::*;
+pub use receipt::*;
+pub use referral::*;
+pub use rent_sponsorship::*;
+pub use roles::*;
+pub use session::*;
+pub use staking::*;
+pub use treasury::*;
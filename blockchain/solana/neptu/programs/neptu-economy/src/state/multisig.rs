@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::*, errors::NeptuError, helpers::*};
+
+/// Built-in M-of-N multisig for privileged operations
+/// Proposals are arbitrary instructions signed by the `multisig_signer` PDA
+#[account]
+pub struct Multisig {
+    pub members: Vec<Pubkey>,
+    pub threshold: u8,
+    /// Bumped when members change; stale proposals can't execute
+    pub nonce: u64,
+    pub proposal_count: u64,
+    pub signer_bump: u8,
+}
+
+impl Multisig {
+    pub const SIZE: usize = 8 + 4 + (32 * MAX_MULTISIG_MEMBERS) + 1 + 8 + 8 + 1;
+
+    pub fn set_members(&mut self, members: Vec<Pubkey>, threshold: u8) -> Result<()> {
+        validate_member_set(&members, threshold, MAX_MULTISIG_MEMBERS)?;
+        self.members = members;
+        self.threshold = threshold;
+        Ok(())
+    }
+
+    pub fn member_index(&self, key: &Pubkey) -> Result<usize> {
+        self.members
+            .iter()
+            .position(|m| m == key)
+            .ok_or_else(|| error!(NeptuError::Unauthorized))
+    }
+}
+
+/// Account meta stored in a multisig proposal
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ProposalAccountMeta {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+/// Instruction awaiting multisig approval
+#[account]
+pub struct MultisigProposal {
+    pub id: u64,
+    pub proposer: Pubkey,
+    pub program_id: Pubkey,
+    pub accounts: Vec<ProposalAccountMeta>,
+    pub data: Vec<u8>,
+    /// Bit `i` set = member `i` approved
+    pub approvals: u16,
+    pub multisig_nonce: u64,
+    pub created_at: i64,
+    pub executed_at: i64,
+}
+
+impl MultisigProposal {
+    pub fn size(accounts: usize, data: usize) -> usize {
+        8 + 8 + 32 + 32 + 4 + (accounts * (32 + 1 + 1)) + 4 + data + 2 + 8 + 8 + 8
+    }
+}
@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+
+/// Escrowed payment awaiting delivery of the reading; SOL orders hold the lamports
+#[account]
+pub struct Order {
+    pub user: Pubkey,
+    pub order_id: u64,
+    pub reading_type: ReadingType,
+    pub currency: PaymentCurrency,
+    pub amount: u64,
+    pub created_at: i64,
+    pub expires_at: i64,
+    /// Paid the order's rent, gets it back when the order closes
+    pub rent_payer: Pubkey,
+}
+
+impl Order {
+    pub const SIZE: usize = 8 + 32 + 8 + 1 + 1 + 8 + 8 + 8 + 32;
+}
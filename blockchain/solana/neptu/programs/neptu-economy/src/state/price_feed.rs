@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::*, errors::NeptuError};
+
+/// Discriminator of a Pyth `PriceUpdateV2` account
+pub const PYTH_PRICE_UPDATE_DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+
+/// How many Wormhole guardian signatures were checked for a Pyth price update
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PythVerificationLevel {
+    Partial { num_signatures: u8 },
+    Full,
+}
+
+/// SOL/USD price as published by Pyth: `price * 10^exponent` USD per SOL,
+/// with confidence interval `conf` in the same units
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct PythPriceMessage {
+    pub feed_id: [u8; 32],
+    pub price: i64,
+    pub conf: u64,
+    pub exponent: i32,
+    pub publish_time: i64,
+    pub prev_publish_time: i64,
+    pub ema_price: i64,
+    pub ema_conf: u64,
+}
+
+impl PythPriceMessage {
+    pub const SIZE: usize = 32 + 8 + 8 + 4 + (8 * 4);
+
+    /// Read the price message out of a Pyth `PriceUpdateV2` account
+    /// Only fully verified updates are accepted
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        require!(
+            data.starts_with(&PYTH_PRICE_UPDATE_DISCRIMINATOR),
+            NeptuError::InvalidPriceFeed
+        );
+        let mut body = &data[8..];
+        let _write_authority =
+            Pubkey::deserialize(&mut body).map_err(|_| error!(NeptuError::InvalidPriceFeed))?;
+        let verification_level = PythVerificationLevel::deserialize(&mut body)
+            .map_err(|_| error!(NeptuError::InvalidPriceFeed))?;
+        require!(
+            verification_level == PythVerificationLevel::Full,
+            NeptuError::InvalidPriceFeed
+        );
+        Self::deserialize(&mut body).map_err(|_| error!(NeptuError::InvalidPriceFeed))
+    }
+
+    /// Lamports worth `cents` USD; None when the price is non-positive or the
+    /// confidence interval is wider than `max_conf_bps` of the price
+    pub fn lamports_for_cents(&self, cents: u64, max_conf_bps: u16) -> Option<u64> {
+        let price = u128::try_from(self.price).ok().filter(|p| *p > 0)?;
+        if (self.conf as u128) * (BPS_DENOMINATOR as u128) > price * max_conf_bps as u128 {
+            return None;
+        }
+        // lamports = cents / 100 * LAMPORTS_PER_SOL / (price * 10^exponent)
+        let numerator = (cents as u128).checked_mul((LAMPORTS_PER_SOL / 100) as u128)?;
+        let lamports = if self.exponent <= 0 {
+            numerator.checked_mul(10u128.checked_pow(self.exponent.unsigned_abs())?)? / price
+        } else {
+            numerator / price.checked_mul(10u128.checked_pow(self.exponent as u32)?)?
+        };
+        u64::try_from(lamports).ok()
+    }
+}
+
+/// Writable stand-in for a Pyth `PriceUpdateV2` account, byte-compatible with it
+/// Only built with the `mock-price-feed` feature, for localnet and tests
+#[cfg(feature = "mock-price-feed")]
+#[account]
+pub struct PriceUpdateV2 {
+    pub write_authority: Pubkey,
+    pub verification_level: PythVerificationLevel,
+    pub price_message: PythPriceMessage,
+    pub posted_slot: u64,
+}
+
+#[cfg(feature = "mock-price-feed")]
+impl PriceUpdateV2 {
+    pub const SIZE: usize = 8 + 32 + 2 + PythPriceMessage::SIZE + 8;
+}
@@ -0,0 +1,232 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::*, errors::NeptuError, helpers::*, state::*};
+
+/// Reading type for pricing
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ReadingType {
+    Potensi,       // Personality potential reading
+    Peluang,       // Daily opportunity reading
+    AiChat,        // AI chat consultation
+    Compatibility, // Compatibility reading
+}
+
+/// Pricing configuration (admin-managed)
+#[account]
+pub struct PricingConfig {
+    pub authority: Pubkey,
+    pub potensi_sol_price: u64,
+    pub peluang_sol_price: u64,
+    pub ai_chat_sol_price: u64,
+    pub compatibility_sol_price: u64,
+    pub potensi_neptu_price: u64,
+    pub peluang_neptu_price: u64,
+    pub ai_chat_neptu_price: u64,
+    pub compatibility_neptu_price: u64,
+    /// Share of NEPTU payments burned (rest is recycled)
+    pub burn_rate_bps: u16,
+    /// Scales NEPTU rewards for SOL payments
+    pub reward_multiplier_bps: u16,
+    /// USD prices indexed by `ReadingType`; 0 = use the fixed lamport price
+    pub usd_prices_cents: [u64; 4],
+    /// SOL/USD feed used to convert USD prices
+    pub price_feed: Pubkey,
+    /// Max seconds since the feed's last publish
+    pub max_price_age: i64,
+    /// Max feed confidence interval relative to price
+    pub max_price_conf_bps: u16,
+    /// Max per-unit rate a usage report may charge a SOL metered deposit
+    pub max_sol_usage_rate: u64,
+    /// Max per-unit rate a usage report may charge a NEPTU metered deposit
+    pub max_neptu_usage_rate: u64,
+}
+
+impl PricingConfig {
+    pub const SIZE: usize = 8 + 32 + (8 * 8) + 2 + 2 // discriminator + authority + 8 u64 prices + burn rate + reward multiplier
+        + (8 * 4) + 32 + 8 + 2 // USD prices + feed + staleness/confidence limits
+        + 8 + 8; // metered usage rate caps
+
+    /// Max per-unit usage rate for metered deposits in `currency`
+    pub fn max_usage_rate(&self, currency: PaymentCurrency) -> Result<u64> {
+        match currency {
+            PaymentCurrency::Sol => Ok(self.max_sol_usage_rate),
+            PaymentCurrency::Neptu => Ok(self.max_neptu_usage_rate),
+            PaymentCurrency::Token(_) => err!(NeptuError::PaymentMintNotAccepted),
+        }
+    }
+
+    pub fn get_sol_price(&self, reading_type: &ReadingType) -> u64 {
+        match reading_type {
+            ReadingType::Potensi => self.potensi_sol_price,
+            ReadingType::Peluang => self.peluang_sol_price,
+            ReadingType::AiChat => self.ai_chat_sol_price,
+            ReadingType::Compatibility => self.compatibility_sol_price,
+        }
+    }
+
+    pub fn get_neptu_price(&self, reading_type: &ReadingType) -> u64 {
+        match reading_type {
+            ReadingType::Potensi => self.potensi_neptu_price,
+            ReadingType::Peluang => self.peluang_neptu_price,
+            ReadingType::AiChat => self.ai_chat_neptu_price,
+            ReadingType::Compatibility => self.compatibility_neptu_price,
+        }
+    }
+
+    pub fn set_price(
+        &mut self,
+        reading_type: &ReadingType,
+        currency: PaymentCurrency,
+        price: u64,
+    ) -> Result<()> {
+        let slot = match (currency, reading_type) {
+            (PaymentCurrency::Sol, ReadingType::Potensi) => &mut self.potensi_sol_price,
+            (PaymentCurrency::Sol, ReadingType::Peluang) => &mut self.peluang_sol_price,
+            (PaymentCurrency::Sol, ReadingType::AiChat) => &mut self.ai_chat_sol_price,
+            (PaymentCurrency::Sol, ReadingType::Compatibility) => &mut self.compatibility_sol_price,
+            (PaymentCurrency::Neptu, ReadingType::Potensi) => &mut self.potensi_neptu_price,
+            (PaymentCurrency::Neptu, ReadingType::Peluang) => &mut self.peluang_neptu_price,
+            (PaymentCurrency::Neptu, ReadingType::AiChat) => &mut self.ai_chat_neptu_price,
+            (PaymentCurrency::Neptu, ReadingType::Compatibility) => {
+                &mut self.compatibility_neptu_price
+            }
+            (PaymentCurrency::Token(_), _) => return err!(NeptuError::PaymentMintNotAccepted),
+        };
+        *slot = price;
+        Ok(())
+    }
+
+    /// SOL price of a reading, converted from its USD price when one is set
+    /// A fresh, confident feed is required for USD prices; otherwise the fixed lamport price
+    pub fn resolve_sol_price(
+        &self,
+        reading_type: &ReadingType,
+        feed: &Option<UncheckedAccount>,
+    ) -> Result<u64> {
+        let fixed = self.get_sol_price(reading_type);
+        let cents = self.usd_prices_cents[*reading_type as usize];
+        if cents == 0 {
+            return Ok(fixed);
+        }
+        let Some(feed) = feed else {
+            return err!(NeptuError::PriceFeedMissing);
+        };
+        require_keys_eq!(feed.key(), self.price_feed, NeptuError::InvalidPriceFeed);
+        let feed = PythPriceMessage::parse(&feed.try_borrow_data()?)?;
+
+        let now = Clock::get()?.unix_timestamp;
+        if now.saturating_sub(feed.publish_time) > self.max_price_age {
+            msg!("Price feed is stale, using fixed price");
+            return Ok(fixed);
+        }
+        match feed.lamports_for_cents(cents, self.max_price_conf_bps) {
+            Some(lamports) => Ok(lamports),
+            None => {
+                msg!("Price feed is not usable, using fixed price");
+                Ok(fixed)
+            }
+        }
+    }
+
+    /// NEPTU reward for a SOL payment before curve/budget adjustments
+    pub fn get_sol_reward(&self, reading_type: &ReadingType) -> Result<u64> {
+        let reward = (self.get_neptu_price(reading_type) as u128)
+            .checked_mul(self.reward_multiplier_bps as u128)
+            .ok_or(NeptuError::Overflow)?
+            / BPS_DENOMINATOR as u128;
+        u64::try_from(reward).map_err(|_| error!(NeptuError::Overflow))
+    }
+
+    /// Split a NEPTU payment into (burned, recycled)
+    pub fn split_neptu_payment(&self, amount: u64) -> Result<(u64, u64)> {
+        let burn = (amount as u128)
+            .checked_mul(self.burn_rate_bps as u128)
+            .ok_or(NeptuError::Overflow)?
+            / BPS_DENOMINATOR as u128;
+        let burn = burn as u64;
+        Ok((burn, amount - burn))
+    }
+}
+
+/// Batch checkout line: `quantity` readings of one type
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct LineItem {
+    pub reading_type: ReadingType,
+    pub quantity: u16,
+}
+
+impl LineItem {
+    /// `unit` amount times quantity
+    pub fn total(&self, unit: u64) -> Result<u64> {
+        unit.checked_mul(u64::from(self.quantity))
+            .ok_or_else(|| error!(NeptuError::Overflow))
+    }
+}
+
+/// Currency a reading is priced or paid in
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PaymentCurrency {
+    Sol,
+    Neptu,
+    /// Accepted SPL payment mint; only `pay_with_token` receipts carry it
+    Token(Pubkey),
+}
+
+impl PaymentCurrency {
+    /// Seed byte of per-currency PDAs
+    pub fn seed(self) -> u8 {
+        match self {
+            PaymentCurrency::Sol => 0,
+            PaymentCurrency::Neptu => 1,
+            PaymentCurrency::Token(_) => 2,
+        }
+    }
+}
+
+/// SPL token accepted as payment
+#[account]
+pub struct PaymentMint {
+    pub mint: Pubkey,
+    pub enabled: bool,
+    /// Prices in the mint's base units, indexed by `ReadingType`
+    pub prices: [u64; 4],
+    /// Treasury token account receiving payments in this mint
+    pub treasury_token_vault: Pubkey,
+    pub total_collected: u64,
+    /// Max base units withdrawn (or refunded) per treasury period; 0 blocks withdrawals
+    /// Only changed through an approved treasury config proposal
+    pub spending_limit: u64,
+    pub period_start: i64,
+    pub period_spent: u64,
+    pub total_withdrawn: u64,
+}
+
+impl PaymentMint {
+    pub const SIZE: usize = 8 + 32 + 1 + (8 * 4) + 32 + 8 + (8 * 4);
+
+    /// Count a withdrawal against this mint's spending limit
+    pub fn spend(&mut self, amount: u64, period_duration: i64, now: i64) -> Result<()> {
+        charge_spending_limit(
+            &mut self.period_start,
+            &mut self.period_spent,
+            self.spending_limit,
+            period_duration,
+            amount,
+            now,
+        )?;
+        self.total_withdrawn = self
+            .total_withdrawn
+            .checked_add(amount)
+            .ok_or(NeptuError::Overflow)?;
+        Ok(())
+    }
+
+    pub fn price(&self, reading_type: ReadingType) -> Result<u64> {
+        let price = self.prices[reading_type as usize];
+        require!(
+            self.enabled && price > 0,
+            NeptuError::PaymentMintNotAccepted
+        );
+        Ok(price)
+    }
+}
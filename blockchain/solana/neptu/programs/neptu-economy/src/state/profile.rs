@@ -0,0 +1,158 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::*, errors::NeptuError, events::*, state::*};
+
+/// Lifetime activity of a user, updated by every payment and claim
+#[account]
+pub struct UserProfile {
+    pub user: Pubkey,
+    /// Readings bought, indexed by `ReadingType`
+    pub readings: [u32; 4],
+    pub total_sol_spent: u64,
+    pub total_neptu_spent: u64,
+    pub total_neptu_burned: u64,
+    pub total_neptu_earned: u64,
+    pub first_activity_at: i64,
+    pub last_activity_at: i64,
+    /// Index into the loyalty program tiers, None below the first tier
+    pub loyalty_tier: Option<u8>,
+    pub gifts_sent: u64,
+    pub gifts_received: u64,
+    /// Index of the next receipt paid by this user
+    pub receipts_paid: u64,
+}
+
+impl UserProfile {
+    pub const SIZE: usize = 8 + 32 + (4 * 4) + (8 * 4) + 8 + 8 + 2 + 8 + 8 + 8;
+
+    pub fn total_readings(&self) -> u64 {
+        self.readings.iter().map(|&n| u64::from(n)).sum()
+    }
+
+    pub fn touch(&mut self, user: Pubkey, now: i64) {
+        if self.first_activity_at == 0 {
+            self.user = user;
+            self.first_activity_at = now;
+        }
+        self.last_activity_at = now;
+    }
+
+    pub fn record_reading(
+        &mut self,
+        user: Pubkey,
+        reading_type: ReadingType,
+        now: i64,
+    ) -> Result<()> {
+        self.record_readings(user, reading_type, 1, now)
+    }
+
+    pub fn record_readings(
+        &mut self,
+        user: Pubkey,
+        reading_type: ReadingType,
+        quantity: u32,
+        now: i64,
+    ) -> Result<()> {
+        self.touch(user, now);
+        let count = &mut self.readings[reading_type as usize];
+        *count = count.checked_add(quantity).ok_or(NeptuError::Overflow)?;
+        Ok(())
+    }
+
+    /// Undo refunded readings
+    pub fn remove_readings(&mut self, reading_type: ReadingType, quantity: u32) {
+        let count = &mut self.readings[reading_type as usize];
+        *count = count.saturating_sub(quantity);
+    }
+
+    pub fn record_earned(&mut self, amount: u64) -> Result<()> {
+        self.total_neptu_earned = self
+            .total_neptu_earned
+            .checked_add(amount)
+            .ok_or(NeptuError::Overflow)?;
+        Ok(())
+    }
+
+    /// Re-derive the loyalty tier from lifetime stats
+    pub fn refresh_loyalty_tier(&mut self, program: &Option<Account<LoyaltyProgram>>) {
+        let Some(program) = program else {
+            return;
+        };
+        let tier = program.tier_index(self);
+        if tier != self.loyalty_tier {
+            self.loyalty_tier = tier;
+            emit!(LoyaltyTierChanged {
+                user: self.user,
+                tier,
+            });
+        }
+    }
+}
+
+/// Loyalty tier: lifetime activity thresholds and the discount they unlock
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct LoyaltyTier {
+    pub min_readings: u64,
+    /// Minimum lifetime NEPTU spent plus earned
+    pub min_neptu_volume: u64,
+    /// Price discount on SOL and NEPTU payments
+    pub price_discount_bps: u16,
+}
+
+impl LoyaltyTier {
+    pub const SIZE: usize = 8 + 8 + 2;
+
+    pub fn discounted(&self, price: u64) -> u64 {
+        let discount = (price as u128 * self.price_discount_bps as u128) / BPS_DENOMINATOR as u128;
+        price - discount as u64
+    }
+}
+
+/// Loyalty tiers derived from user profiles
+#[account]
+pub struct LoyaltyProgram {
+    /// Ordered from lowest to highest tier
+    pub tiers: Vec<LoyaltyTier>,
+}
+
+impl LoyaltyProgram {
+    pub const SIZE: usize = 8 + 4 + (LoyaltyTier::SIZE * MAX_LOYALTY_TIERS);
+
+    pub fn set_tiers(&mut self, tiers: Vec<LoyaltyTier>) -> Result<()> {
+        require!(
+            tiers.len() <= MAX_LOYALTY_TIERS,
+            NeptuError::InvalidLoyaltyTiers
+        );
+        for pair in tiers.windows(2) {
+            require!(
+                pair[1].min_readings >= pair[0].min_readings
+                    && pair[1].min_neptu_volume >= pair[0].min_neptu_volume,
+                NeptuError::InvalidLoyaltyTiers
+            );
+        }
+        for tier in &tiers {
+            require!(
+                u64::from(tier.price_discount_bps) <= BPS_DENOMINATOR,
+                NeptuError::InvalidLoyaltyTiers
+            );
+        }
+        self.tiers = tiers;
+        Ok(())
+    }
+
+    /// Highest tier the profile qualifies for
+    pub fn tier_index(&self, profile: &UserProfile) -> Option<u8> {
+        let readings = profile.total_readings();
+        let volume = profile
+            .total_neptu_spent
+            .saturating_add(profile.total_neptu_earned);
+        self.tiers
+            .iter()
+            .rposition(|t| readings >= t.min_readings && volume >= t.min_neptu_volume)
+            .map(|i| i as u8)
+    }
+
+    pub fn tier_for(&self, profile: &UserProfile) -> Option<LoyaltyTier> {
+        self.tier_index(profile).map(|i| self.tiers[i as usize])
+    }
+}
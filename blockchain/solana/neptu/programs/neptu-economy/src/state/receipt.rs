@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+
+/// Proof of a paid reading; the backend unlocks the reading for `beneficiary`
+#[account]
+pub struct Receipt {
+    pub payer: Pubkey,
+    pub beneficiary: Pubkey,
+    pub reading_type: ReadingType,
+    pub currency: PaymentCurrency,
+    pub amount: u64,
+    pub issued_at: i64,
+    /// Hash of the encrypted gift message, if any
+    pub message_hash: Option<[u8; 32]>,
+    /// NEPTU burned by the payment
+    pub burned: u64,
+    pub refunded: bool,
+    /// NEPTU reward paid out for the payment, returned on refund
+    pub reward: u64,
+    /// Holder of `reward` (the payer, or a gift's beneficiary)
+    pub reward_recipient: Pubkey,
+    /// Part of the recycled NEPTU that went to stakers
+    pub staker_yield: u64,
+    /// Bonus paid to the payer's referrer
+    pub referral_bonus: u64,
+    /// Readings covered (batch line items can cover several)
+    pub quantity: u16,
+}
+
+impl Receipt {
+    pub const SIZE: usize =
+        8 + 32 + 32 + 1 + (1 + 32) + 8 + 8 + (1 + 32) + 8 + 1 + 8 + 32 + 8 + 8 + 2;
+
+    /// Split a NEPTU refund into (returned by the ecosystem pool, burned or paid to stakers)
+    pub fn neptu_refund_sources(&self) -> (u64, u64) {
+        let recycled = self.amount - self.burned;
+        (
+            recycled - self.staker_yield,
+            self.burned + self.staker_yield,
+        )
+    }
+}
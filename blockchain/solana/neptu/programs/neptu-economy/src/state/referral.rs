@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+/// Referee -> referrer binding, set once on the referee's first payment
+#[account]
+pub struct Referral {
+    pub referee: Pubkey,
+    pub referrer: Pubkey,
+    pub bound_at: i64,
+}
+
+impl Referral {
+    pub const SIZE: usize = 8 + 32 + 32 + 8;
+}
+
+/// Lifetime referral stats of a registered referrer
+#[account]
+pub struct ReferrerStats {
+    pub referrer: Pubkey,
+    pub referee_count: u32,
+    pub referred_payments: u64,
+    pub total_earned: u64,
+    /// Bonuses of refunded payments, withheld from the next bonuses
+    pub clawback: u64,
+}
+
+impl ReferrerStats {
+    pub const SIZE: usize = 8 + 32 + 4 + 8 + 8 + 8;
+
+    /// Clip a bonus to the referrer's remaining cap and the pool, then withhold
+    /// the bonuses of refunded payments from it
+    pub fn clip_bonus(&mut self, bonus: u64, referral_cap: u64, pool_balance: u64) -> u64 {
+        let bonus = bonus
+            .min(referral_cap.saturating_sub(self.total_earned))
+            .min(pool_balance);
+        let withheld = bonus.min(self.clawback);
+        self.clawback -= withheld;
+        bonus - withheld
+    }
+}
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+/// Program-funded vault reimbursing onboarding rent paid by the sponsor
+#[account]
+pub struct RentSponsorship {
+    /// Fee payer eligible for reimbursement (backend)
+    pub sponsor: Pubkey,
+    /// Max lamports reimbursed per user
+    pub per_user_limit: u64,
+    pub total_sponsored: u64,
+    pub bump: u8,
+}
+
+impl RentSponsorship {
+    pub const SIZE: usize = 8 + 32 + 8 + 8 + 1;
+}
+
+/// Onboarding rent reimbursed for a user
+#[account]
+pub struct SponsoredRent {
+    pub user: Pubkey,
+    pub lamports: u64,
+    /// SPONSOR_* bitmask of accounts already reimbursed
+    pub accounts: u8,
+}
+
+impl SponsoredRent {
+    pub const SIZE: usize = 8 + 32 + 8 + 1;
+}
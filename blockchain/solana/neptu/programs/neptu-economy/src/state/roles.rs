@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::*, errors::NeptuError};
+
+/// Administrative roles; the economy authority implicitly holds all of them
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Role {
+    PricingManager,   // Update reading prices
+    Treasurer,        // Move funds between program vaults
+    Pauser,           // Pause / resume payments and claims
+    ClaimSignerAdmin, // Rotate the claim signer
+    CatalogManager,   // Manage accepted payment mints and their prices
+    Refunds,          // Refund receipted payments
+}
+
+impl Role {
+    pub fn bit(self) -> u8 {
+        1 << (self as u8)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct RoleAssignment {
+    pub member: Pubkey,
+    /// Bitmask of `Role::bit`
+    pub roles: u8,
+}
+
+/// Role registry for economy administration
+#[account]
+pub struct Roles {
+    pub assignments: Vec<RoleAssignment>,
+}
+
+impl Roles {
+    pub const SIZE: usize = 8 + 4 + ((32 + 1) * MAX_ROLE_MEMBERS);
+
+    pub fn has_role(&self, key: &Pubkey, role: Role) -> bool {
+        self.assignments
+            .iter()
+            .any(|a| a.member == *key && a.roles & role.bit() != 0)
+    }
+
+    /// True if `key` is the economy authority or holds `role`
+    pub fn authorize(&self, admin: &Pubkey, key: &Pubkey, role: Role) -> bool {
+        key == admin || self.has_role(key, role)
+    }
+
+    pub fn grant(&mut self, member: Pubkey, role: Role) -> Result<()> {
+        if let Some(a) = self.assignments.iter_mut().find(|a| a.member == member) {
+            a.roles |= role.bit();
+            return Ok(());
+        }
+        require!(
+            self.assignments.len() < MAX_ROLE_MEMBERS,
+            NeptuError::TooManyRoleMembers
+        );
+        self.assignments.push(RoleAssignment {
+            member,
+            roles: role.bit(),
+        });
+        Ok(())
+    }
+
+    pub fn revoke(&mut self, member: Pubkey, role: Role) -> Result<()> {
+        let a = self
+            .assignments
+            .iter_mut()
+            .find(|a| a.member == member)
+            .ok_or(NeptuError::RoleNotFound)?;
+        require!(a.roles & role.bit() != 0, NeptuError::RoleNotHeld);
+        a.roles &= !role.bit();
+        self.assignments.retain(|a| a.roles != 0);
+        Ok(())
+    }
+}
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::NeptuError, state::*};
+
+/// Ephemeral key allowed to pay for readings on the owner's behalf
+#[account]
+pub struct Session {
+    pub owner: Pubkey,
+    pub session_key: Pubkey,
+    pub expires_at: i64,
+    /// SESSION_PAY_* bitmask
+    pub allowed_instructions: u8,
+    /// Bitmask indexed by ReadingType
+    pub allowed_readings: u8,
+    /// Lamports deposited into the session
+    pub sol_limit: u64,
+    pub sol_spent: u64,
+    /// NEPTU delegated from the owner's account
+    pub neptu_limit: u64,
+    pub neptu_spent: u64,
+    /// Paid the session's rent, gets it back on revoke
+    pub rent_payer: Pubkey,
+}
+
+impl Session {
+    pub const SIZE: usize = 8 + 32 + 32 + 8 + 1 + 1 + 8 + 8 + 8 + 8 + 32;
+
+    /// Check the session may make this payment and count it against its limit
+    pub fn authorize(
+        &mut self,
+        now: i64,
+        instruction: u8,
+        reading_type: ReadingType,
+        currency: PaymentCurrency,
+        amount: u64,
+    ) -> Result<()> {
+        require!(now < self.expires_at, NeptuError::SessionExpired);
+        require!(
+            self.allowed_instructions & instruction != 0
+                && self.allowed_readings & (1 << reading_type as u8) != 0,
+            NeptuError::SessionNotAllowed
+        );
+        let (spent, limit) = match currency {
+            PaymentCurrency::Sol => (&mut self.sol_spent, self.sol_limit),
+            PaymentCurrency::Neptu => (&mut self.neptu_spent, self.neptu_limit),
+            PaymentCurrency::Token(_) => return err!(NeptuError::PaymentMintNotAccepted),
+        };
+        let total = spent.checked_add(amount).ok_or(NeptuError::Overflow)?;
+        require!(total <= limit, NeptuError::SessionLimitExceeded);
+        *spent = total;
+        Ok(())
+    }
+}
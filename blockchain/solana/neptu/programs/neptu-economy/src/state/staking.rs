@@ -0,0 +1,165 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::*, errors::NeptuError};
+
+/// Staking tier: thresholds and the benefits they unlock
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct StakeTier {
+    pub min_amount: u64,
+    /// Minimum committed lock duration in seconds
+    pub min_lock_duration: i64,
+    /// Price discount on SOL and NEPTU payments
+    pub price_discount_bps: u16,
+    /// Boost on NEPTU rewards for SOL payments
+    pub reward_boost_bps: u16,
+}
+
+impl StakeTier {
+    pub const SIZE: usize = 8 + 8 + 2 + 2;
+
+    pub fn discounted(&self, price: u64) -> u64 {
+        let discount = (price as u128 * self.price_discount_bps as u128) / BPS_DENOMINATOR as u128;
+        price - discount as u64
+    }
+
+    pub fn boosted(&self, reward: u64) -> Result<u64> {
+        let boost = (reward as u128 * self.reward_boost_bps as u128) / BPS_DENOMINATOR as u128;
+        reward
+            .checked_add(u64::try_from(boost).map_err(|_| error!(NeptuError::Overflow))?)
+            .ok_or_else(|| error!(NeptuError::Overflow))
+    }
+}
+
+/// NEPTU staking pool
+#[account]
+pub struct StakingPool {
+    /// Ordered from lowest to highest tier
+    pub tiers: Vec<StakeTier>,
+    /// Share of unstaked amount burned when unstaking before lock ends
+    pub early_unstake_penalty_bps: u16,
+    pub stake_vault: Pubkey,
+    pub total_staked: u64,
+    pub total_penalties_burned: u64,
+    /// Share of recycled NEPTU payments distributed to stakers
+    pub yield_bps: u16,
+    /// Yield per staked NEPTU, scaled by YIELD_PRECISION
+    pub acc_yield_per_share: u128,
+    pub total_yield_distributed: u64,
+    pub total_yield_claimed: u64,
+}
+
+impl StakingPool {
+    pub const SIZE: usize = 8
+        + 4 + (StakeTier::SIZE * MAX_STAKE_TIERS) // tiers
+        + 2 + 32 + 8 + 8 // penalty, vault, totals
+        + 2 + 16 + 8 + 8; // yield
+
+    pub fn set_tiers(
+        &mut self,
+        tiers: Vec<StakeTier>,
+        early_unstake_penalty_bps: u16,
+    ) -> Result<()> {
+        require!(
+            tiers.len() <= MAX_STAKE_TIERS,
+            NeptuError::InvalidStakeTiers
+        );
+        for pair in tiers.windows(2) {
+            require!(
+                pair[1].min_amount >= pair[0].min_amount
+                    && pair[1].min_lock_duration >= pair[0].min_lock_duration,
+                NeptuError::InvalidStakeTiers
+            );
+        }
+        for tier in &tiers {
+            require!(
+                u64::from(tier.price_discount_bps) <= BPS_DENOMINATOR,
+                NeptuError::InvalidStakeTiers
+            );
+        }
+        require!(
+            u64::from(early_unstake_penalty_bps) <= BPS_DENOMINATOR,
+            NeptuError::InvalidAmount
+        );
+        self.tiers = tiers;
+        self.early_unstake_penalty_bps = early_unstake_penalty_bps;
+        Ok(())
+    }
+
+    /// Highest tier the position qualifies for
+    /// Tiers requiring a lock only count while the position's lock is still running
+    pub fn tier_index(&self, position: &StakePosition, now: i64) -> Option<u8> {
+        self.tiers
+            .iter()
+            .rposition(|t| {
+                position.amount >= t.min_amount
+                    && position.lock_duration >= t.min_lock_duration
+                    && (t.min_lock_duration == 0 || position.lock_until > now)
+            })
+            .map(|i| i as u8)
+    }
+
+    pub fn tier_for(&self, position: &StakePosition, now: i64) -> StakeTier {
+        self.tier_index(position, now)
+            .map(|i| self.tiers[i as usize])
+            .unwrap_or_default()
+    }
+
+    /// Take the stakers' share of a recycled amount and credit it to the accumulator
+    /// Returns 0 (nothing taken) while nobody is staking
+    pub fn distribute_yield(&mut self, recycle_amount: u64) -> Result<u64> {
+        if self.total_staked == 0 || self.yield_bps == 0 {
+            return Ok(0);
+        }
+        let amount =
+            ((recycle_amount as u128 * self.yield_bps as u128) / BPS_DENOMINATOR as u128) as u64;
+        self.acc_yield_per_share = self
+            .acc_yield_per_share
+            .checked_add((amount as u128 * YIELD_PRECISION) / self.total_staked as u128)
+            .ok_or(NeptuError::Overflow)?;
+        self.total_yield_distributed = self
+            .total_yield_distributed
+            .checked_add(amount)
+            .ok_or(NeptuError::Overflow)?;
+        Ok(amount)
+    }
+}
+
+/// A user's staked NEPTU
+#[account]
+pub struct StakePosition {
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub staked_at: i64,
+    /// Longest lock committed to; determines tier eligibility
+    pub lock_duration: i64,
+    pub lock_until: i64,
+    /// Accumulator value already accounted for, scaled by YIELD_PRECISION
+    pub yield_debt: u128,
+    /// Settled yield not yet claimed
+    pub pending_yield: u64,
+}
+
+impl StakePosition {
+    pub const SIZE: usize = 8 + 32 + 8 + 8 + 8 + 8 + 16 + 8;
+
+    /// Move yield accrued since the last settlement into `pending_yield`
+    pub fn settle_yield(&mut self, acc_yield_per_share: u128) -> Result<()> {
+        let accrued = (self.amount as u128)
+            .checked_mul(acc_yield_per_share)
+            .ok_or(NeptuError::Overflow)?
+            .saturating_sub(self.yield_debt)
+            / YIELD_PRECISION;
+        self.pending_yield = self
+            .pending_yield
+            .checked_add(accrued as u64)
+            .ok_or(NeptuError::Overflow)?;
+        self.reset_yield_debt(acc_yield_per_share)
+    }
+
+    pub fn reset_yield_debt(&mut self, acc_yield_per_share: u128) -> Result<()> {
+        self.yield_debt = (self.amount as u128)
+            .checked_mul(acc_yield_per_share)
+            .ok_or(NeptuError::Overflow)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,135 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::*, errors::NeptuError, events::*, helpers::*};
+
+/// Program-owned vault holding protocol SOL revenue
+/// Withdrawals need M-of-N approvals and respect a per-period spending limit
+#[account]
+pub struct TreasuryVault {
+    pub approvers: Vec<Pubkey>,
+    pub threshold: u8,
+    /// Bumped when the config changes; stale proposals can't execute
+    pub config_version: u64,
+    /// Max lamports withdrawn per period
+    pub spending_limit: u64,
+    pub period_duration: i64,
+    pub period_start: i64,
+    pub period_spent: u64,
+    pub withdrawal_count: u64,
+    pub total_received: u64,
+    pub total_withdrawn: u64,
+    pub bump: u8,
+    pub config_proposal_count: u64,
+}
+
+impl TreasuryVault {
+    pub const SIZE: usize = 8
+        + 4 + (32 * MAX_TREASURY_APPROVERS) // approvers
+        + 1 + 8 // threshold + config version
+        + 8 + 8 + 8 + 8 // spending limit + period
+        + 8 + 8 + 8 // counters
+        + 1 // bump
+        + 8; // config proposal count
+
+    pub fn apply_config(
+        &mut self,
+        approvers: Vec<Pubkey>,
+        threshold: u8,
+        spending_limit: u64,
+        period_duration: i64,
+    ) -> Result<()> {
+        validate_member_set(&approvers, threshold, MAX_TREASURY_APPROVERS)?;
+        require!(period_duration > 0, NeptuError::InvalidAmount);
+        self.approvers = approvers;
+        self.threshold = threshold;
+        self.spending_limit = spending_limit;
+        self.period_duration = period_duration;
+        Ok(())
+    }
+
+    pub fn approver_index(&self, key: &Pubkey) -> Result<usize> {
+        self.approvers
+            .iter()
+            .position(|a| a == key)
+            .ok_or_else(|| error!(NeptuError::Unauthorized))
+    }
+
+    pub fn record_deposit(&mut self, from: Pubkey, amount: u64) -> Result<()> {
+        self.total_received = self
+            .total_received
+            .checked_add(amount)
+            .ok_or(NeptuError::Overflow)?;
+        emit!(TreasuryDeposited {
+            from,
+            amount,
+            total_received: self.total_received,
+        });
+        Ok(())
+    }
+
+    /// Count a withdrawal against the current period's spending limit
+    pub fn spend(&mut self, amount: u64, now: i64) -> Result<()> {
+        charge_spending_limit(
+            &mut self.period_start,
+            &mut self.period_spent,
+            self.spending_limit,
+            self.period_duration,
+            amount,
+            now,
+        )?;
+        self.total_withdrawn = self
+            .total_withdrawn
+            .checked_add(amount)
+            .ok_or(NeptuError::Overflow)?;
+        Ok(())
+    }
+}
+
+/// Pending treasury withdrawal
+#[account]
+pub struct WithdrawalProposal {
+    pub id: u64,
+    pub proposer: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    /// Off-chain accounting reference (e.g. hash of invoice)
+    pub memo: [u8; 32],
+    /// Bit `i` set = approver `i` approved
+    pub approvals: u16,
+    pub config_version: u64,
+    pub created_at: i64,
+    pub executed_at: i64,
+    /// Token mint for treasury token vault withdrawals, None for SOL
+    pub mint: Option<Pubkey>,
+}
+
+impl WithdrawalProposal {
+    pub const SIZE: usize = 8 + 8 + 32 + 32 + 8 + 32 + 2 + 8 + 8 + 8 + (1 + 32);
+}
+
+/// Pending change of treasury approvers and spending limits
+#[account]
+pub struct TreasuryConfigProposal {
+    pub id: u64,
+    pub proposer: Pubkey,
+    /// Payment mint whose spending limit changes; None changes the vault config
+    pub mint: Option<Pubkey>,
+    pub approvers: Vec<Pubkey>,
+    pub threshold: u8,
+    pub spending_limit: u64,
+    pub period_duration: i64,
+    /// Bit `i` set = approver `i` approved
+    pub approvals: u16,
+    pub config_version: u64,
+    pub created_at: i64,
+    pub executed_at: i64,
+}
+
+impl TreasuryConfigProposal {
+    pub const SIZE: usize = 8
+        + 8 + 32 // id + proposer
+        + (1 + 32) // mint
+        + 4 + (32 * MAX_TREASURY_APPROVERS) + 1 // approvers + threshold
+        + 8 + 8 // spending limit + period
+        + 2 + 8 + 8 + 8; // approvals + version + timestamps
+}
@@ -0,0 +1,576 @@
+use super::*;
+use crate::helpers::*;
+
+fn claim_record() -> ClaimRecord {
+    ClaimRecord {
+        user: Pubkey::default(),
+        nonce_base: 0,
+        nonce_bitmap: [0; NONCE_WINDOW_WORDS],
+        total_claimed: 0,
+        epoch: 0,
+        epoch_emitted: 0,
+    }
+}
+
+fn economy_state() -> EconomyState {
+    EconomyState::from(EconomyStateV0 {
+        authority: Pubkey::default(),
+        neptu_mint: Pubkey::default(),
+        treasury: Pubkey::default(),
+        ecosystem_pool: Pubkey::default(),
+        total_sol_collected: 0,
+        total_neptu_burned: 0,
+        total_neptu_rewarded: 0,
+    })
+}
+
+#[test]
+fn nonces_are_accepted_out_of_order() {
+    let mut record = claim_record();
+    for nonce in [5, 0, 200, 3, 63, 64, 1] {
+        record.consume_nonce(nonce).unwrap();
+    }
+    assert_eq!(record.nonce_base, 0);
+}
+
+#[test]
+fn replayed_nonce_is_rejected() {
+    let mut record = claim_record();
+    record.consume_nonce(7).unwrap();
+    assert_eq!(
+        record.consume_nonce(7).unwrap_err(),
+        NeptuError::NonceAlreadyUsed.into()
+    );
+}
+
+#[test]
+fn window_slides_past_its_end() {
+    let mut record = claim_record();
+    record.consume_nonce(NONCE_WINDOW - 1).unwrap();
+    record.consume_nonce(NONCE_WINDOW).unwrap();
+    assert_eq!(record.nonce_base, 1);
+
+    // The last nonce of the old window keeps its bit after the shift
+    assert_eq!(
+        record.consume_nonce(NONCE_WINDOW - 1).unwrap_err(),
+        NeptuError::NonceAlreadyUsed.into()
+    );
+    assert_eq!(
+        record.consume_nonce(0).unwrap_err(),
+        NeptuError::NonceOutOfWindow.into()
+    );
+    record.consume_nonce(1).unwrap();
+}
+
+#[test]
+fn window_slides_across_words() {
+    let mut record = claim_record();
+    record.consume_nonce(100).unwrap();
+    record.consume_nonce(130).unwrap();
+    record.consume_nonce(NONCE_WINDOW + 99).unwrap();
+    assert_eq!(record.nonce_base, 100);
+
+    assert_eq!(
+        record.consume_nonce(100).unwrap_err(),
+        NeptuError::NonceAlreadyUsed.into()
+    );
+    assert_eq!(
+        record.consume_nonce(130).unwrap_err(),
+        NeptuError::NonceAlreadyUsed.into()
+    );
+    record.consume_nonce(101).unwrap();
+}
+
+#[test]
+fn far_jump_clears_the_window() {
+    let mut record = claim_record();
+    record.consume_nonce(3).unwrap();
+    record.consume_nonce(10 * NONCE_WINDOW).unwrap();
+    assert_eq!(record.nonce_base, 9 * NONCE_WINDOW + 1);
+    assert_eq!(
+        record.nonce_bitmap[..NONCE_WINDOW_WORDS - 1],
+        [0; NONCE_WINDOW_WORDS - 1]
+    );
+    assert_eq!(
+        record.consume_nonce(3).unwrap_err(),
+        NeptuError::NonceOutOfWindow.into()
+    );
+}
+
+#[test]
+fn epoch_duration_change_never_reuses_epoch_numbers() {
+    let mut state = economy_state();
+    state.epoch_duration = 100;
+    state.epoch_user_cap = 50;
+    let mut record = claim_record();
+
+    state.reserve_emission(&mut record, 50, 1_000).unwrap();
+    assert_eq!(record.epoch, 10);
+
+    // Numbering restarts one past the current epoch, so old usage is dropped
+    state.set_epoch_duration(10, 1_050);
+    assert_eq!(state.current_epoch, 11);
+    assert_eq!(state.next_epoch_at(), 1_060);
+    let grant = state.reserve_emission(&mut record, 50, 1_055).unwrap();
+    assert_eq!(grant.granted, 50);
+    assert_eq!(record.epoch, 11);
+
+    state.roll_epoch(1_075);
+    assert_eq!(state.current_epoch, 13);
+}
+
+#[test]
+fn member_set_rejects_duplicates_and_bad_thresholds() {
+    let a = Pubkey::new_unique();
+    let b = Pubkey::new_unique();
+    validate_member_set(&[a, b], 2, 2).unwrap();
+    assert_eq!(
+        validate_member_set(&[a, a], 1, 2).unwrap_err(),
+        NeptuError::InvalidApprovers.into()
+    );
+    assert_eq!(
+        validate_member_set(&[a, b], 1, 1).unwrap_err(),
+        NeptuError::InvalidApprovers.into()
+    );
+    for threshold in [0, 3] {
+        assert_eq!(
+            validate_member_set(&[a, b], threshold, 2).unwrap_err(),
+            NeptuError::InvalidThreshold.into()
+        );
+    }
+}
+
+#[test]
+fn revoking_a_role_that_is_not_held_fails() {
+    let member = Pubkey::new_unique();
+    let mut roles = Roles {
+        assignments: Vec::new(),
+    };
+    assert_eq!(
+        roles.revoke(member, Role::Pauser).unwrap_err(),
+        NeptuError::RoleNotFound.into()
+    );
+    roles.grant(member, Role::Treasurer).unwrap();
+    assert_eq!(
+        roles.revoke(member, Role::Pauser).unwrap_err(),
+        NeptuError::RoleNotHeld.into()
+    );
+    roles.revoke(member, Role::Treasurer).unwrap();
+    assert!(roles.assignments.is_empty());
+}
+
+#[test]
+fn largest_parameter_change_fits_its_size() {
+    let change = ParameterChange::Price {
+        reading_type: ReadingType::Compatibility,
+        currency: PaymentCurrency::Token(Pubkey::new_unique()),
+        amount: u64::MAX,
+    };
+    assert_eq!(change.try_to_vec().unwrap().len(), ParameterChange::SIZE);
+}
+
+#[test]
+fn returned_reward_frees_only_the_current_epoch_budget() {
+    let mut state = economy_state();
+    state.epoch_duration = 100;
+    let mut record = claim_record();
+
+    state.reserve_emission(&mut record, 30, 1_010).unwrap();
+    state.release_emission(&mut record, 30, 1_010, 1_050);
+    assert_eq!((state.epoch_emitted, record.epoch_emitted), (0, 0));
+
+    state.reserve_emission(&mut record, 30, 1_020).unwrap();
+    state.reserve_emission(&mut record, 10, 1_110).unwrap();
+    // Emitted last epoch: this epoch's usage stays as is
+    state.release_emission(&mut record, 30, 1_020, 1_120);
+    assert_eq!((state.epoch_emitted, record.epoch_emitted), (10, 10));
+}
+
+fn sol_usd(price: i64, conf: u64, exponent: i32) -> PythPriceMessage {
+    PythPriceMessage {
+        price,
+        conf,
+        exponent,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn usd_price_converts_with_negative_exponent() {
+    // $150.00000000 per SOL: $15 buys 0.1 SOL
+    let feed = sol_usd(15_000_000_000, 0, -8);
+    assert_eq!(feed.lamports_for_cents(1_500, 200), Some(100_000_000));
+    // Rounds down to the lamport
+    assert_eq!(feed.lamports_for_cents(1, 200), Some(66_666));
+}
+
+#[test]
+fn usd_price_converts_with_positive_exponent() {
+    // 15 x 10^1 = $150 per SOL
+    let feed = sol_usd(15, 0, 1);
+    assert_eq!(feed.lamports_for_cents(1_500, 200), Some(100_000_000));
+    assert_eq!(sol_usd(15, 0, 40).lamports_for_cents(1_500, 200), None);
+}
+
+#[test]
+fn unusable_usd_price_is_rejected() {
+    assert_eq!(sol_usd(0, 0, -8).lamports_for_cents(1_500, 200), None);
+    assert_eq!(sol_usd(-1, 0, -8).lamports_for_cents(1_500, 200), None);
+    // Confidence of 2% passes a 2% limit, 2.01% does not
+    let price = 15_000_000_000;
+    assert!(sol_usd(price, 300_000_000, -8)
+        .lamports_for_cents(1_500, 200)
+        .is_some());
+    assert_eq!(
+        sol_usd(price, 301_500_000, -8).lamports_for_cents(1_500, 200),
+        None
+    );
+}
+
+#[test]
+fn only_fully_verified_pyth_updates_parse() {
+    let message = sol_usd(15_000_000_000, 1_000_000, -8);
+    let account = |level: PythVerificationLevel| {
+        let mut data = PYTH_PRICE_UPDATE_DISCRIMINATOR.to_vec();
+        Pubkey::new_unique().serialize(&mut data).unwrap();
+        level.serialize(&mut data).unwrap();
+        message.serialize(&mut data).unwrap();
+        42u64.serialize(&mut data).unwrap();
+        data
+    };
+
+    let data = account(PythVerificationLevel::Full);
+    assert_eq!(PythPriceMessage::parse(&data).unwrap(), message);
+    assert_eq!(
+        PythPriceMessage::parse(&account(PythVerificationLevel::Partial {
+            num_signatures: 5
+        }))
+        .unwrap_err(),
+        NeptuError::InvalidPriceFeed.into()
+    );
+    let mut foreign = data.clone();
+    foreign[0] ^= 1;
+    assert_eq!(
+        PythPriceMessage::parse(&foreign).unwrap_err(),
+        NeptuError::InvalidPriceFeed.into()
+    );
+    assert_eq!(
+        PythPriceMessage::parse(&data[..40]).unwrap_err(),
+        NeptuError::InvalidPriceFeed.into()
+    );
+}
+
+#[test]
+fn hybrid_payment_splits_pro_rata() {
+    // 1/3 paid in NEPTU: SOL share rounds up, reward share rounds down
+    assert_eq!(hybrid_shares(300, 1_000, 50, 100).unwrap(), (667, 33));
+    assert_eq!(hybrid_shares(300, 1_000, 50, 0).unwrap(), (1_000, 50));
+    assert_eq!(hybrid_shares(300, 1_000, 50, 300).unwrap(), (0, 0));
+    // Never charges less than the exact pro-rata share
+    for neptu_amount in 0..=7 {
+        let (sol_amount, reward) = hybrid_shares(7, 10, 10, neptu_amount).unwrap();
+        assert!(sol_amount * 7 >= 10 * (7 - neptu_amount));
+        assert!(reward * 7 <= 10 * (7 - neptu_amount));
+    }
+}
+
+#[test]
+fn hybrid_payment_rejects_overpaying_neptu() {
+    assert_eq!(
+        hybrid_shares(300, 1_000, 50, 301).unwrap_err(),
+        NeptuError::InvalidAmount.into()
+    );
+    assert_eq!(
+        hybrid_shares(0, 1_000, 50, 0).unwrap_err(),
+        NeptuError::InvalidAmount.into()
+    );
+}
+
+#[test]
+fn batch_shares_add_up_to_the_total() {
+    assert_eq!(pro_rata_shares(100, &[1, 1, 1]).unwrap(), vec![33, 33, 34]);
+    assert_eq!(pro_rata_shares(7, &[300, 100]).unwrap(), vec![5, 2]);
+    assert_eq!(pro_rata_shares(0, &[5, 5]).unwrap(), vec![0, 0]);
+    let shares = pro_rata_shares(999, &[13, 7, 1, 40]).unwrap();
+    assert_eq!(shares.iter().sum::<u64>(), 999);
+}
+
+#[test]
+fn consumed_credits_release_the_whole_escrow() {
+    let mut credits = Credits {
+        user: Pubkey::default(),
+        reading_type: ReadingType::Potensi,
+        currency: PaymentCurrency::Sol,
+        balance: 0,
+        escrowed: 0,
+        total_bought: 0,
+        total_consumed: 0,
+    };
+    credits.add(3, 100).unwrap();
+    // Rounds down per credit; the last credit takes the remainder
+    assert_eq!(credits.consume(1).unwrap(), 33);
+    assert_eq!(credits.consume(1).unwrap(), 33);
+    assert_eq!(credits.consume(1).unwrap(), 34);
+    assert_eq!((credits.balance, credits.escrowed), (0, 0));
+
+    credits.add(4, 10).unwrap();
+    assert_eq!(credits.consume(3).unwrap(), 7);
+    assert_eq!(
+        credits.consume(2).unwrap_err(),
+        NeptuError::InsufficientCredits.into()
+    );
+    assert_eq!(
+        credits.consume(0).unwrap_err(),
+        NeptuError::InsufficientCredits.into()
+    );
+    assert_eq!(credits.consume(1).unwrap(), 3);
+    assert_eq!(credits.total_consumed, 7);
+}
+
+#[test]
+fn session_payments_stay_within_scope_and_limits() {
+    let mut session = Session {
+        owner: Pubkey::default(),
+        session_key: Pubkey::default(),
+        expires_at: 1_000,
+        allowed_instructions: SESSION_PAY_WITH_SOL,
+        allowed_readings: 1 << ReadingType::AiChat as u8,
+        sol_limit: 10,
+        sol_spent: 0,
+        neptu_limit: 0,
+        neptu_spent: 0,
+        rent_payer: Pubkey::default(),
+    };
+    let pay = |session: &mut Session, now, instruction, reading_type, amount| {
+        session.authorize(now, instruction, reading_type, PaymentCurrency::Sol, amount)
+    };
+
+    pay(
+        &mut session,
+        0,
+        SESSION_PAY_WITH_SOL,
+        ReadingType::AiChat,
+        6,
+    )
+    .unwrap();
+    pay(
+        &mut session,
+        0,
+        SESSION_PAY_WITH_SOL,
+        ReadingType::AiChat,
+        4,
+    )
+    .unwrap();
+    assert_eq!(session.sol_spent, 10);
+    assert_eq!(
+        pay(
+            &mut session,
+            0,
+            SESSION_PAY_WITH_SOL,
+            ReadingType::AiChat,
+            1
+        )
+        .unwrap_err(),
+        NeptuError::SessionLimitExceeded.into()
+    );
+    assert_eq!(
+        session
+            .authorize(
+                0,
+                SESSION_PAY_WITH_SOL,
+                ReadingType::AiChat,
+                PaymentCurrency::Neptu,
+                1
+            )
+            .unwrap_err(),
+        NeptuError::SessionLimitExceeded.into()
+    );
+
+    session.sol_limit = 20;
+    assert_eq!(
+        pay(
+            &mut session,
+            0,
+            SESSION_PAY_WITH_SOL,
+            ReadingType::Potensi,
+            1
+        )
+        .unwrap_err(),
+        NeptuError::SessionNotAllowed.into()
+    );
+    assert_eq!(
+        pay(
+            &mut session,
+            0,
+            SESSION_PAY_WITH_NEPTU,
+            ReadingType::AiChat,
+            1
+        )
+        .unwrap_err(),
+        NeptuError::SessionNotAllowed.into()
+    );
+    assert_eq!(
+        pay(
+            &mut session,
+            1_000,
+            SESSION_PAY_WITH_SOL,
+            ReadingType::AiChat,
+            1
+        )
+        .unwrap_err(),
+        NeptuError::SessionExpired.into()
+    );
+    // Rejected payments are not counted
+    assert_eq!(session.sol_spent, 10);
+}
+
+fn staking_pool(yield_bps: u16) -> StakingPool {
+    StakingPool {
+        tiers: vec![],
+        early_unstake_penalty_bps: 0,
+        stake_vault: Pubkey::default(),
+        total_staked: 0,
+        total_penalties_burned: 0,
+        yield_bps,
+        acc_yield_per_share: 0,
+        total_yield_distributed: 0,
+        total_yield_claimed: 0,
+    }
+}
+
+fn stake(pool: &mut StakingPool, position: &mut StakePosition, amount: u64) {
+    position.settle_yield(pool.acc_yield_per_share).unwrap();
+    position.amount += amount;
+    pool.total_staked += amount;
+    position.reset_yield_debt(pool.acc_yield_per_share).unwrap();
+}
+
+fn stake_position() -> StakePosition {
+    StakePosition {
+        owner: Pubkey::default(),
+        amount: 0,
+        staked_at: 0,
+        lock_duration: 0,
+        lock_until: 0,
+        yield_debt: 0,
+        pending_yield: 0,
+    }
+}
+
+#[test]
+fn staking_yield_is_shared_pro_rata() {
+    let mut pool = staking_pool(2_000);
+    // Nothing is taken while nobody is staking
+    assert_eq!(pool.distribute_yield(1_000).unwrap(), 0);
+    assert_eq!(pool.acc_yield_per_share, 0);
+
+    let (mut alice, mut bob) = (stake_position(), stake_position());
+    stake(&mut pool, &mut alice, 300);
+    stake(&mut pool, &mut bob, 100);
+    assert_eq!(pool.distribute_yield(1_000).unwrap(), 200);
+
+    // Staking more after a distribution earns nothing from it
+    stake(&mut pool, &mut bob, 400);
+    assert_eq!(pool.distribute_yield(4_000).unwrap(), 800);
+
+    alice.settle_yield(pool.acc_yield_per_share).unwrap();
+    bob.settle_yield(pool.acc_yield_per_share).unwrap();
+    assert_eq!(
+        (alice.pending_yield, bob.pending_yield),
+        (150 + 300, 50 + 500)
+    );
+    assert_eq!(pool.total_yield_distributed, 1_000);
+
+    // Settling again adds nothing
+    alice.settle_yield(pool.acc_yield_per_share).unwrap();
+    assert_eq!(alice.pending_yield, 450);
+}
+
+#[test]
+fn neptu_refund_returns_the_staker_yield_with_the_burn() {
+    let mut pool = staking_pool(2_000);
+    pool.total_staked = 1;
+    let (amount, burned) = (1_000, 500);
+    let staker_yield = pool.distribute_yield(amount - burned).unwrap();
+    let receipt = Receipt {
+        payer: Pubkey::default(),
+        beneficiary: Pubkey::default(),
+        reading_type: ReadingType::Potensi,
+        currency: PaymentCurrency::Neptu,
+        amount,
+        issued_at: 0,
+        message_hash: None,
+        burned,
+        refunded: false,
+        reward: 0,
+        reward_recipient: Pubkey::default(),
+        staker_yield,
+        referral_bonus: 0,
+        quantity: 1,
+    };
+    // Only what reached the ecosystem pool comes back from it
+    assert_eq!(receipt.neptu_refund_sources(), (400, 600));
+}
+
+#[test]
+fn refunded_referral_bonus_is_withheld_from_the_next_ones() {
+    let mut stats = ReferrerStats {
+        referrer: Pubkey::default(),
+        referee_count: 1,
+        referred_payments: 0,
+        total_earned: 0,
+        clawback: 70,
+    };
+    assert_eq!(stats.clip_bonus(50, 1_000, 1_000), 0);
+    assert_eq!(stats.clip_bonus(50, 1_000, 1_000), 30);
+    assert_eq!(stats.clawback, 0);
+
+    // Clipped to the remaining cap and the pool before anything is withheld
+    stats.total_earned = 980;
+    stats.clawback = 5;
+    assert_eq!(stats.clip_bonus(50, 1_000, 1_000), 15);
+    stats.total_earned = 0;
+    assert_eq!(stats.clip_bonus(50, 1_000, 10), 10);
+}
+
+fn metering(balance: u64) -> MeteringAccount {
+    MeteringAccount {
+        user: Pubkey::default(),
+        currency: PaymentCurrency::Sol,
+        balance,
+        total_deposited: balance,
+        total_settled: 0,
+        last_period_end: 0,
+        withdrawal_requested_at: 0,
+    }
+}
+
+#[test]
+fn usage_beyond_the_deposit_is_unpaid() {
+    let mut metering = metering(100);
+    assert_eq!(metering.settle(6, 10, 0, 10).unwrap(), (60, 0));
+    assert_eq!(metering.settle(6, 10, 10, 20).unwrap(), (40, 20));
+    assert_eq!((metering.balance, metering.total_settled), (0, 100));
+    assert_eq!(metering.settle(1, 10, 20, 30).unwrap(), (0, 10));
+    assert_eq!(
+        metering.settle(1, 10, 25, 40).unwrap_err(),
+        NeptuError::InvalidUsagePeriod.into()
+    );
+    assert_eq!(
+        metering.settle(1, 10, 40, 40).unwrap_err(),
+        NeptuError::InvalidUsagePeriod.into()
+    );
+}
+
+#[test]
+fn withdrawal_request_cuts_off_later_periods() {
+    let mut metering = metering(100);
+    metering.withdrawal_requested_at = 50;
+    // A period already running at the request can still be settled
+    assert_eq!(metering.settle(1, 10, 40, 60).unwrap(), (10, 0));
+    assert_eq!(
+        metering.settle(1, 10, 60, 70).unwrap_err(),
+        NeptuError::WithdrawalPending.into()
+    );
+    assert_eq!((metering.balance, metering.last_period_end), (90, 60));
+}
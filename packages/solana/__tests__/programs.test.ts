@@ -0,0 +1,203 @@
+import { describe, expect, test } from "bun:test";
+
+import { AccountRole, address } from "@solana/kit";
+
+import {
+  ASSOCIATED_TOKEN_PROGRAM,
+  SYSTEM_PROGRAM,
+  TOKEN_PROGRAM,
+  buildClaimRewardsInstruction,
+  buildPayWithNeptuInstruction,
+  buildPayWithSolInstruction,
+  createNeptuPrograms,
+  derivePayerAccounts,
+} from "../src/programs";
+
+const user = address("9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM");
+const sponsor = address("4Nd1mBQtrMJVYVfKf2PJy9NZUZdTAsp7D4xWLs4gDB4T");
+const ecosystemPool = address("7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU");
+
+async function setup() {
+  const programs = await createNeptuPrograms("devnet");
+  const payer = await derivePayerAccounts(programs, user);
+  return { programs, payer };
+}
+
+describe("buildPayWithSolInstruction", () => {
+  test("matches the PayWithSol account order", async () => {
+    const { programs, payer } = await setup();
+    const ix = buildPayWithSolInstruction({
+      programs,
+      user,
+      readingType: "POTENSI",
+      ...payer,
+    });
+
+    const accounts = ix.accounts!;
+    expect(accounts).toHaveLength(26);
+    expect(accounts[0]).toEqual({
+      address: user,
+      role: AccountRole.WRITABLE_SIGNER,
+    });
+    // The user pays rent when no sponsor is given
+    expect(accounts[1].address).toBe(user);
+    expect(accounts[4].address).toBe(programs.economyStatePda);
+    expect(accounts[5].address).toBe(payer.claimRecordPda);
+    expect(accounts[6].address).toBe(programs.treasuryVaultPda);
+    expect(accounts[8].address).toBe(payer.userNeptuAccount);
+    expect(accounts[14].address).toBe(payer.userProfilePda);
+    expect(accounts[17].address).toBe(payer.referralPda);
+    expect(accounts.slice(-3).map((a) => a.address)).toEqual([
+      TOKEN_PROGRAM,
+      ASSOCIATED_TOKEN_PROGRAM,
+      SYSTEM_PROGRAM,
+    ]);
+  });
+
+  test("passes omitted optional accounts as the program ID", async () => {
+    const { programs, payer } = await setup();
+    const ix = buildPayWithSolInstruction({
+      programs,
+      user,
+      readingType: "AI_CHAT",
+      ...payer,
+    });
+
+    const accounts = ix.accounts!;
+    for (const index of [3, 11, 12, 13, 15, 16, 18, 19, 20, 21, 22]) {
+      expect(accounts[index]).toEqual({
+        address: programs.economyProgramId,
+        role: AccountRole.READONLY,
+      });
+    }
+    expect(Array.from(ix.data!.slice(8))).toEqual([2]);
+  });
+
+  test("passes the staking pool along with the stake position", async () => {
+    const { programs, payer } = await setup();
+    const stakePositionPda = address(
+      "Hx6LbkMHe69DYawhPyVNs8Apa6tyfogfzQV6a7XkwBUU"
+    );
+    const ix = buildPayWithSolInstruction({
+      programs,
+      user,
+      readingType: "POTENSI",
+      ...payer,
+      feePayer: sponsor,
+      stakePositionPda,
+    });
+
+    const accounts = ix.accounts!;
+    expect(accounts[1]).toEqual({
+      address: sponsor,
+      role: AccountRole.WRITABLE_SIGNER,
+    });
+    expect(accounts[12].address).toBe(programs.stakingPoolPda);
+    expect(accounts[13].address).toBe(stakePositionPda);
+  });
+});
+
+describe("buildPayWithNeptuInstruction", () => {
+  test("matches the PayWithNeptu account order", async () => {
+    const { programs, payer } = await setup();
+    const ix = buildPayWithNeptuInstruction({
+      programs,
+      user,
+      readingType: "PELUANG",
+      ...payer,
+      ecosystemPool,
+    });
+
+    const accounts = ix.accounts!;
+    expect(accounts).toHaveLength(23);
+    expect(accounts[3].address).toBe(programs.economyStatePda);
+    expect(accounts[6].address).toBe(ecosystemPool);
+    expect(accounts[7].address).toBe(programs.stakingPoolPda);
+    expect(accounts[9].address).toBe(payer.userProfilePda);
+    expect(accounts[12].address).toBe(programs.stakeVaultPda);
+    expect(accounts[15].address).toBe(payer.referralPda);
+    expect(accounts[19].address).toBe(SYSTEM_PROGRAM);
+    expect(accounts[22].address).toBe(TOKEN_PROGRAM);
+    expect(Array.from(ix.data!.slice(8))).toEqual([1]);
+  });
+
+  test("skips the staking accounts before staking is set up", async () => {
+    const { programs, payer } = await setup();
+    const ix = buildPayWithNeptuInstruction({
+      programs,
+      user,
+      readingType: "PELUANG",
+      ...payer,
+      ecosystemPool,
+      stakingEnabled: false,
+    });
+
+    const accounts = ix.accounts!;
+    expect(accounts[7].address).toBe(programs.economyProgramId);
+    expect(accounts[12].address).toBe(programs.economyProgramId);
+  });
+
+  test("passes the rewards pool only with referrer accounts", async () => {
+    const { programs, payer } = await setup();
+    const referrer = {
+      referrerStats: address("Hx6LbkMHe69DYawhPyVNs8Apa6tyfogfzQV6a7XkwBUU"),
+      referrerClaimRecord: address(
+        "4Nd1mBQtrMJVYVfKf2PJy9NZUZdTAsp7D4xWLs4gDB4T"
+      ),
+      referrerNeptuAccount: address(
+        "7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU"
+      ),
+    };
+    const ix = buildPayWithNeptuInstruction({
+      programs,
+      user,
+      readingType: "PELUANG",
+      ...payer,
+      ecosystemPool,
+      referrer,
+    });
+
+    const accounts = ix.accounts!;
+    expect(accounts[13].address).toBe(programs.rewardsPoolAta);
+    expect(accounts.slice(16, 19).map((a) => a.address)).toEqual([
+      referrer.referrerStats,
+      referrer.referrerClaimRecord,
+      referrer.referrerNeptuAccount,
+    ]);
+  });
+});
+
+describe("buildClaimRewardsInstruction", () => {
+  test("encodes expires_at before the signature", async () => {
+    const { programs, payer } = await setup();
+    const ix = buildClaimRewardsInstruction({
+      programs,
+      user,
+      userNeptuAccount: payer.userNeptuAccount,
+      claimRecordPda: payer.claimRecordPda,
+      userProfilePda: payer.userProfilePda,
+      claimSigner: sponsor,
+      amount: 5n,
+      nonce: 7n,
+      expiresAt: 1_700_000_000n,
+      signature: new Uint8Array(64).fill(9),
+    });
+
+    const data = ix.data!;
+    const view = new DataView(data.buffer, data.byteOffset);
+    expect(data).toHaveLength(96);
+    expect(view.getBigUint64(8, true)).toBe(5n);
+    expect(view.getBigUint64(16, true)).toBe(7n);
+    expect(view.getBigInt64(24, true)).toBe(1_700_000_000n);
+    expect(data[32]).toBe(9);
+
+    const accounts = ix.accounts!;
+    expect(accounts).toHaveLength(15);
+    expect(accounts[2]).toEqual({
+      address: sponsor,
+      role: AccountRole.READONLY_SIGNER,
+    });
+    expect(accounts[4].address).toBe(payer.userProfilePda);
+    expect(accounts[5].address).toBe(programs.economyStatePda);
+  });
+});
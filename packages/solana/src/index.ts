@@ -61,6 +61,14 @@ export {
   // PDA helpers
   deriveAssociatedTokenAddress,
   deriveClaimRecordPda,
+  deriveUserProfilePda,
+  deriveReferralPda,
+  deriveReferrerStatsPda,
+  derivePendingRewardPda,
+  deriveStakePositionPda,
+  deriveSponsoredRentPda,
+  deriveReceiptPda,
+  derivePayerAccounts,
   // Instruction builders
   buildPayWithSolInstruction,
   buildPayWithNeptuInstruction,
@@ -71,6 +79,10 @@ export {
   // Types
   type NeptuPrograms,
   type ClaimRewardsParams,
+  type PayWithSolParams,
+  type PayWithNeptuParams,
+  type PayerAccounts,
+  type ReferrerAccounts,
   type PricingConfig,
   type EconomyState,
 } from "./programs";
@@ -13,6 +13,7 @@ import {
   type Instruction,
   type AccountRole,
   getU64Encoder,
+  getI64Encoder,
   getU64Decoder,
   getStructDecoder,
   type ReadonlyUint8Array,
@@ -107,6 +108,11 @@ export interface NeptuPrograms {
   economyStatePda: Address;
   pricingConfigPda: Address;
   rewardsPoolAta: Address;
+  treasuryVaultPda: Address;
+  stakingPoolPda: Address;
+  stakeVaultPda: Address;
+  loyaltyProgramPda: Address;
+  rentSponsorshipPda: Address;
 }
 
 export interface PricingConfig {
@@ -201,6 +207,104 @@ export async function deriveClaimRecordPda(
   return pda;
 }
 
+export async function deriveUserProfilePda(
+  user: Address,
+  programId: Address
+): Promise<Address> {
+  const encoder = getUtf8Encoder();
+  const addressEncoder = getAddressEncoder();
+
+  const [pda] = await getProgramDerivedAddress({
+    programAddress: programId,
+    seeds: [encoder.encode("profile"), addressEncoder.encode(user)],
+  });
+
+  return pda;
+}
+
+async function deriveUserPda(
+  seed: string,
+  user: Address,
+  programId: Address
+): Promise<Address> {
+  return derivePda(
+    [seed, new Uint8Array(getAddressEncoder().encode(user))],
+    programId
+  );
+}
+
+export async function deriveReferralPda(
+  user: Address,
+  programId: Address
+): Promise<Address> {
+  return deriveUserPda("referral", user, programId);
+}
+
+export async function derivePendingRewardPda(
+  user: Address,
+  programId: Address
+): Promise<Address> {
+  return deriveUserPda("pending_reward", user, programId);
+}
+
+export async function deriveStakePositionPda(
+  user: Address,
+  programId: Address
+): Promise<Address> {
+  return deriveUserPda("stake", user, programId);
+}
+
+export async function deriveSponsoredRentPda(
+  user: Address,
+  programId: Address
+): Promise<Address> {
+  return deriveUserPda("sponsored_rent", user, programId);
+}
+
+export async function deriveReferrerStatsPda(
+  referrer: Address,
+  programId: Address
+): Promise<Address> {
+  return deriveUserPda("referrer", referrer, programId);
+}
+
+// Receipts are indexed by the payer's `receipts_paid` counter in UserProfile
+export async function deriveReceiptPda(
+  payer: Address,
+  index: bigint,
+  programId: Address
+): Promise<Address> {
+  return derivePda(
+    [
+      "receipt",
+      new Uint8Array(getAddressEncoder().encode(payer)),
+      new Uint8Array(getU64Encoder().encode(index)),
+    ],
+    programId
+  );
+}
+
+// Per-user PDAs every payment instruction takes
+export interface PayerAccounts {
+  userNeptuAccount: Address;
+  claimRecordPda: Address;
+  userProfilePda: Address;
+  referralPda: Address;
+}
+
+export async function derivePayerAccounts(
+  programs: NeptuPrograms,
+  user: Address
+): Promise<PayerAccounts> {
+  const programId = programs.economyProgramId;
+  return {
+    userNeptuAccount: await deriveAssociatedTokenAddress(user, programs.mintPda),
+    claimRecordPda: await deriveClaimRecordPda(user, programId),
+    userProfilePda: await deriveUserProfilePda(user, programId),
+    referralPda: await deriveReferralPda(user, programId),
+  };
+}
+
 // ============================================================================
 // CREATE NEPTU PROGRAMS CONTEXT
 // ============================================================================
@@ -227,6 +331,17 @@ export async function createNeptuPrograms(
     economyAuthorityPda,
     mintPda
   );
+  const treasuryVaultPda = await derivePda(
+    ["treasury_vault"],
+    programIds.economy
+  );
+  const stakingPoolPda = await derivePda(["staking_pool"], programIds.economy);
+  const stakeVaultPda = await derivePda(["stake_vault"], programIds.economy);
+  const loyaltyProgramPda = await derivePda(["loyalty"], programIds.economy);
+  const rentSponsorshipPda = await derivePda(
+    ["rent_sponsorship"],
+    programIds.economy
+  );
 
   return {
     network,
@@ -238,6 +353,11 @@ export async function createNeptuPrograms(
     economyStatePda,
     pricingConfigPda,
     rewardsPoolAta,
+    treasuryVaultPda,
+    stakingPoolPda,
+    stakeVaultPda,
+    loyaltyProgramPda,
+    rentSponsorshipPda,
   };
 }
 
@@ -269,17 +389,72 @@ function createAccountMeta(
   return { address: pubkey, role };
 }
 
+// Anchor reads the program ID in an Option<Account> slot as None
+function optionalAccountMeta(
+  programs: NeptuPrograms,
+  pubkey: Address | undefined,
+  options: { isSigner?: boolean; isWritable?: boolean } = {}
+): AccountMeta {
+  return pubkey
+    ? createAccountMeta(pubkey, options)
+    : createAccountMeta(programs.economyProgramId);
+}
+
 // ============================================================================
 // INSTRUCTION BUILDERS
 // ============================================================================
 
-export function buildPayWithSolInstruction(
-  programs: NeptuPrograms,
-  user: Address,
-  treasury: Address,
-  userNeptuAccount: Address,
-  readingType: ReadingType
-): Instruction {
+// Referrer accounts, required once the payer's referral is bound
+export interface ReferrerAccounts {
+  referrerStats: Address;
+  referrerClaimRecord: Address;
+  referrerNeptuAccount: Address;
+}
+
+// Optional accounts are passed as the program ID (Anchor's None) when omitted
+interface PaymentParams extends PayerAccounts {
+  programs: NeptuPrograms;
+  user: Address;
+  readingType: ReadingType;
+  // Defaults to the user; a sponsor wallet pays rent when set
+  feePayer?: Address;
+  // Applies the stake tier; the staking pool is passed along with it
+  stakePositionPda?: Address;
+  // Applies the loyalty tier discount
+  loyaltyProgram?: Address;
+  // Issues a refundable receipt at the payer's next receipt index
+  receiptPda?: Address;
+  referrer?: ReferrerAccounts;
+  // Rent sponsorship vault and per-user record, only with a sponsored fee payer
+  rentSponsorship?: Address;
+  sponsoredRentPda?: Address;
+}
+
+export interface PayWithSolParams extends PaymentParams {
+  // Pyth SOL/USD update, required for USD-priced readings
+  priceFeed?: Address;
+  // IOU record for rewards the pool cannot cover
+  pendingRewardPda?: Address;
+}
+
+export function buildPayWithSolInstruction({
+  programs,
+  user,
+  readingType,
+  userNeptuAccount,
+  claimRecordPda,
+  userProfilePda,
+  referralPda,
+  feePayer = user,
+  priceFeed,
+  pendingRewardPda,
+  stakePositionPda,
+  loyaltyProgram,
+  receiptPda,
+  referrer,
+  rentSponsorship,
+  sponsoredRentPda,
+}: PayWithSolParams): Instruction {
   const data = new Uint8Array(9);
   data.set(DISCRIMINATORS.payWithSol, 0);
   data[8] = getReadingTypeIndex(readingType);
@@ -288,12 +463,29 @@ export function buildPayWithSolInstruction(
     programAddress: programs.economyProgramId,
     accounts: [
       createAccountMeta(user, { isSigner: true, isWritable: true }),
+      createAccountMeta(feePayer, { isSigner: true, isWritable: true }),
       createAccountMeta(programs.pricingConfigPda),
-      createAccountMeta(treasury, { isWritable: true }),
+      optionalAccountMeta(programs, priceFeed),
+      createAccountMeta(programs.economyStatePda, { isWritable: true }),
+      createAccountMeta(claimRecordPda, { isWritable: true }),
+      createAccountMeta(programs.treasuryVaultPda, { isWritable: true }),
       createAccountMeta(programs.mintPda, { isWritable: true }),
       createAccountMeta(userNeptuAccount, { isWritable: true }),
       createAccountMeta(programs.rewardsPoolAta, { isWritable: true }),
       createAccountMeta(programs.economyAuthorityPda),
+      optionalAccountMeta(programs, pendingRewardPda, { isWritable: true }),
+      optionalAccountMeta(
+        programs,
+        stakePositionPda ? programs.stakingPoolPda : undefined
+      ),
+      optionalAccountMeta(programs, stakePositionPda),
+      createAccountMeta(userProfilePda, { isWritable: true }),
+      optionalAccountMeta(programs, loyaltyProgram),
+      optionalAccountMeta(programs, receiptPda, { isWritable: true }),
+      createAccountMeta(referralPda, { isWritable: true }),
+      ...referrerAccountMetas(programs, referrer),
+      optionalAccountMeta(programs, rentSponsorship, { isWritable: true }),
+      optionalAccountMeta(programs, sponsoredRentPda, { isWritable: true }),
       createAccountMeta(TOKEN_PROGRAM),
       createAccountMeta(ASSOCIATED_TOKEN_PROGRAM),
       createAccountMeta(SYSTEM_PROGRAM),
@@ -302,13 +494,29 @@ export function buildPayWithSolInstruction(
   };
 }
 
-export function buildPayWithNeptuInstruction(
-  programs: NeptuPrograms,
-  user: Address,
-  userNeptuAccount: Address,
-  ecosystemPool: Address,
-  readingType: ReadingType
-): Instruction {
+export interface PayWithNeptuParams extends PaymentParams {
+  ecosystemPool: Address;
+  // Shares the recycled amount with stakers; false before initialize_staking
+  stakingEnabled?: boolean;
+}
+
+export function buildPayWithNeptuInstruction({
+  programs,
+  user,
+  readingType,
+  userNeptuAccount,
+  userProfilePda,
+  referralPda,
+  ecosystemPool,
+  stakingEnabled = true,
+  feePayer = user,
+  stakePositionPda,
+  loyaltyProgram,
+  receiptPda,
+  referrer,
+  rentSponsorship,
+  sponsoredRentPda,
+}: PayWithNeptuParams): Instruction {
   const data = new Uint8Array(9);
   data.set(DISCRIMINATORS.payWithNeptu, 0);
   data[8] = getReadingTypeIndex(readingType);
@@ -316,25 +524,77 @@ export function buildPayWithNeptuInstruction(
   return {
     programAddress: programs.economyProgramId,
     accounts: [
-      createAccountMeta(user, { isSigner: true }),
+      createAccountMeta(user, { isSigner: true, isWritable: true }),
+      createAccountMeta(feePayer, { isSigner: true, isWritable: true }),
       createAccountMeta(programs.pricingConfigPda),
+      createAccountMeta(programs.economyStatePda, { isWritable: true }),
       createAccountMeta(programs.mintPda, { isWritable: true }),
       createAccountMeta(userNeptuAccount, { isWritable: true }),
       createAccountMeta(ecosystemPool, { isWritable: true }),
+      optionalAccountMeta(
+        programs,
+        stakingEnabled ? programs.stakingPoolPda : undefined,
+        { isWritable: true }
+      ),
+      optionalAccountMeta(programs, stakePositionPda),
+      createAccountMeta(userProfilePda, { isWritable: true }),
+      optionalAccountMeta(programs, loyaltyProgram),
+      optionalAccountMeta(programs, receiptPda, { isWritable: true }),
+      optionalAccountMeta(
+        programs,
+        stakingEnabled ? programs.stakeVaultPda : undefined,
+        { isWritable: true }
+      ),
+      optionalAccountMeta(
+        programs,
+        referrer ? programs.rewardsPoolAta : undefined,
+        { isWritable: true }
+      ),
+      createAccountMeta(programs.economyAuthorityPda),
+      createAccountMeta(referralPda, { isWritable: true }),
+      ...referrerAccountMetas(programs, referrer),
+      createAccountMeta(SYSTEM_PROGRAM),
+      optionalAccountMeta(programs, rentSponsorship, { isWritable: true }),
+      optionalAccountMeta(programs, sponsoredRentPda, { isWritable: true }),
       createAccountMeta(TOKEN_PROGRAM),
     ],
     data,
   };
 }
 
+function referrerAccountMetas(
+  programs: NeptuPrograms,
+  referrer: ReferrerAccounts | undefined
+): AccountMeta[] {
+  return [
+    optionalAccountMeta(programs, referrer?.referrerStats, {
+      isWritable: true,
+    }),
+    optionalAccountMeta(programs, referrer?.referrerClaimRecord, {
+      isWritable: true,
+    }),
+    optionalAccountMeta(programs, referrer?.referrerNeptuAccount, {
+      isWritable: true,
+    }),
+  ];
+}
+
 export interface ClaimRewardsParams {
   programs: NeptuPrograms;
   user: Address;
   userNeptuAccount: Address;
   claimRecordPda: Address;
+  userProfilePda: Address;
+  claimSigner: Address;
   amount: bigint;
   nonce: bigint;
+  expiresAt: bigint;
   signature?: Uint8Array;
+  // Defaults to the user; a sponsor wallet pays rent when set
+  feePayer?: Address;
+  // Rent sponsorship vault and per-user record, only with a sponsored fee payer
+  rentSponsorship?: Address;
+  sponsoredRentPda?: Address;
 }
 
 export function buildClaimRewardsInstruction({
@@ -342,28 +602,41 @@ export function buildClaimRewardsInstruction({
   user,
   userNeptuAccount,
   claimRecordPda,
+  userProfilePda,
+  claimSigner,
   amount,
   nonce,
+  expiresAt,
   signature = new Uint8Array(64),
+  feePayer = user,
+  rentSponsorship,
+  sponsoredRentPda,
 }: ClaimRewardsParams): Instruction {
-  // Build instruction data: discriminator + amount(u64) + nonce(u64) + signature(64 bytes)
-  const data = new Uint8Array(8 + 8 + 8 + 64);
+  // Build instruction data: discriminator + amount(u64) + nonce(u64) + expires_at(i64) + signature(64 bytes)
+  const data = new Uint8Array(8 + 8 + 8 + 8 + 64);
   data.set(DISCRIMINATORS.claimRewards, 0);
 
   const u64Encoder = getU64Encoder();
   data.set(u64Encoder.encode(amount), 8);
   data.set(u64Encoder.encode(nonce), 16);
-  data.set(signature, 24);
+  data.set(getI64Encoder().encode(expiresAt), 24);
+  data.set(signature, 32);
 
   return {
     programAddress: programs.economyProgramId,
     accounts: [
       createAccountMeta(user, { isSigner: true, isWritable: true }),
+      createAccountMeta(feePayer, { isSigner: true, isWritable: true }),
+      createAccountMeta(claimSigner, { isSigner: true }),
       createAccountMeta(claimRecordPda, { isWritable: true }),
+      createAccountMeta(userProfilePda, { isWritable: true }),
+      createAccountMeta(programs.economyStatePda, { isWritable: true }),
       createAccountMeta(programs.mintPda, { isWritable: true }),
       createAccountMeta(userNeptuAccount, { isWritable: true }),
       createAccountMeta(programs.rewardsPoolAta, { isWritable: true }),
       createAccountMeta(programs.economyAuthorityPda),
+      optionalAccountMeta(programs, rentSponsorship, { isWritable: true }),
+      optionalAccountMeta(programs, sponsoredRentPda, { isWritable: true }),
       createAccountMeta(TOKEN_PROGRAM),
       createAccountMeta(ASSOCIATED_TOKEN_PROGRAM),
       createAccountMeta(SYSTEM_PROGRAM),